@@ -0,0 +1,320 @@
+//! In-process bank for testing Solana programs compiled for the host.
+//!
+//! Transactions are processed by the bank of the Solana 1.9 runtime, with
+//! its system program, BPF loaders and the SPL programs bundled with
+//! `solana-program-test`. Programs under test are registered by their
+//! entrypoint function, e.g. `smallet::entry`, and run natively: the runtime
+//! invokes them like BPF programs, see [native].
+//!
+//! Transactions are not signed: the accounts marked as signers by the
+//! instructions must be listed as signers when processing them, and the logs
+//! of successful transactions are returned, including Anchor events. The
+//! clock can be warped at will.
+//!
+//! `solana_program_test::ProgramTest` is not used directly because its
+//! `BanksClient` needs the keypair of every signer, e.g. of the bases of
+//! smallets, and only returns the logs of failed transactions. Its
+//! `processor!` shim does not pass the executable accounts of a
+//! cross-program invocation to the callee, so an Anchor 0.24 `init` of an
+//! account through the system program fails with `MissingAccount`, and it
+//! neither supports `sol_log_data` nor resizing accounts in invoked programs.
+
+mod native;
+
+use std::fmt;
+
+use solana_program::account_info::AccountInfo;
+use solana_program::clock::{Clock, MAX_PROCESSING_AGE};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::instruction::Instruction;
+use solana_program::message::Message;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable};
+use solana_program_runtime::timings::ExecuteTimings;
+use solana_runtime::bank::{Bank as RuntimeBank, TransactionExecutionResult};
+use solana_runtime::genesis_utils::create_genesis_config;
+use solana_sdk::account::{AccountSharedData, ReadableAccount};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::Transaction;
+
+/// Entrypoint of a program.
+pub type ProcessInstruction = fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult;
+
+/// The native loader, owner of builtin programs.
+pub const NATIVE_LOADER_ID: Pubkey = solana_sdk::native_loader::ID;
+
+/// An account stored in the [Bank].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl Account {
+    /// Creates a rent exempt account holding `data`.
+    pub fn new_data(data: Vec<u8>, owner: &Pubkey) -> Self {
+        Self {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: *owner,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<AccountSharedData> for Account {
+    fn from(account: AccountSharedData) -> Self {
+        Self {
+            lamports: account.lamports(),
+            data: account.data().to_vec(),
+            owner: *account.owner(),
+            executable: account.executable(),
+            rent_epoch: account.rent_epoch(),
+        }
+    }
+}
+
+impl From<Account> for AccountSharedData {
+    fn from(account: Account) -> Self {
+        solana_sdk::account::Account {
+            lamports: account.lamports,
+            data: account.data,
+            owner: account.owner,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+        .into()
+    }
+}
+
+/// Why a transaction failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InstructionError {
+    /// The instruction failed with an error a program can return.
+    Program(ProgramError),
+    /// An account marked as signer was not listed as a signer of the
+    /// transaction.
+    MissingSigner(Pubkey),
+    /// The runtime rejected the instruction, e.g. because an account not
+    /// marked as writable was modified.
+    Runtime(solana_sdk::instruction::InstructionError),
+    /// The runtime rejected the transaction.
+    Transaction(solana_sdk::transaction::TransactionError),
+}
+
+/// A failed transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionError {
+    /// Index of the failing instruction.
+    pub index: usize,
+    pub error: InstructionError,
+    pub logs: Vec<String>,
+}
+
+impl TransactionError {
+    /// The program error, if the failure was returned by a program.
+    pub fn program_error(&self) -> Option<&ProgramError> {
+        match &self.error {
+            InstructionError::Program(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "instruction {} failed: {:?}", self.index, self.error)?;
+        for line in &self.logs {
+            writeln!(f, "  {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of processing a transaction: its logs, or why it failed.
+pub type TransactionResult = Result<Vec<String>, TransactionError>;
+
+/// Builder of a [Bank].
+#[derive(Default)]
+pub struct ProgramTest {
+    programs: Vec<(Pubkey, ProcessInstruction)>,
+    accounts: Vec<(Pubkey, Account)>,
+}
+
+impl ProgramTest {
+    /// Registers a program. It replaces the bundled SPL program of the same
+    /// id, if any.
+    pub fn add_program(mut self, program_id: Pubkey, process: ProcessInstruction) -> Self {
+        self.programs.push((program_id, process));
+        self
+    }
+
+    /// Adds an account to the genesis of the bank.
+    pub fn add_account(mut self, address: Pubkey, account: Account) -> Self {
+        self.accounts.push((address, account));
+        self
+    }
+
+    /// Creates the bank.
+    pub fn start(self) -> Bank {
+        let mut genesis_config = create_genesis_config(0).genesis_config;
+        genesis_config.rent = Rent::default();
+        let mut bank = RuntimeBank::new_for_tests(&genesis_config);
+
+        bank.add_builtin(
+            "solana_bpf_loader_deprecated_program",
+            &bpf_loader_deprecated::id(),
+            solana_bpf_loader_program::process_instruction,
+        );
+        bank.add_builtin(
+            "solana_bpf_loader_program",
+            &bpf_loader::id(),
+            solana_bpf_loader_program::process_instruction,
+        );
+        bank.add_builtin(
+            "solana_bpf_loader_upgradeable_program",
+            &bpf_loader_upgradeable::id(),
+            solana_bpf_loader_program::process_instruction,
+        );
+        for (program_id, account) in solana_program_test::programs::spl_programs(&Rent::default()) {
+            bank.store_account(&program_id, &account);
+        }
+        for (program_id, process) in self.programs {
+            native::register(program_id, process);
+            bank.add_builtin(
+                &program_id.to_string(),
+                &program_id,
+                native::process_instruction,
+            );
+        }
+        for (address, account) in self.accounts {
+            bank.store_account(&address, &account.into());
+        }
+        bank.set_sysvar_for_tests(&Clock {
+            slot: 1,
+            unix_timestamp: 1_650_000_000,
+            ..Clock::default()
+        });
+        Bank { bank }
+    }
+}
+
+/// Accounts, registered programs and the clock.
+pub struct Bank {
+    bank: RuntimeBank,
+}
+
+impl Bank {
+    /// Fetches an account.
+    pub fn get_account(&self, address: &Pubkey) -> Option<Account> {
+        self.bank.get_account(address).map(Account::from)
+    }
+
+    /// Creates or replaces an account.
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.bank.store_account(&address, &account.into());
+    }
+
+    /// Lamports held by an account.
+    pub fn lamports(&self, address: &Pubkey) -> u64 {
+        self.bank.get_balance(address)
+    }
+
+    /// Credits lamports to an account, creating it if needed.
+    pub fn airdrop(&mut self, address: &Pubkey, lamports: u64) {
+        let mut account = self.get_account(address).unwrap_or_default();
+        account.lamports += lamports;
+        self.set_account(*address, account);
+    }
+
+    /// The current clock.
+    pub fn clock(&self) -> Clock {
+        self.bank.clock()
+    }
+
+    /// The rent parameters.
+    pub fn rent(&self) -> Rent {
+        self.bank.rent_collector().rent
+    }
+
+    /// Sets the unix timestamp of the clock, advancing the slot.
+    pub fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        let clock = self.clock();
+        self.bank.set_sysvar_for_tests(&Clock {
+            slot: clock.slot + 1,
+            unix_timestamp,
+            ..clock
+        });
+    }
+
+    /// Advances the clock by `seconds`.
+    pub fn advance_clock(&mut self, seconds: i64) {
+        self.warp_to_timestamp(self.clock().unix_timestamp + seconds);
+    }
+
+    /// Processes the instructions of a transaction signed by `signers`, the
+    /// first of which pays for it. Either every instruction succeeds, or no
+    /// account is modified.
+    pub fn process_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[Pubkey],
+    ) -> TransactionResult {
+        for (index, ix) in instructions.iter().enumerate() {
+            for meta in &ix.accounts {
+                if meta.is_signer && !signers.contains(&meta.pubkey) {
+                    return Err(TransactionError {
+                        index,
+                        error: InstructionError::MissingSigner(meta.pubkey),
+                        logs: vec![],
+                    });
+                }
+            }
+        }
+
+        // The bank does not verify signatures, and forgetting the processed
+        // ones allows to process the same instructions again.
+        self.bank.clear_signatures();
+        let mut message = Message::new(instructions, signers.first());
+        message.recent_blockhash = self.bank.last_blockhash();
+        let transaction = Transaction {
+            signatures: (0..message.header.num_required_signatures)
+                .map(|_| Signature::new_unique())
+                .collect(),
+            message,
+        };
+        let batch = self.bank.prepare_batch_for_tests(vec![transaction]);
+        let (mut results, _) = self.bank.load_execute_and_commit_transactions(
+            &batch,
+            MAX_PROCESSING_AGE,
+            false,
+            false,
+            true,
+            &mut ExecuteTimings::default(),
+        );
+
+        let (status, logs) = match results.execution_results.remove(0) {
+            TransactionExecutionResult::Executed { details, .. } => {
+                (details.status, details.log_messages.unwrap_or_default())
+            }
+            TransactionExecutionResult::NotExecuted(error) => (Err(error), vec![]),
+        };
+        status.map(|_| logs.clone()).map_err(|error| {
+            let (index, error) = match error {
+                solana_sdk::transaction::TransactionError::InstructionError(index, error) => (
+                    index as usize,
+                    ProgramError::try_from(error.clone())
+                        .map(InstructionError::Program)
+                        .unwrap_or(InstructionError::Runtime(error)),
+                ),
+                error => (0, InstructionError::Transaction(error)),
+            };
+            TransactionError { index, error, logs }
+        })
+    }
+}
@@ -0,0 +1,375 @@
+//! Programs compiled for the host, run by the runtime like BPF programs.
+//!
+//! The accounts of an instruction are serialized with the BPF loader's own
+//! [serialize_parameters] and handed to the program through
+//! [solana_program::entrypoint::deserialize], so `AccountInfo::realloc` and
+//! account writes behave as on chain. Cross-program invocations go through
+//! [InvokeContext::create_message] and [InvokeContext::process_instruction],
+//! which check signer and writable privileges and the accounts modified by
+//! both programs, like the BPF loader's `sol_invoke_signed`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::sync::{Once, RwLock};
+
+use solana_bpf_loader_program::serialization::{deserialize_parameters, serialize_parameters};
+use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
+use solana_program::entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS};
+use solana_program::epoch_schedule::EpochSchedule;
+use solana_program::instruction::{Instruction, InstructionError};
+use solana_program::program_error::ProgramError;
+use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::{bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable};
+use solana_program_runtime::invoke_context::InvokeContext;
+use solana_program_runtime::timings::ExecuteTimings;
+use solana_program_runtime::{ic_msg, stable_log};
+use solana_sdk::account::{ReadableAccount, WritableAccount};
+use solana_sdk::feature_set::do_support_realloc;
+use solana_sdk::message::SanitizedMessage;
+use solana_sdk::native_loader;
+
+use crate::ProcessInstruction;
+
+/// Entrypoints of the registered programs, by program id.
+static PROGRAMS: RwLock<Option<HashMap<Pubkey, ProcessInstruction>>> = RwLock::new(None);
+
+/// Registers the entrypoint of a program.
+pub(crate) fn register(program_id: Pubkey, process: ProcessInstruction) {
+    PROGRAMS
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(program_id, process);
+    install_syscalls();
+}
+
+fn entrypoint(program_id: &Pubkey) -> Option<ProcessInstruction> {
+    PROGRAMS.read().unwrap().as_ref()?.get(program_id).copied()
+}
+
+// An instruction of a host program being processed.
+struct Frame {
+    // The accounts of the instruction and their data length when serialized,
+    // which bounds the reallocs done by invoked programs.
+    keys: Vec<Pubkey>,
+    lengths: Vec<usize>,
+    // A failed invocation aborts the program, like a failed syscall aborts
+    // the BPF VM, whatever the program does with the error.
+    abort: Option<InstructionError>,
+}
+
+thread_local! {
+    static INVOKE_CONTEXT: Cell<*mut ()> = const { Cell::new(std::ptr::null_mut()) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+fn set_invoke_context(invoke_context: &mut InvokeContext) {
+    INVOKE_CONTEXT.with(|cell| cell.set(invoke_context as *mut InvokeContext as *mut ()));
+}
+
+#[allow(clippy::mut_from_ref)]
+fn invoke_context<'a, 'b>() -> &'a mut InvokeContext<'b> {
+    let ptr = INVOKE_CONTEXT.with(Cell::get) as *mut InvokeContext;
+    unsafe { ptr.as_mut() }.expect("no instruction is being processed")
+}
+
+/// Processes an instruction of a registered program, as the BPF loader does.
+pub(crate) fn process_instruction(
+    first_instruction_account: usize,
+    instruction_data: &[u8],
+    invoke_context: &mut InvokeContext,
+) -> Result<(), InstructionError> {
+    set_invoke_context(invoke_context);
+    let log_collector = invoke_context.get_log_collector();
+    let program_id = *invoke_context.get_caller()?;
+    let process = entrypoint(&program_id).ok_or(InstructionError::UnsupportedProgramId)?;
+
+    let keyed_accounts = &invoke_context.get_keyed_accounts()?[first_instruction_account..];
+    let (mut parameters, lengths) = serialize_parameters(
+        &bpf_loader::id(),
+        &program_id,
+        keyed_accounts,
+        instruction_data,
+    )?;
+    let keys = keyed_accounts
+        .iter()
+        .map(|keyed_account| *keyed_account.unsigned_key())
+        .collect();
+
+    stable_log::program_invoke(
+        &log_collector,
+        &program_id,
+        invoke_context.get_stack_height(),
+    );
+    FRAMES.with(|frames| {
+        frames.borrow_mut().push(Frame {
+            keys,
+            lengths: lengths.clone(),
+            abort: None,
+        })
+    });
+    let result = {
+        let (program_id, accounts, data) = unsafe {
+            solana_program::entrypoint::deserialize(parameters.as_slice_mut().as_mut_ptr())
+        };
+        process(program_id, &accounts, data)
+    };
+    let frame = FRAMES.with(|frames| frames.borrow_mut().pop()).unwrap();
+    set_invoke_context(invoke_context);
+
+    let result = match frame.abort {
+        Some(error) => Err(error),
+        None => result.map_err(|error| InstructionError::from(u64::from(error))),
+    }
+    .and_then(|_| {
+        deserialize_parameters(
+            &bpf_loader::id(),
+            &invoke_context.get_keyed_accounts()?[first_instruction_account..],
+            parameters.as_slice(),
+            &lengths,
+            invoke_context
+                .feature_set
+                .is_active(&do_support_realloc::id()),
+        )
+    });
+    match &result {
+        Ok(()) => stable_log::program_success(&log_collector, &program_id),
+        Err(error) => stable_log::program_failure(&log_collector, &program_id, error),
+    }
+    result
+}
+
+// Programs which may not be invoked, as in the BPF loader.
+fn check_authorized_program(
+    invoke_context: &InvokeContext,
+    instruction: &Instruction,
+) -> Result<(), InstructionError> {
+    let program_id = &instruction.program_id;
+    let data = &instruction.data;
+    if native_loader::check_id(program_id)
+        || bpf_loader::check_id(program_id)
+        || bpf_loader_deprecated::check_id(program_id)
+        || (bpf_loader_upgradeable::check_id(program_id)
+            && !(bpf_loader_upgradeable::is_upgrade_instruction(data)
+                || bpf_loader_upgradeable::is_set_authority_instruction(data)
+                || bpf_loader_upgradeable::is_close_instruction(data)))
+    {
+        ic_msg!(invoke_context, "Program {} is not supported", program_id);
+        return Err(InstructionError::ProgramFailedToComplete);
+    }
+    Ok(())
+}
+
+fn invoke_signed(
+    invoke_context: &mut InvokeContext,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), InstructionError> {
+    let caller = *invoke_context.get_caller()?;
+    let signers = signers_seeds
+        .iter()
+        .map(|seeds| {
+            Pubkey::create_program_address(seeds, &caller).map_err(|_| {
+                ic_msg!(
+                    invoke_context,
+                    "Could not create program address with signer seeds"
+                );
+                InstructionError::ProgramFailedToComplete
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (message, caller_write_privileges, program_indices) =
+        invoke_context.create_message(instruction, &signers)?;
+    check_authorized_program(invoke_context, instruction)?;
+
+    // Update the accounts of the runtime from the caller's, keeping the
+    // writable ones to copy the results back.
+    let (keys, lengths) = FRAMES.with(|frames| {
+        let frames = frames.borrow();
+        let frame = frames.last().unwrap();
+        (frame.keys.clone(), frame.lengths.clone())
+    });
+    let program_id_index = message.instructions[0].program_id_index as usize;
+    let mut account_indices = Vec::with_capacity(message.account_keys.len());
+    let mut accounts = Vec::with_capacity(message.account_keys.len());
+    for (i, key) in message.account_keys.iter().enumerate() {
+        let (index, account) = invoke_context.get_account(key).ok_or_else(|| {
+            ic_msg!(
+                invoke_context,
+                "Instruction references an unknown account {}",
+                key
+            );
+            InstructionError::MissingAccount
+        })?;
+        if i == program_id_index || account.borrow().executable() {
+            account_indices.push(index);
+            accounts.push((account, None));
+            continue;
+        }
+        let info = account_infos
+            .iter()
+            .find(|info| info.key == key)
+            .ok_or_else(|| {
+                ic_msg!(
+                    invoke_context,
+                    "Instruction references an unknown account {}",
+                    key
+                );
+                InstructionError::MissingAccount
+            })?;
+        {
+            let mut account = account.borrow_mut();
+            account.copy_into_owner_from_slice(info.owner.as_ref());
+            account.set_data_from_slice(
+                &info
+                    .try_borrow_data()
+                    .map_err(|_| InstructionError::AccountBorrowFailed)?,
+            );
+            account.set_lamports(info.lamports());
+            account.set_executable(info.executable);
+            account.set_rent_epoch(info.rent_epoch);
+        }
+        let caller_account = if message.is_writable(i) {
+            let position = keys
+                .iter()
+                .position(|k| k == key)
+                .ok_or(InstructionError::MissingAccount)?;
+            Some((info, lengths[position]))
+        } else {
+            None
+        };
+        account_indices.push(index);
+        accounts.push((account, caller_account));
+    }
+
+    invoke_context.record_instruction(
+        invoke_context.get_stack_height().saturating_add(1),
+        instruction.clone(),
+    );
+    let message = SanitizedMessage::Legacy(message);
+    invoke_context
+        .process_instruction(
+            &message,
+            &message.instructions()[0],
+            &program_indices,
+            &account_indices,
+            &caller_write_privileges,
+            &mut ExecuteTimings::default(),
+        )
+        .result?;
+
+    // Copy the results back to the caller.
+    for (account, caller_account) in accounts {
+        let (info, original_len) = match caller_account {
+            Some(caller_account) => caller_account,
+            None => continue,
+        };
+        let account = account.borrow();
+        **info
+            .try_borrow_mut_lamports()
+            .map_err(|_| InstructionError::AccountBorrowFailed)? = account.lamports();
+        if info.owner != account.owner() {
+            info.assign(account.owner());
+        }
+        let new_len = account.data().len();
+        if info.data_len() != new_len {
+            if new_len > original_len + MAX_PERMITTED_DATA_INCREASE {
+                ic_msg!(
+                    invoke_context,
+                    "Account data size realloc limited to {} in inner instructions",
+                    MAX_PERMITTED_DATA_INCREASE
+                );
+                return Err(InstructionError::InvalidRealloc);
+            }
+            info.realloc(new_len, true)
+                .map_err(|_| InstructionError::AccountBorrowFailed)?;
+        }
+        info.try_borrow_mut_data()
+            .map_err(|_| InstructionError::AccountBorrowFailed)?
+            .copy_from_slice(account.data());
+    }
+    Ok(())
+}
+
+fn get_sysvar<T: Clone>(
+    sysvar: Result<std::sync::Arc<T>, InstructionError>,
+    var_addr: *mut u8,
+) -> u64 {
+    match sysvar {
+        Ok(sysvar) => {
+            unsafe { *(var_addr as *mut T) = T::clone(&sysvar) };
+            SUCCESS
+        }
+        Err(_) => solana_program::program_error::UNSUPPORTED_SYSVAR,
+    }
+}
+
+struct HostSyscalls;
+
+impl SyscallStubs for HostSyscalls {
+    fn sol_log(&self, message: &str) {
+        stable_log::program_log(&invoke_context().get_log_collector(), message);
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        stable_log::program_data(&invoke_context().get_log_collector(), fields);
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let invoke_context = invoke_context();
+        let result = invoke_signed(invoke_context, instruction, account_infos, signers_seeds);
+        set_invoke_context(invoke_context);
+        result.map_err(|error| {
+            let program_error =
+                ProgramError::try_from(error.clone()).unwrap_or(ProgramError::InvalidArgument);
+            FRAMES.with(|frames| {
+                if let Some(frame) = frames.borrow_mut().last_mut() {
+                    frame.abort.get_or_insert(error);
+                }
+            });
+            program_error
+        })
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar::<Clock>(invoke_context().get_sysvar_cache().get_clock(), var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar::<EpochSchedule>(
+            invoke_context().get_sysvar_cache().get_epoch_schedule(),
+            var_addr,
+        )
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        get_sysvar::<Rent>(invoke_context().get_sysvar_cache().get_rent(), var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        let (program_id, data) = &invoke_context().return_data;
+        Some((*program_id, data.to_vec()))
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let invoke_context = invoke_context();
+        let caller = *invoke_context.get_caller().unwrap();
+        invoke_context.return_data = (caller, data.to_vec());
+    }
+}
+
+fn install_syscalls() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(HostSyscalls));
+    });
+}
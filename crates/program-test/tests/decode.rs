@@ -0,0 +1,257 @@
+//! Tests of the rendering of proposed instructions.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{bpf_loader_upgradeable, system_instruction};
+use anchor_lang::InstructionData;
+use anchor_spl::token::spl_token;
+use smallet::{
+    AccountCheck, OutflowLimit, PostCondition, PreCondition, Smallet, TXAccountMeta, TXInstruction,
+};
+use smallet_client::decode::{
+    decode, describe, describe_post_condition, describe_pre_condition, Labels, MEMO_PROGRAM_ID,
+};
+use smallet_client::pda;
+
+fn smallet_with_owners(owners: Vec<Pubkey>) -> Smallet {
+    Smallet {
+        threshold: 1,
+        owners,
+        ..Smallet::default()
+    }
+}
+
+#[test]
+fn describe_sol_transfer_from_derived_wallet() {
+    let smallet = Pubkey::new_unique();
+    let (derived, _) = pda::find_wallet_derived_address(&smallet, 2);
+    let to = Pubkey::new_unique();
+    let labels = Labels::for_smallet_address(&smallet);
+
+    let ix: TXInstruction = system_instruction::transfer(&derived, &to, 1_500_000_000).into();
+    assert_eq!(
+        describe(&ix, &labels),
+        format!("transfer 1.5 SOL from derived #2 to {}", to)
+    );
+    let decoded = decode(&ix, &labels).unwrap();
+    assert_eq!(decoded.program, "System");
+    assert_eq!(decoded.name, "transfer");
+    let roles: Vec<_> = decoded.accounts.iter().map(|a| a.role).collect();
+    assert_eq!(roles, ["source", "destination"]);
+}
+
+#[test]
+fn describe_token_transfers_with_mint_units() {
+    let smallet = Pubkey::new_unique();
+    let (derived, _) = pda::find_wallet_derived_address(&smallet, 2);
+    let usdc = Pubkey::new_unique();
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let labels = Labels::for_smallet_address(&smallet)
+        .with_mint(usdc, "USDC", 6)
+        .with_name(recipient, "treasury")
+        .with_token_account(source, usdc, derived)
+        .with_token_account(destination, usdc, recipient);
+
+    let transfer: TXInstruction = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &source,
+        &destination,
+        &derived,
+        &[],
+        500_000_000,
+    )
+    .unwrap()
+    .into();
+    assert_eq!(
+        describe(&transfer, &labels),
+        "transfer 500 USDC from derived #2 to treasury"
+    );
+
+    let checked: TXInstruction = spl_token::instruction::transfer_checked(
+        &spl_token::ID,
+        &source,
+        &usdc,
+        &destination,
+        &derived,
+        &[],
+        1_250_000,
+        6,
+    )
+    .unwrap()
+    .into();
+    assert_eq!(
+        describe(&checked, &labels),
+        "transfer 1.25 USDC from derived #2 to treasury"
+    );
+
+    // Without labels the amount is rendered in base units.
+    assert_eq!(
+        describe(&transfer, &Labels::default()),
+        format!(
+            "transfer 500000000 base units from {} to {}",
+            source, destination
+        )
+    );
+}
+
+#[test]
+fn describe_smallet_governance() {
+    let smallet = Pubkey::new_unique();
+    let owners = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let new_owner = Pubkey::new_unique();
+    let labels = Labels::for_smallet(&smallet, &smallet_with_owners(owners.clone()));
+
+    let set_owners = TXInstruction {
+        program_id: smallet::ID,
+        keys: vec![TXAccountMeta {
+            pubkey: smallet,
+            is_signer: true,
+            is_writable: true,
+        }],
+        data: smallet::instruction::SetOwners {
+            owners: vec![owners[1], new_owner],
+        }
+        .data(),
+    };
+    assert_eq!(
+        describe(&set_owners, &labels),
+        format!("set the owners of smallet to [owner #1, {}]", new_owner)
+    );
+
+    let change_threshold = TXInstruction {
+        data: smallet::instruction::ChangeThreshold { threshold: 2 }.data(),
+        ..set_owners
+    };
+    assert_eq!(
+        describe(&change_threshold, &labels),
+        "change the threshold of smallet to 2"
+    );
+}
+
+#[test]
+fn describe_program_upgrade_and_memo() {
+    let smallet = Pubkey::new_unique();
+    let (invoker, _) = pda::find_owner_invoker_address(&smallet, 0);
+    let program = Pubkey::new_unique();
+    let buffer = Pubkey::new_unique();
+    let labels = Labels::for_smallet_address(&smallet)
+        .with_name(program, "my program")
+        .with_name(buffer, "v2 buffer");
+
+    let upgrade: TXInstruction =
+        bpf_loader_upgradeable::upgrade(&program, &buffer, &invoker, &invoker).into();
+    assert_eq!(
+        describe(&upgrade, &labels),
+        "upgrade program my program from buffer v2 buffer, sending the buffer rent to invoker #0"
+    );
+    let decoded = decode(&upgrade, &labels).unwrap();
+    assert_eq!(decoded.accounts[6].role, "upgrade authority");
+
+    let memo = TXInstruction {
+        program_id: MEMO_PROGRAM_ID,
+        keys: vec![],
+        data: b"payroll".to_vec(),
+    };
+    assert_eq!(describe(&memo, &labels), "memo \"payroll\"");
+}
+
+#[test]
+fn describe_unknown_instruction() {
+    let program = Pubkey::new_unique();
+    let ix = TXInstruction {
+        program_id: program,
+        keys: vec![],
+        data: vec![0xde, 0xad],
+    };
+    assert!(decode(&ix, &Labels::default()).is_none());
+    assert_eq!(
+        describe(&ix, &Labels::default()),
+        format!("unknown instruction of {} with data 0xdead", program)
+    );
+
+    // Malformed data of a known program is not decoded either.
+    let ix = TXInstruction {
+        program_id: spl_token::ID,
+        ..ix
+    };
+    assert!(decode(&ix, &Labels::default()).is_none());
+}
+
+#[test]
+fn describe_pre_conditions() {
+    let smallet = Pubkey::new_unique();
+    let (derived, _) = pda::find_wallet_derived_address(&smallet, 0);
+    let usdc = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let labels = Labels::for_smallet_address(&smallet)
+        .with_mint(usdc, "USDC", 6)
+        .with_token_account(vault, usdc, derived);
+
+    let describe =
+        |account, check| describe_pre_condition(&PreCondition { account, check }, &labels);
+    assert_eq!(
+        describe(
+            derived,
+            AccountCheck::MinLamports {
+                lamports: 1_000_000_000
+            }
+        ),
+        "derived #0 holds at least 1 SOL"
+    );
+    assert_eq!(
+        describe(
+            vault,
+            AccountCheck::TokenAmount {
+                min: 1_000_000,
+                max: 2_500_000
+            }
+        ),
+        "derived #0 holds between 1 USDC and 2.5 USDC"
+    );
+    assert_eq!(
+        describe(
+            derived,
+            AccountCheck::Owner {
+                program_id: spl_token::ID
+            }
+        ),
+        "derived #0 is owned by Token Program"
+    );
+    assert_eq!(
+        describe(derived, AccountCheck::DataHash { hash: [0xab; 32] }),
+        format!("data of derived #0 hashes to {}", "ab".repeat(32))
+    );
+}
+
+#[test]
+fn describe_post_conditions() {
+    let smallet = Pubkey::new_unique();
+    let (derived, _) = pda::find_wallet_derived_address(&smallet, 0);
+    let usdc = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let labels = Labels::for_smallet_address(&smallet)
+        .with_mint(usdc, "USDC", 6)
+        .with_token_account(vault, usdc, derived);
+
+    let describe =
+        |account, limit| describe_post_condition(&PostCondition { account, limit }, &labels);
+    assert_eq!(
+        describe(
+            derived,
+            OutflowLimit::Lamports {
+                max_outflow: 500_000_000
+            }
+        ),
+        "at most 0.5 SOL leaves derived #0"
+    );
+    assert_eq!(
+        describe(
+            vault,
+            OutflowLimit::Tokens {
+                max_outflow: 2_000_000
+            }
+        ),
+        "at most 2 USDC leaves derived #0"
+    );
+}
@@ -0,0 +1,462 @@
+//! Fuzzes the smallet state machine against an in-process bank.
+//!
+//! Each run decodes a byte string into a sequence of [Op]s and checks the
+//! invariants of every smallet and transaction after each of them. Runs are
+//! seeded: set `SMALLET_FUZZ_SEED` to replay one and `SMALLET_FUZZ_RUNS` to
+//! change how many are made.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use smallet::{Smallet, TXInstruction, Transaction, DEFAULT_GRACE_PERIOD, NO_ETA};
+use smallet_client::events::{parse_logs, SmalletEvent};
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{Bank, ProgramTest, TransactionResult};
+
+/// Number of keys owners are drawn from.
+const NUM_KEYS: usize = 6;
+const MAX_SMALLETS: usize = 3;
+const DEFAULT_RUNS: u64 = 200;
+const MAX_INPUT_LEN: usize = 512;
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    CreateSmallet {
+        max_owners: u8,
+        owners: u8,
+        threshold: u64,
+        minimum_delay: i64,
+    },
+    ProposeSetOwners {
+        smallet: usize,
+        proposer: usize,
+        owners: u8,
+        eta: i64,
+    },
+    ProposeChangeThreshold {
+        smallet: usize,
+        proposer: usize,
+        threshold: u64,
+        eta: i64,
+    },
+    ProposeNothing {
+        smallet: usize,
+        proposer: usize,
+        eta: i64,
+    },
+    Approve {
+        transaction: usize,
+        owner: usize,
+    },
+    Unapprove {
+        transaction: usize,
+        owner: usize,
+    },
+    Execute {
+        transaction: usize,
+        owner: usize,
+    },
+    Warp {
+        seconds: i64,
+    },
+}
+
+/// Reads bytes, yielding zeroes once exhausted.
+struct Input<'a>(&'a [u8]);
+
+impl Input<'_> {
+    fn byte(&mut self) -> u8 {
+        match self.0.split_first() {
+            Some((byte, rest)) => {
+                self.0 = rest;
+                *byte
+            }
+            None => 0,
+        }
+    }
+
+    fn choose<T: Copy>(&mut self, values: &[T]) -> T {
+        values[self.byte() as usize % values.len()]
+    }
+
+    /// A delay relative to the current time, or [NO_ETA].
+    fn eta(&mut self) -> i64 {
+        self.choose(&[NO_ETA, 0, 10, 100, 1_000])
+    }
+}
+
+impl Op {
+    fn decode(data: &[u8]) -> Vec<Op> {
+        let mut input = Input(data);
+        let mut ops = vec![];
+        while !input.0.is_empty() {
+            let op = match input.byte() % 8 {
+                0 => Op::CreateSmallet {
+                    max_owners: 1 + input.byte() % 8,
+                    owners: input.byte(),
+                    threshold: u64::from(input.byte() % 8),
+                    minimum_delay: input.choose(&[0, 0, 10, 100]),
+                },
+                1 => Op::ProposeSetOwners {
+                    smallet: input.byte().into(),
+                    proposer: input.byte().into(),
+                    owners: input.byte(),
+                    eta: input.eta(),
+                },
+                2 => Op::ProposeChangeThreshold {
+                    smallet: input.byte().into(),
+                    proposer: input.byte().into(),
+                    threshold: u64::from(input.byte() % 8),
+                    eta: input.eta(),
+                },
+                3 => Op::ProposeNothing {
+                    smallet: input.byte().into(),
+                    proposer: input.byte().into(),
+                    eta: input.eta(),
+                },
+                4 => Op::Approve {
+                    transaction: input.byte().into(),
+                    owner: input.byte().into(),
+                },
+                5 => Op::Unapprove {
+                    transaction: input.byte().into(),
+                    owner: input.byte().into(),
+                },
+                6 => Op::Execute {
+                    transaction: input.byte().into(),
+                    owner: input.byte().into(),
+                },
+                _ => Op::Warp {
+                    seconds: input.choose(&[1, 10, 100, DEFAULT_GRACE_PERIOD]),
+                },
+            };
+            ops.push(op);
+        }
+        ops
+    }
+}
+
+struct Fuzzer {
+    bank: Bank,
+    payer: Pubkey,
+    keys: Vec<Pubkey>,
+    smallets: Vec<Pubkey>,
+    transactions: Vec<Pubkey>,
+    /// Number of owners of each smallet, by owner set sequence number.
+    owner_counts: BTreeMap<(Pubkey, u32), usize>,
+    /// Executor and time of executed transactions.
+    executed: BTreeMap<Pubkey, (Pubkey, i64)>,
+    /// Transactions for which an execution event was emitted.
+    execute_events: BTreeSet<Pubkey>,
+    /// A transaction for which a second execution event was emitted.
+    executed_twice: Option<Pubkey>,
+}
+
+type Check = std::result::Result<(), String>;
+
+macro_rules! check {
+    ($cond:expr, $($arg:tt)+) => {
+        if !$cond {
+            return Err(format!($($arg)+));
+        }
+    };
+}
+
+impl Fuzzer {
+    fn new() -> Self {
+        let mut bank = ProgramTest::default()
+            .add_program(smallet::ID, smallet::entry)
+            .start();
+        let payer = Pubkey::new_unique();
+        bank.airdrop(&payer, u64::MAX / 2);
+        Self {
+            bank,
+            payer,
+            keys: (0..NUM_KEYS).map(|_| Pubkey::new_unique()).collect(),
+            smallets: vec![],
+            transactions: vec![],
+            owner_counts: BTreeMap::new(),
+            executed: BTreeMap::new(),
+            execute_events: BTreeSet::new(),
+            executed_twice: None,
+        }
+    }
+
+    fn owners(&self, mask: u8) -> Vec<Pubkey> {
+        (0..NUM_KEYS)
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| self.keys[i])
+            .collect()
+    }
+
+    fn key(&self, index: usize) -> Pubkey {
+        self.keys[index % NUM_KEYS]
+    }
+
+    fn smallet(&self, address: &Pubkey) -> Smallet {
+        let account = self.bank.get_account(address).unwrap();
+        Smallet::try_deserialize_versioned(&account.data).unwrap()
+    }
+
+    fn transaction(&self, address: &Pubkey) -> Transaction {
+        let account = self.bank.get_account(address).unwrap();
+        Transaction::try_deserialize_versioned(&account.data).unwrap()
+    }
+
+    fn process(&mut self, instruction: Instruction, signer: Pubkey) -> TransactionResult {
+        let result = self
+            .bank
+            .process_transaction(&[instruction], &[self.payer, signer]);
+        if let Ok(logs) = &result {
+            for event in parse_logs(logs) {
+                if let SmalletEvent::TransactionExecute(event) = event {
+                    if !self.execute_events.insert(event.transaction) {
+                        self.executed_twice = Some(event.transaction);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn pick_smallet(&self, index: usize) -> Option<Pubkey> {
+        if self.smallets.is_empty() {
+            return None;
+        }
+        Some(self.smallets[index % self.smallets.len()])
+    }
+
+    fn propose(&mut self, smallet: Pubkey, proposer: usize, ixs: Vec<TXInstruction>, eta: i64) {
+        let index = self.smallet(&smallet).num_transactions;
+        let eta = if eta == NO_ETA {
+            NO_ETA
+        } else {
+            self.bank.clock().unix_timestamp + eta
+        };
+        let proposer = self.key(proposer);
+        let payer = self.payer;
+        let instruction =
+            ix::create_transaction_with_timelock(&smallet, index, &proposer, &payer, ixs, eta);
+        if self.process(instruction, proposer).is_ok() {
+            self.transactions
+                .push(pda::find_transaction_address(&smallet, index).0);
+        }
+    }
+
+    fn pick_transaction(&self, index: usize) -> Option<Pubkey> {
+        if self.transactions.is_empty() {
+            return None;
+        }
+        Some(self.transactions[index % self.transactions.len()])
+    }
+
+    fn apply(&mut self, op: Op) -> Check {
+        match op {
+            Op::CreateSmallet {
+                max_owners,
+                owners,
+                threshold,
+                minimum_delay,
+            } => {
+                if self.smallets.len() >= MAX_SMALLETS {
+                    return Ok(());
+                }
+                let base = Pubkey::new_unique();
+                let payer = self.payer;
+                let instruction = ix::create_smallet(
+                    &base,
+                    &payer,
+                    max_owners,
+                    self.owners(owners),
+                    threshold,
+                    minimum_delay,
+                );
+                if self.process(instruction, base).is_ok() {
+                    self.smallets.push(pda::find_smallet(&base).0);
+                }
+            }
+            Op::ProposeSetOwners {
+                smallet,
+                proposer,
+                owners,
+                eta,
+            } => {
+                if let Some(smallet) = self.pick_smallet(smallet) {
+                    let ix = ix::set_owners(&smallet, self.owners(owners)).into();
+                    self.propose(smallet, proposer, vec![ix], eta);
+                }
+            }
+            Op::ProposeChangeThreshold {
+                smallet,
+                proposer,
+                threshold,
+                eta,
+            } => {
+                if let Some(smallet) = self.pick_smallet(smallet) {
+                    let ix = ix::change_threshold(&smallet, threshold).into();
+                    self.propose(smallet, proposer, vec![ix], eta);
+                }
+            }
+            Op::ProposeNothing {
+                smallet,
+                proposer,
+                eta,
+            } => {
+                if let Some(smallet) = self.pick_smallet(smallet) {
+                    self.propose(smallet, proposer, vec![], eta);
+                }
+            }
+            Op::Approve { transaction, owner } | Op::Unapprove { transaction, owner } => {
+                if let Some(transaction) = self.pick_transaction(transaction) {
+                    let tx = self.transaction(&transaction);
+                    let owner = self.key(owner);
+                    let instruction = match op {
+                        Op::Approve { .. } => ix::approve(&tx.smallet, &transaction, &owner),
+                        _ => ix::unapprove(&tx.smallet, &transaction, &owner),
+                    };
+                    let _ = self.process(instruction, owner);
+                }
+            }
+            Op::Execute { transaction, owner } => {
+                if let Some(transaction) = self.pick_transaction(transaction) {
+                    self.execute(transaction, self.key(owner))?;
+                }
+            }
+            Op::Warp { seconds } => self.bank.advance_clock(seconds),
+        }
+        self.check_invariants()
+    }
+
+    fn execute(&mut self, transaction: Pubkey, owner: Pubkey) -> Check {
+        let tx = self.transaction(&transaction);
+        let smallet = self.smallet(&tx.smallet);
+        let now = self.bank.clock().unix_timestamp;
+        let instruction = ix::execute_transaction(&tx.smallet, &transaction, &tx, &owner);
+        let result = self.process(instruction, owner);
+
+        let executable = tx.executed_at == -1
+            && tx.owner_set_seqno == smallet.owner_set_seqno
+            && tx.num_signers() as u64 >= smallet.threshold
+            && now >= tx.eta
+            && (tx.eta == NO_ETA || now <= tx.eta + smallet.grace_period)
+            && smallet.owners.contains(&owner);
+        match result {
+            Ok(_) => {
+                check!(
+                    tx.executed_at == -1,
+                    "{} executed twice, first at {}",
+                    transaction,
+                    tx.executed_at
+                );
+                check!(
+                    tx.num_signers() as u64 >= smallet.threshold,
+                    "{} executed with {} of {} approvals",
+                    transaction,
+                    tx.num_signers(),
+                    smallet.threshold
+                );
+                check!(
+                    executable,
+                    "{} executed in state {:?} of {:?}",
+                    transaction,
+                    tx,
+                    smallet
+                );
+            }
+            // Only transactions without instructions are sure to succeed.
+            Err(err) => check!(
+                !executable || !tx.instructions.is_empty(),
+                "{} could not be executed: {}",
+                transaction,
+                err
+            ),
+        }
+        Ok(())
+    }
+
+    fn check_invariants(&mut self) -> Check {
+        if let Some(transaction) = self.executed_twice {
+            return Err(format!("{} emitted two execution events", transaction));
+        }
+        for address in self.smallets.clone() {
+            let smallet = self.smallet(&address);
+            check!(
+                smallet.threshold <= smallet.owners.len() as u64,
+                "{} has threshold {} with {} owners",
+                address,
+                smallet.threshold,
+                smallet.owners.len()
+            );
+            let count = *self
+                .owner_counts
+                .entry((address, smallet.owner_set_seqno))
+                .or_insert(smallet.owners.len());
+            check!(
+                count == smallet.owners.len(),
+                "{} changed owners without changing its sequence number {}",
+                address,
+                smallet.owner_set_seqno
+            );
+        }
+        for address in self.transactions.clone() {
+            let tx = self.transaction(&address);
+            let count = self.owner_counts.get(&(tx.smallet, tx.owner_set_seqno));
+            check!(
+                count == Some(&tx.signers.len()),
+                "{} has {} signers for {:?} owners at sequence number {}",
+                address,
+                tx.signers.len(),
+                count,
+                tx.owner_set_seqno
+            );
+            if tx.executed_at != -1 {
+                let execution = *self
+                    .executed
+                    .entry(address)
+                    .or_insert((tx.executor, tx.executed_at));
+                check!(
+                    execution == (tx.executor, tx.executed_at),
+                    "{} executed again by {} at {}",
+                    address,
+                    tx.executor,
+                    tx.executed_at
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs the ops decoded from `data`, returning the first broken invariant.
+fn run(data: &[u8]) -> std::result::Result<(), (Vec<Op>, String)> {
+    let ops = Op::decode(data);
+    let mut fuzzer = Fuzzer::new();
+    for (i, op) in ops.iter().enumerate() {
+        if let Err(err) = fuzzer.apply(*op) {
+            return Err((ops[..=i].to_vec(), err));
+        }
+    }
+    Ok(())
+}
+
+fn env_u64(name: &str) -> Option<u64> {
+    std::env::var(name).ok().map(|value| value.parse().unwrap())
+}
+
+#[test]
+fn fuzz_state_machine() {
+    let (first, runs) = match env_u64("SMALLET_FUZZ_SEED") {
+        Some(seed) => (seed, 1),
+        None => (0, env_u64("SMALLET_FUZZ_RUNS").unwrap_or(DEFAULT_RUNS)),
+    };
+    for seed in first..first + runs {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut data = vec![0; rng.gen_range(0, MAX_INPUT_LEN)];
+        rng.fill(&mut data[..]);
+        if let Err((ops, err)) = run(&data) {
+            panic!("seed {}: {}\nops: {:#?}", seed, err, ops);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Tests of the reconstruction of smallet histories from fixed event sequences.
+
+use ::smallet::*;
+use anchor_lang::prelude::*;
+use smallet_client::events::SmalletEvent;
+use smallet_client::history::{History, SmalletTimeline};
+use smallet_client::pda;
+
+const CREATED_AT: i64 = 1_650_000_000;
+
+struct Fixture {
+    smallet: Pubkey,
+    owners: Vec<Pubkey>,
+    transaction: Pubkey,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        Self {
+            smallet: Pubkey::new_unique(),
+            owners: (0..3).map(|_| Pubkey::new_unique()).collect(),
+            transaction: Pubkey::new_unique(),
+        }
+    }
+
+    fn create(&self, threshold: u64) -> SmalletEvent {
+        SmalletEvent::WalletCreate(WalletCreateEvent {
+            smallet: self.smallet,
+            owners: self.owners.clone(),
+            threshold,
+            minimum_delay: 0,
+            timestamp: CREATED_AT,
+        })
+    }
+
+    fn set_owners(&self, owners: usize, timestamp: i64) -> SmalletEvent {
+        SmalletEvent::WalletSetOwners(WalletSetOwnersEvent {
+            smallet: self.smallet,
+            owners: self.owners[..owners].to_vec(),
+            timestamp,
+        })
+    }
+
+    fn propose(&self, timestamp: i64) -> SmalletEvent {
+        SmalletEvent::TransactionCreate(TransactionCreateEvent {
+            smallet: self.smallet,
+            transaction: self.transaction,
+            proposer: self.owners[0],
+            instructions: vec![],
+            eta: NO_ETA,
+            timestamp,
+        })
+    }
+
+    fn approve(&self, owner: usize, timestamp: i64) -> SmalletEvent {
+        SmalletEvent::TransactionApprove(TransactionApproveEvent {
+            smallet: self.smallet,
+            transaction: self.transaction,
+            owner: self.owners[owner],
+            timestamp,
+        })
+    }
+
+    fn unapprove(&self, owner: usize, timestamp: i64) -> SmalletEvent {
+        SmalletEvent::TransactionUnapprove(TransactionUnapproveEvent {
+            smallet: self.smallet,
+            transaction: self.transaction,
+            owner: self.owners[owner],
+            timestamp,
+        })
+    }
+
+    fn execute(&self, executor: usize, timestamp: i64) -> SmalletEvent {
+        SmalletEvent::TransactionExecute(TransactionExecuteEvent {
+            smallet: self.smallet,
+            transaction: self.transaction,
+            executor: self.owners[executor],
+            timestamp,
+        })
+    }
+
+    fn replay(&self, events: Vec<SmalletEvent>) -> SmalletTimeline {
+        let mut history = History::default();
+        for event in events {
+            history.apply(event);
+        }
+        history.smallet(&self.smallet).unwrap().clone()
+    }
+}
+
+#[test]
+fn replay_transaction_lifecycle() {
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.create(2),
+        f.propose(CREATED_AT + 10),
+        f.approve(0, CREATED_AT + 10),
+        f.approve(1, CREATED_AT + 20),
+        f.unapprove(0, CREATED_AT + 30),
+        f.approve(2, CREATED_AT + 40),
+        SmalletEvent::TransactionIndexAssign(TransactionIndexAssignEvent {
+            smallet: f.smallet,
+            transaction: f.transaction,
+            index: 4,
+            timestamp: CREATED_AT + 40,
+        }),
+        f.execute(2, CREATED_AT + 50),
+    ]);
+
+    assert_eq!(timeline.created_at, Some(CREATED_AT));
+    assert_eq!(timeline.threshold(), Some(2));
+    assert_eq!(timeline.events.len(), 8);
+    let tx = timeline.transaction(&f.transaction).unwrap();
+    assert_eq!(tx.owner_set_seqno, Some(0));
+    assert_eq!(tx.index, Some(4));
+    assert_eq!(tx.approvers(), vec![f.owners[1], f.owners[2]]);
+    assert_eq!(tx.executed, Some((f.owners[2], CREATED_AT + 50)));
+
+    // Only the approval given within the TTL is fresh.
+    assert_eq!(tx.fresh_approvers(15, CREATED_AT + 50), vec![f.owners[2]]);
+    assert_eq!(tx.fresh_approvers(0, CREATED_AT + 50), tx.approvers());
+}
+
+#[test]
+fn replay_owner_set_changes() {
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.create(3),
+        f.set_owners(2, CREATED_AT + 100),
+        f.propose(CREATED_AT + 150),
+        SmalletEvent::WalletChangeThreshold(WalletChangeThresholdEvent {
+            smallet: f.smallet,
+            threshold: 1,
+            timestamp: CREATED_AT + 200,
+        }),
+        f.set_owners(3, CREATED_AT + 300),
+    ]);
+
+    let seqnos: Vec<Option<u32>> = timeline.owner_sets.iter().map(|set| set.seqno).collect();
+    assert_eq!(seqnos, vec![Some(0), Some(1), Some(2)]);
+    assert_eq!(timeline.owner_set().unwrap().owners, f.owners);
+    assert_eq!(
+        timeline.owner_set_at(CREATED_AT + 150).unwrap().seqno,
+        Some(1)
+    );
+    assert!(timeline.owner_set_at(CREATED_AT - 1).is_none());
+
+    // Shrinking the owner set below the threshold lowered it implicitly;
+    // growing it back does not raise it.
+    let thresholds: Vec<(u64, bool)> = timeline
+        .thresholds
+        .iter()
+        .map(|change| (change.threshold, change.implicit))
+        .collect();
+    assert_eq!(thresholds, vec![(3, false), (2, true), (1, false)]);
+    assert_eq!(
+        timeline
+            .transaction(&f.transaction)
+            .unwrap()
+            .owner_set_seqno,
+        Some(1)
+    );
+}
+
+#[test]
+fn replay_events_of_several_smallets() {
+    let first = Fixture::new();
+    let second = Fixture::new();
+    let mut history = History::default();
+    history.apply(first.create(1));
+    history.apply(second.create(2));
+    history.apply(second.propose(CREATED_AT + 1));
+    history.apply(first.set_owners(1, CREATED_AT + 2));
+
+    assert_eq!(history.smallets.len(), 2);
+    let first_timeline = history.smallet(&first.smallet).unwrap();
+    assert_eq!(first_timeline.owner_sets.len(), 2);
+    assert!(first_timeline.transactions.is_empty());
+    let second_timeline = history.smallet(&second.smallet).unwrap();
+    assert_eq!(second_timeline.owner_sets.len(), 1);
+    assert_eq!(second_timeline.transactions.len(), 1);
+}
+
+#[test]
+fn replay_out_of_order_transaction_events() {
+    // Events of a transaction seen before its creation are recorded, but do
+    // not apply to the transaction.
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.create(2),
+        f.approve(1, CREATED_AT + 20),
+        f.execute(1, CREATED_AT + 30),
+        f.propose(CREATED_AT + 10),
+        f.approve(0, CREATED_AT + 10),
+    ]);
+
+    assert_eq!(timeline.events.len(), 5);
+    let tx = timeline.transaction(&f.transaction).unwrap();
+    assert_eq!(tx.approvers(), vec![f.owners[0]]);
+    assert_eq!(tx.executed, None);
+}
+
+#[test]
+fn replay_out_of_order_approvals() {
+    // Approval changes are replayed in the order they are applied, not by
+    // timestamp.
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.create(2),
+        f.propose(CREATED_AT),
+        f.unapprove(0, CREATED_AT + 20),
+        f.approve(0, CREATED_AT + 10),
+        f.unapprove(1, CREATED_AT + 30),
+    ]);
+
+    let tx = timeline.transaction(&f.transaction).unwrap();
+    assert_eq!(tx.approvals.len(), 3);
+    assert_eq!(tx.approvers(), vec![f.owners[0]]);
+}
+
+#[test]
+fn replay_without_smallet_creation() {
+    // The history of a smallet created before indexing started. How many
+    // owner sets it had before is unknown.
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.set_owners(2, CREATED_AT + 100),
+        f.propose(CREATED_AT + 200),
+        f.approve(0, CREATED_AT + 200),
+        f.set_owners(3, CREATED_AT + 300),
+    ]);
+
+    assert_eq!(timeline.created_at, None);
+    assert_eq!(timeline.minimum_delay, None);
+    assert_eq!(timeline.threshold(), None);
+    assert!(timeline.thresholds.is_empty());
+    let seqnos: Vec<Option<u32>> = timeline.owner_sets.iter().map(|set| set.seqno).collect();
+    assert_eq!(seqnos, vec![None, None]);
+    assert_eq!(timeline.owner_set().unwrap().owners, f.owners);
+    let tx = timeline.transaction(&f.transaction).unwrap();
+    assert_eq!(tx.owner_set_seqno, None);
+    assert_eq!(tx.approvers(), vec![f.owners[0]]);
+}
+
+#[test]
+fn replay_transaction_proposed_before_indexing() {
+    // Neither the smallet nor its owner set were observed.
+    let f = Fixture::new();
+    let timeline = f.replay(vec![f.propose(CREATED_AT + 200)]);
+    assert!(timeline.owner_sets.is_empty());
+    let tx = timeline.transaction(&f.transaction).unwrap();
+    assert_eq!(tx.owner_set_seqno, None);
+}
+
+#[test]
+fn replay_without_transaction_creation() {
+    let f = Fixture::new();
+    let timeline = f.replay(vec![
+        f.create(1),
+        f.approve(0, CREATED_AT + 10),
+        f.unapprove(0, CREATED_AT + 20),
+        f.execute(0, CREATED_AT + 30),
+    ]);
+
+    assert!(timeline.transactions.is_empty());
+    assert!(timeline.transaction(&f.transaction).is_none());
+    assert_eq!(timeline.events.len(), 4);
+}
+
+#[test]
+fn replay_subaccount_events_without_creation() {
+    let f = Fixture::new();
+    let (subaccount, _) = pda::find_owner_invoker_address(&f.smallet, 0);
+    let (subaccount_info, _) = pda::find_subaccount_info_address(&subaccount);
+    let update = SmalletEvent::SubaccountUpdate(SubaccountUpdateEvent {
+        smallet: f.smallet,
+        subaccount_info,
+        label: Some("ops".to_string()),
+        timestamp: CREATED_AT + 20,
+    });
+    let close = SmalletEvent::SubaccountClose(SubaccountCloseEvent {
+        smallet: f.smallet,
+        subaccount,
+        registry_index: 0,
+        timestamp: CREATED_AT + 30,
+    });
+
+    // Updates and closes of unknown sub-accounts are ignored.
+    let timeline = f.replay(vec![f.create(1), update.clone(), close.clone()]);
+    assert!(timeline.subaccounts.is_empty());
+
+    let create = SmalletEvent::SubaccountCreate(SubaccountCreateEvent {
+        smallet: f.smallet,
+        subaccount,
+        subaccount_type: SubaccountType::OwnerInvoker,
+        index: 0,
+        registry_index: 0,
+        label: None,
+        timestamp: CREATED_AT + 10,
+    });
+    let timeline = f.replay(vec![f.create(1), create, update, close]);
+    assert_eq!(timeline.subaccounts.len(), 1);
+    let entry = &timeline.subaccounts[0];
+    assert_eq!(
+        entry.subaccount_type,
+        Some((SubaccountType::OwnerInvoker, 0))
+    );
+    assert_eq!(entry.label.as_deref(), Some("ops"));
+    assert_eq!(entry.closed_at, Some(CREATED_AT + 30));
+}
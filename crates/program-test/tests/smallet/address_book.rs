@@ -0,0 +1,476 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token::spl_token;
+use smallet::{
+    AddressBook, AddressBookEntry, ErrorCode, PdaSigner, SubaccountType, TXInstruction,
+    TransactionOptions, TransferAsset, VaultTransfer, NO_ETA,
+};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{TransactionError, TransactionResult};
+
+use crate::helpers::*;
+
+impl Env {
+    /// A smallet of 3 owners needing all of them, whose address book needs a
+    /// single approval and trusts entries right away.
+    fn with_address_book() -> Self {
+        let mut env = Env::new(3, 3, 0);
+        env.create_address_book(1, 0, 0).unwrap();
+        env
+    }
+
+    fn create_address_book(
+        &mut self,
+        threshold: u64,
+        minimum_delay: i64,
+        entry_delay: i64,
+    ) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.govern(vec![ix::create_address_book(
+            &smallet,
+            &payer,
+            threshold,
+            minimum_delay,
+            entry_delay,
+        )
+        .into()])
+    }
+
+    fn add_entry(&mut self, recipient: Pubkey) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.govern(vec![ix::add_address_book_entry(
+            &smallet,
+            &payer,
+            recipient,
+            "vendor".to_string(),
+        )
+        .into()])
+    }
+
+    fn address_book_account(&self) -> AddressBook {
+        let (address, _) = pda::find_address_book_address(&self.smallet);
+        let account = self.bank.get_account(&address).unwrap();
+        AddressBook::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    /// Proposes `instructions` signed by the wallet derived address at index
+    /// 0 as a payment of address book entries, passing the address book.
+    fn propose_payment(
+        &mut self,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let (_, bump) = pda::find_wallet_derived_address(&self.smallet, 0);
+        let proposer = self.owners[0];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        let mut instruction = ix::create_transaction_v2(
+            &smallet,
+            &proposer,
+            &payer,
+            nonce,
+            instructions,
+            eta,
+            TransactionOptions {
+                pda_signers: vec![PdaSigner::Derived { index: 0, bump }],
+                address_book: true,
+                ..Default::default()
+            },
+        );
+        instruction
+            .accounts
+            .push(ix::address_book_account(&smallet));
+        self.process(&[instruction], &[proposer])?;
+        Ok(transaction)
+    }
+
+    /// The wallet derived address at index 0, funded with 1 SOL.
+    fn funded_vault(&mut self) -> Pubkey {
+        let (vault, _) = pda::find_wallet_derived_address(&self.smallet, 0);
+        self.fund(&vault, LAMPORTS_PER_SOL);
+        vault
+    }
+}
+
+#[test]
+fn manage_address_book() {
+    let mut env = Env::new(3, 2, 0);
+    let result = env.create_address_book(1, 60, 0);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::AddressBookPolicySet(e) if e.threshold == 1 && e.minimum_delay == 60
+    )));
+
+    let recipient = Pubkey::new_unique();
+    let result = env.add_entry(recipient);
+    let now = env.now();
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::AddressBookEntryAdd(e) if e.recipient == recipient && e.trusted_at == now
+    )));
+    assert_error(env.add_entry(recipient), ErrorCode::AddressBookEntryExists);
+
+    let smallet = env.smallet;
+    env.govern(vec![ix::set_address_book_policy(&smallet, 2, 0, 100).into()])
+        .unwrap();
+    let address_book = env.address_book_account();
+    assert_eq!(address_book.smallet, smallet);
+    assert_eq!(
+        (
+            address_book.threshold,
+            address_book.minimum_delay,
+            address_book.entry_delay
+        ),
+        (2, 0, 100)
+    );
+    assert_eq!(
+        address_book.entries,
+        vec![AddressBookEntry {
+            recipient,
+            label: "vendor".to_string(),
+            trusted_at: now,
+        }]
+    );
+
+    env.govern(vec![
+        ix::remove_address_book_entry(&smallet, recipient).into()
+    ])
+    .unwrap();
+    assert!(env.address_book_account().entries.is_empty());
+    assert_error(
+        env.govern(vec![
+            ix::remove_address_book_entry(&smallet, recipient).into()
+        ]),
+        ErrorCode::AddressBookEntryNotFound,
+    );
+    assert_error(
+        env.govern(vec![ix::set_address_book_policy(&smallet, 4, 0, 0).into()]),
+        ErrorCode::InvalidThreshold,
+    );
+}
+
+#[test]
+fn pay_entry_with_lower_threshold() {
+    let mut env = Env::with_address_book();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+
+    let transaction = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 2)],
+            NO_ETA,
+        )
+        .unwrap();
+    assert!(env.transaction_account(&transaction).address_book);
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn pay_tokens_to_entry() {
+    let mut env = Env::with_address_book();
+    let (vault, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let mint = Pubkey::new_unique();
+    let source = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    env.set_token_account(source, mint, vault, 1_000);
+    env.set_token_account(destination, mint, recipient, 0);
+    let transfer: TXInstruction =
+        spl_token::instruction::transfer(&spl_token::ID, &source, &destination, &vault, &[], 600)
+            .unwrap()
+            .into();
+
+    let transaction = env.propose_payment(vec![transfer.clone()], NO_ETA).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::RecipientNotTrusted);
+
+    env.add_entry(recipient).unwrap();
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn pay_untrusted_recipient() {
+    let mut env = Env::with_address_book();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let other = Pubkey::new_unique();
+
+    let transaction = env
+        .propose_payment(
+            vec![
+                transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 10),
+                transfer_from(&vault, &other, LAMPORTS_PER_SOL / 10),
+            ],
+            NO_ETA,
+        )
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::RecipientNotTrusted);
+
+    // Only payments are allowed.
+    env.bank.advance_clock(1);
+    let assign = system_instruction::assign(&vault, &Pubkey::new_unique());
+    let transaction = env.propose_payment(vec![assign.into()], NO_ETA).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::RecipientNotTrusted);
+}
+
+#[test]
+fn pay_removed_entry() {
+    let mut env = Env::with_address_book();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let transaction = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 10)],
+            NO_ETA,
+        )
+        .unwrap();
+
+    let smallet = env.smallet;
+    env.govern(vec![
+        ix::remove_address_book_entry(&smallet, recipient).into()
+    ])
+    .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::RecipientNotTrusted);
+}
+
+#[test]
+fn entries_are_timelocked() {
+    let mut env = Env::new(3, 3, 0);
+    env.create_address_book(1, 0, 100).unwrap();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let trusted_at = env.now() + 100;
+    assert_eq!(env.address_book_account().entries[0].trusted_at, trusted_at);
+
+    let transaction = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 10)],
+            NO_ETA,
+        )
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::RecipientNotTrusted);
+    env.bank.warp_to_timestamp(trusted_at);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn address_book_delay() {
+    let mut env = Env::new(3, 3, 0);
+    env.create_address_book(1, 50, 0).unwrap();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+
+    let transfer = transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 10);
+    assert_error(
+        env.propose_payment(vec![transfer.clone()], NO_ETA)
+            .map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    let eta = env.now() + 50;
+    let transaction = env.propose_payment(vec![transfer], eta).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::TransactionNotReady);
+    env.bank.warp_to_timestamp(eta);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn pay_without_address_book() {
+    let mut env = Env::new(3, 3, 0);
+    let vault = env.funded_vault();
+    assert_error(
+        env.propose_payment(
+            vec![transfer_from(
+                &vault,
+                &Pubkey::new_unique(),
+                LAMPORTS_PER_SOL / 10,
+            )],
+            NO_ETA,
+        )
+        .map(|_| vec![]),
+        ErrorCode::MissingAddressBook,
+    );
+
+    env.create_address_book(1, 0, 0).unwrap();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let transaction = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 10)],
+            NO_ETA,
+        )
+        .unwrap();
+    let owner = env.owners[0];
+    let tx = env.transaction_account(&transaction);
+    let mut instruction = ix::execute_transaction(&env.smallet, &transaction, &tx, &owner);
+    instruction.accounts.pop();
+    assert_error(
+        env.process(&[instruction], &[owner]),
+        ErrorCode::MissingAddressBook,
+    );
+}
+
+#[test]
+fn transfer_to_entry() {
+    let mut env = Env::with_address_book();
+    let (vault, _) = SubaccountType::Derived.find_address(&env.smallet, 0);
+    env.fund(&vault, LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let transfer = VaultTransfer {
+        asset: TransferAsset::Sol,
+        amount: LAMPORTS_PER_SOL / 10,
+        source_index: 0,
+        recipient,
+    };
+
+    let propose = |env: &mut Env, with_address_book: bool| {
+        env.bank.advance_clock(1);
+        let proposer = env.owners[0];
+        let nonce = env.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&env.smallet, &proposer, nonce);
+        let smallet = env.smallet;
+        let payer = env.payer;
+        let mut instruction =
+            ix::propose_transfer(&smallet, &proposer, &payer, nonce, transfer, NO_ETA);
+        if with_address_book {
+            instruction
+                .accounts
+                .push(ix::address_book_account(&smallet));
+        }
+        env.process(&[instruction], &[proposer]).unwrap();
+        transaction
+    };
+
+    // Without the address book, the threshold of the smallet applies.
+    let transaction = propose(&mut env, false);
+    assert!(!env.transaction_account(&transaction).address_book);
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+
+    let transaction = propose(&mut env, true);
+    assert!(env.transaction_account(&transaction).address_book);
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 10);
+}
+
+#[test]
+fn fifo_blocks_behind_approved_payment() {
+    // The payment has the approvals the address book needs, though not those
+    // the smallet needs, so later transactions wait for it.
+    let mut env = Env::with_address_book();
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let smallet = env.smallet;
+    env.govern(vec![ix::set_fifo_mode(&smallet, true).into()])
+        .unwrap();
+
+    let payment = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 2)],
+            NO_ETA,
+        )
+        .unwrap();
+    env.process(&[ix::assign_transaction_index(&smallet, &payment)], &[])
+        .unwrap();
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.approve(&transaction, 1).unwrap();
+    env.approve(&transaction, 2).unwrap();
+
+    let owner = env.owners[0];
+    let tx = env.transaction_account(&transaction);
+    let mut execute = ix::execute_transaction(&smallet, &transaction, &tx, &owner);
+    execute
+        .accounts
+        .extend(ix::transaction_accounts(&[payment]));
+    assert_error(
+        env.process(&[execute.clone()], &[owner]),
+        ErrorCode::MissingAddressBook,
+    );
+    execute.accounts.push(ix::address_book_account(&smallet));
+    assert_error(
+        env.process(&[execute.clone()], &[owner]),
+        ErrorCode::EarlierTransactionPending,
+    );
+
+    env.execute(&payment, 0).unwrap();
+    env.process(&[execute], &[owner]).unwrap();
+}
+
+#[test]
+fn address_book_threshold_is_positive() {
+    let mut env = Env::new(3, 2, 0);
+    assert_error(
+        env.create_address_book(0, 0, 0),
+        ErrorCode::InvalidThreshold,
+    );
+    env.create_address_book(1, 0, 0).unwrap();
+    let smallet = env.smallet;
+    assert_error(
+        env.govern(vec![ix::set_address_book_policy(&smallet, 0, 0, 0).into()]),
+        ErrorCode::InvalidThreshold,
+    );
+    assert_eq!(env.address_book_account().threshold, 1);
+}
+
+#[test]
+fn set_owners_lowers_address_book_threshold() {
+    let mut env = Env::new(3, 3, 0);
+    env.create_address_book(3, 0, 0).unwrap();
+
+    let owners = env.owners[..2].to_vec();
+    let result = env.set_owners(owners);
+    assert_eq!(env.address_book_account().threshold, 2);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::AddressBookPolicySet(e) if e.threshold == 2
+    )));
+
+    // Without an address book, its address is not required.
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let mut set_owners = ix::set_owners(&smallet, vec![env.owners[0]]);
+    set_owners.accounts.pop();
+    env.govern(vec![set_owners.into()]).unwrap();
+    assert_eq!(env.smallet_account().owners, vec![env.owners[0]]);
+}
+
+#[test]
+fn set_owners_proposed_without_address_book() {
+    // Transactions proposed before the address book was passed to
+    // `set_owners` leave its threshold as is, so it is clamped to the owner
+    // count when paying.
+    let mut env = Env::new(3, 3, 0);
+    env.create_address_book(3, 0, 0).unwrap();
+    let smallet = env.smallet;
+    let owners = env.owners[..2].to_vec();
+    let mut set_owners = ix::set_owners(&smallet, owners.clone());
+    set_owners.accounts.pop();
+    env.govern(vec![set_owners.into()]).unwrap();
+    env.owners = owners;
+    assert_eq!(env.smallet_account().threshold, 2);
+    assert_eq!(env.address_book_account().threshold, 3);
+
+    let vault = env.funded_vault();
+    let recipient = Pubkey::new_unique();
+    env.add_entry(recipient).unwrap();
+    let transaction = env
+        .propose_payment(
+            vec![transfer_from(&vault, &recipient, LAMPORTS_PER_SOL / 2)],
+            NO_ETA,
+        )
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+    env.approve(&transaction, 1).unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
@@ -0,0 +1,82 @@
+use smallet::{ErrorCode, NO_ETA, SECONDS_PER_DAY};
+use smallet_client::events::SmalletEvent;
+use smallet_client::instructions as ix;
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+impl Env {
+    fn change_approval_ttl(&mut self, approval_ttl: i64) -> TransactionResult {
+        let smallet = self.smallet;
+        self.govern(vec![ix::change_approval_ttl(&smallet, approval_ttl).into()])
+    }
+}
+
+#[test]
+fn approvals_carry_timestamps() {
+    let mut env = Env::new(3, 2, 0);
+    let created_at = env.now();
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.bank.advance_clock(60);
+    env.approve(&transaction, 2).unwrap();
+    assert_eq!(
+        env.transaction_account(&transaction).approved_at,
+        vec![created_at, 0, created_at + 60]
+    );
+
+    env.unapprove(&transaction, 0).unwrap();
+    assert_eq!(
+        env.transaction_account(&transaction).approved_at,
+        vec![0, 0, created_at + 60]
+    );
+}
+
+#[test]
+fn expired_approvals_do_not_count() {
+    let mut env = Env::new(3, 2, 0);
+    env.change_approval_ttl(SECONDS_PER_DAY).unwrap();
+    let smallet = env.smallet;
+    let transaction = env.propose(0, vec![ix::change_threshold(&smallet, 3).into()], NO_ETA);
+    env.approve(&transaction, 1).unwrap();
+
+    env.bank.advance_clock(SECONDS_PER_DAY + 1);
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+
+    // A single renewed approval is not enough, both are.
+    env.approve(&transaction, 1).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+    env.approve(&transaction, 0).unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.smallet_account().threshold, 3);
+}
+
+#[test]
+fn approvals_within_ttl() {
+    let mut env = Env::new(3, 2, 0);
+    env.change_approval_ttl(SECONDS_PER_DAY).unwrap();
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.approve(&transaction, 1).unwrap();
+    env.bank.advance_clock(SECONDS_PER_DAY);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn approvals_without_ttl_never_expire() {
+    let mut env = Env::new(3, 2, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.approve(&transaction, 1).unwrap();
+    env.bank.advance_clock(365 * SECONDS_PER_DAY);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn change_approval_ttl() {
+    let mut env = Env::new(2, 1, 0);
+    let result = env.change_approval_ttl(SECONDS_PER_DAY);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::WalletChangeApprovalTtl(e) if e.approval_ttl == SECONDS_PER_DAY
+    )));
+    assert_eq!(env.smallet_account().approval_ttl, SECONDS_PER_DAY);
+    assert_invariant_failed(env.change_approval_ttl(-1));
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use smallet::{PdaSigner, SubaccountType, TXInstruction, TransactionOptions, NO_ETA};
+use smallet_client::{accounts, instructions as ix, pda};
+
+use crate::helpers::*;
+
+#[test]
+fn addresses_match_program_accounts() {
+    let mut env = Env::new(2, 1, 0);
+    assert_eq!(env.smallet, pda::find_smallet(&env.base).0);
+    assert_eq!(env.smallet_account().bump, pda::find_smallet(&env.base).1);
+
+    let transaction = env.propose(0, vec![], NO_ETA);
+    assert_eq!(
+        transaction,
+        pda::find_transaction_address(&env.smallet, 0).0
+    );
+    assert_eq!(
+        env.transaction_account(&transaction).bump,
+        pda::find_transaction_address(&env.smallet, 0).1
+    );
+
+    assert_eq!(
+        SubaccountType::Derived.find_address(&env.smallet, 3),
+        pda::find_wallet_derived_address(&env.smallet, 3)
+    );
+    assert_eq!(
+        SubaccountType::OwnerInvoker.find_address(&env.smallet, 3),
+        pda::find_owner_invoker_address(&env.smallet, 3)
+    );
+    assert_ne!(
+        pda::find_wallet_derived_address(&env.smallet, 3).0,
+        pda::find_owner_invoker_address(&env.smallet, 3).0
+    );
+}
+
+#[test]
+fn decode_accounts() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    let smallet = env.bank.get_account(&env.smallet).unwrap().data;
+    let tx = env.bank.get_account(&transaction).unwrap().data;
+
+    assert_eq!(
+        accounts::decode_smallet(&smallet).unwrap().owners,
+        env.owners
+    );
+    assert_eq!(
+        accounts::decode_transaction(&tx).unwrap().proposer,
+        env.owners[0]
+    );
+
+    // Decoders check the discriminator of the account.
+    assert!(accounts::decode_transaction(&smallet).is_err());
+    assert!(accounts::decode_smallet(&tx).is_err());
+    assert!(accounts::decode_subaccount_info(&smallet).is_err());
+}
+
+#[test]
+fn execute_transaction_strips_pda_signatures() {
+    let mut env = Env::new(1, 1, 0);
+    let smallet = env.smallet;
+    let (wallet, bump) = pda::find_wallet_derived_address(&smallet, 0);
+    let recipient = Pubkey::new_unique();
+    let instructions: Vec<TXInstruction> = vec![
+        transfer_from(&wallet, &recipient, 1),
+        ix::change_threshold(&smallet, 1).into(),
+    ];
+    let proposer = env.owners[0];
+    let payer = env.payer;
+    env.process(
+        &[ix::create_transaction_v2(
+            &smallet,
+            &proposer,
+            &payer,
+            0,
+            instructions.clone(),
+            NO_ETA,
+            TransactionOptions {
+                pda_signers: vec![PdaSigner::Derived { index: 0, bump }, PdaSigner::Smallet],
+                ..Default::default()
+            },
+        )],
+        &[proposer],
+    )
+    .unwrap();
+    let (transaction, _) = pda::find_transaction_v2_address(&smallet, &proposer, 0);
+    let tx = env.transaction_account(&transaction);
+
+    // The program signs as the declared PDAs, so the executor does not.
+    let execute = ix::execute_transaction(&smallet, &transaction, &tx, &proposer);
+    let remaining = &execute.accounts[3..];
+    assert_eq!(remaining.len(), 3 + 2);
+    assert_eq!(remaining[0].pubkey, system_program::ID);
+    assert_eq!(remaining[3].pubkey, smallet::ID);
+    assert!(remaining.iter().all(|meta| !meta.is_signer));
+    assert_eq!(
+        remaining
+            .iter()
+            .map(|meta| meta.pubkey)
+            .filter(|key| *key == wallet || *key == smallet)
+            .count(),
+        2
+    );
+}
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, PdaSigner, TXInstruction, Transaction, TransactionOptions, NO_ETA};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+/// Salt of the committed hashes.
+const SALT: [u8; 32] = [7; 32];
+
+impl Env {
+    /// Proposes the hash of `instructions` via `create_committed_transaction`.
+    fn propose_committed(
+        &mut self,
+        proposer: usize,
+        nonce: u64,
+        instructions: &[TXInstruction],
+        pda_signers: Vec<PdaSigner>,
+    ) -> Pubkey {
+        let proposer = self.owners[proposer];
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_committed_transaction(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                &SALT,
+                instructions,
+                NO_ETA,
+                TransactionOptions {
+                    pda_signers,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )
+        .unwrap();
+        transaction
+    }
+
+    fn reveal(
+        &mut self,
+        transaction: &Pubkey,
+        owner: usize,
+        instructions: Vec<TXInstruction>,
+    ) -> TransactionResult {
+        self.reveal_with_salt(transaction, owner, SALT, instructions)
+    }
+
+    fn reveal_with_salt(
+        &mut self,
+        transaction: &Pubkey,
+        owner: usize,
+        salt: [u8; 32],
+        instructions: Vec<TXInstruction>,
+    ) -> TransactionResult {
+        let owner = self.owners[owner];
+        let tx = self.transaction_account(transaction);
+        self.process(
+            &[ix::execute_committed_transaction(
+                &self.smallet,
+                transaction,
+                &tx,
+                &owner,
+                salt,
+                instructions,
+            )],
+            &[owner],
+        )
+    }
+}
+
+#[test]
+fn commit_and_reveal() {
+    let mut env = Env::new(3, 2, 0);
+    let smallet = env.smallet;
+    let instructions: Vec<TXInstruction> = vec![ix::change_threshold(&smallet, 3).into()];
+    let hash = Transaction::hash_instructions(&SALT, &instructions).unwrap();
+
+    let transaction = env.propose_committed(0, 0, &instructions, vec![]);
+    let tx = env.transaction_account(&transaction);
+    assert!(tx.instructions.is_empty());
+    assert_eq!(tx.instructions_hash, Some(hash));
+
+    env.approve(&transaction, 1).unwrap();
+    let result = env.reveal(&transaction, 1, instructions.clone());
+    assert!(result.is_ok());
+    assert_eq!(env.smallet_account().threshold, 3);
+    assert_eq!(
+        env.transaction_account(&transaction).executor,
+        env.owners[1]
+    );
+
+    let events = events(&result);
+    assert!(matches!(
+        &events[0],
+        SmalletEvent::TransactionReveal(e) if e.salt == SALT && e.instructions == instructions
+    ));
+    assert!(matches!(
+        events.last(),
+        Some(SmalletEvent::TransactionExecute(e)) if e.transaction == transaction
+    ));
+}
+
+#[test]
+fn commit_publishes_only_the_hash() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let instructions: Vec<TXInstruction> = vec![ix::change_threshold(&smallet, 2).into()];
+    let proposer = env.owners[0];
+    let payer = env.payer;
+    let result = env.process(
+        &[ix::create_committed_transaction(
+            &smallet,
+            &proposer,
+            &payer,
+            0,
+            &SALT,
+            &instructions,
+            NO_ETA,
+            TransactionOptions::default(),
+        )],
+        &[proposer],
+    );
+
+    let events = events(&result);
+    assert!(matches!(
+        &events[0],
+        SmalletEvent::TransactionCreate(e) if e.instructions.is_empty()
+    ));
+    assert!(matches!(
+        &events[1],
+        SmalletEvent::TransactionCommit(e)
+            if e.instructions_hash == Transaction::hash_instructions(&SALT, &instructions).unwrap()
+    ));
+}
+
+#[test]
+fn reveal_other_instructions() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let transaction =
+        env.propose_committed(0, 0, &[ix::change_threshold(&smallet, 2).into()], vec![]);
+    assert_error(
+        env.reveal(
+            &transaction,
+            0,
+            vec![ix::change_threshold(&smallet, 0).into()],
+        ),
+        ErrorCode::InstructionsHashMismatch,
+    );
+    assert_error(
+        env.reveal(&transaction, 0, vec![]),
+        ErrorCode::InstructionsHashMismatch,
+    );
+    assert_eq!(env.smallet_account().threshold, 1);
+}
+
+#[test]
+fn commitment_is_salted() {
+    let smallet = Pubkey::new_unique();
+    let instructions: Vec<TXInstruction> = vec![ix::change_threshold(&smallet, 2).into()];
+    assert_ne!(
+        Transaction::hash_instructions(&SALT, &instructions).unwrap(),
+        Transaction::hash_instructions(&[0; 32], &instructions).unwrap()
+    );
+    assert_ne!(
+        Transaction::hash_instructions(&SALT, &instructions).unwrap(),
+        anchor_lang::solana_program::hash::hash(&instructions.try_to_vec().unwrap()).to_bytes()
+    );
+}
+
+#[test]
+fn reveal_with_other_salt() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let instructions: Vec<TXInstruction> = vec![ix::change_threshold(&smallet, 2).into()];
+    let transaction = env.propose_committed(0, 0, &instructions, vec![]);
+    assert_error(
+        env.reveal_with_salt(&transaction, 0, [0; 32], instructions.clone()),
+        ErrorCode::InstructionsHashMismatch,
+    );
+    env.reveal(&transaction, 0, instructions).unwrap();
+    assert_eq!(env.smallet_account().threshold, 2);
+}
+
+#[test]
+fn execute_committed_without_reveal() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let transaction =
+        env.propose_committed(0, 0, &[ix::change_threshold(&smallet, 2).into()], vec![]);
+    assert_error(
+        env.execute(&transaction, 0),
+        ErrorCode::InstructionsNotRevealed,
+    );
+}
+
+#[test]
+fn reveal_uncommitted_transaction() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    assert_error(
+        env.reveal(&transaction, 0, vec![]),
+        ErrorCode::TransactionNotCommitted,
+    );
+}
+
+#[test]
+fn reveal_signed_by_derived_address() {
+    let mut env = Env::new(2, 1, 0);
+    let (wallet, bump) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let recipient = Pubkey::new_unique();
+    env.fund(&wallet, LAMPORTS_PER_SOL);
+
+    let instructions = vec![transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 2)];
+    let transaction = env.propose_committed(
+        0,
+        0,
+        &instructions,
+        vec![PdaSigner::Derived { index: 0, bump }],
+    );
+    env.reveal(&transaction, 0, instructions).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use smallet::{ErrorCode, DEFAULT_GRACE_PERIOD, MAX_DELAY_SECONDS, SMALLET_VERSION};
+use smallet_client::events::SmalletEvent;
+use smallet_program_test::InstructionError;
+
+use crate::helpers::*;
+
+#[test]
+fn create_smallet() {
+    let mut env = Env::empty();
+    let owners = vec![
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    ];
+    let result = env.create_smallet(5, owners.clone(), 2, 60);
+
+    let smallet = env.smallet_account();
+    assert_eq!(smallet.base, env.base);
+    assert_eq!(smallet.owners, owners);
+    assert_eq!(smallet.threshold, 2);
+    assert_eq!(smallet.minimum_delay, 60);
+    assert_eq!(smallet.grace_period, DEFAULT_GRACE_PERIOD);
+    assert_eq!(smallet.owner_set_seqno, 0);
+    assert_eq!(smallet.num_transactions, 0);
+    assert_eq!(smallet.num_subaccounts, 0);
+    assert_eq!(smallet.version, SMALLET_VERSION);
+
+    let account = env.bank.get_account(&env.smallet).unwrap();
+    assert_eq!(account.owner, smallet::ID);
+    assert_eq!(account.data.len(), smallet::Smallet::space(5));
+
+    match &events(&result)[..] {
+        [SmalletEvent::WalletCreate(event)] => {
+            assert_eq!(event.smallet, env.smallet);
+            assert_eq!(event.owners, owners);
+            assert_eq!(event.threshold, 2);
+            assert_eq!(event.minimum_delay, 60);
+            assert_eq!(event.timestamp, env.now());
+        }
+        events => panic!("unexpected events {:?}", events),
+    }
+}
+
+#[test]
+fn create_smallet_twice() {
+    let mut env = Env::new(2, 1, 0);
+    let owners = env.owners.clone();
+    let result = env.create_smallet(2, owners, 1, 0);
+    assert_error(result, 0u32); // SystemError::AccountAlreadyInUse
+}
+
+#[test]
+fn create_smallet_requires_base_signature() {
+    let mut env = Env::empty();
+    let base = env.base;
+    let payer = env.payer;
+    let ix = smallet_client::instructions::create_smallet(
+        &base,
+        &payer,
+        1,
+        vec![Pubkey::new_unique()],
+        1,
+        0,
+    );
+    let err = env.bank.process_transaction(&[ix], &[payer]).unwrap_err();
+    assert_eq!(err.error, InstructionError::MissingSigner(base));
+    assert!(env.bank.get_account(&env.smallet).is_none());
+    assert_eq!(
+        env.bank.get_account(&payer).unwrap().owner,
+        system_program::ID
+    );
+}
+
+#[test]
+fn create_smallet_delay_too_high() {
+    let mut env = Env::empty();
+    let owners = vec![Pubkey::new_unique()];
+    assert_error(
+        env.create_smallet(1, owners.clone(), 1, MAX_DELAY_SECONDS),
+        ErrorCode::DelayTooHigh,
+    );
+    env.create_smallet(1, owners, 1, MAX_DELAY_SECONDS - 1)
+        .unwrap();
+}
+
+#[test]
+fn create_smallet_threshold_above_owners() {
+    let mut env = Env::empty();
+    let owners = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    assert_error(
+        env.create_smallet(3, owners.clone(), 3, 0),
+        ErrorCode::InvalidThreshold,
+    );
+    env.create_smallet(3, owners, 2, 0).unwrap();
+}
+
+#[test]
+fn create_smallet_negative_delay() {
+    let mut env = Env::empty();
+    assert_invariant_failed(env.create_smallet(1, vec![Pubkey::new_unique()], 1, -1));
+}
+
+#[test]
+fn create_smallet_too_many_owners() {
+    let mut env = Env::empty();
+    let owners = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    assert_invariant_failed(env.create_smallet(1, owners, 1, 0));
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, DEFAULT_GRACE_PERIOD, NO_ETA};
+use smallet_client::events::SmalletEvent;
+use smallet_client::instructions as ix;
+
+use crate::helpers::*;
+
+#[test]
+fn execute_transaction() {
+    let mut env = Env::new(3, 2, 0);
+    let smallet = env.smallet;
+    let transaction = env.propose(0, vec![ix::change_threshold(&smallet, 3).into()], NO_ETA);
+    env.approve(&transaction, 2).unwrap();
+
+    let result = env.execute(&transaction, 1).unwrap();
+    assert_eq!(env.smallet_account().threshold, 3);
+
+    let tx = env.transaction_account(&transaction);
+    assert_eq!(tx.executor, env.owners[1]);
+    assert_eq!(tx.executed_at, env.now());
+    assert!(matches!(
+        &events(&Ok(result))[..],
+        [
+            SmalletEvent::WalletChangeThreshold(_),
+            SmalletEvent::TransactionExecute(event)
+        ] if event.transaction == transaction && event.executor == env.owners[1]
+    ));
+}
+
+#[test]
+fn execute_without_enough_signers() {
+    let mut env = Env::new(3, 2, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+
+    env.approve(&transaction, 1).unwrap();
+    env.unapprove(&transaction, 0).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+
+    env.approve(&transaction, 2).unwrap();
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn execute_by_non_owner() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    let tx = env.transaction_account(&transaction);
+    let stranger = Pubkey::new_unique();
+    let smallet = env.smallet;
+    assert_error(
+        env.process(
+            &[ix::execute_transaction(
+                &smallet,
+                &transaction,
+                &tx,
+                &stranger,
+            )],
+            &[stranger],
+        ),
+        ErrorCode::InvalidOwner,
+    );
+}
+
+#[test]
+fn execute_twice() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.execute(&transaction, 0).unwrap();
+    env.bank.advance_clock(1);
+    assert_error(env.execute(&transaction, 1), ErrorCode::AlreadyExecuted);
+}
+
+#[test]
+fn execute_after_owner_set_change() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.set_owners(env.owners.clone()).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::OwnerSetChanged);
+}
+
+#[test]
+fn execute_failing_instruction_is_atomic() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let transaction = env.propose(
+        0,
+        vec![
+            ix::change_threshold(&smallet, 2).into(),
+            ix::change_threshold(&smallet, 3).into(),
+        ],
+        NO_ETA,
+    );
+    assert_error(env.execute(&transaction, 0), ErrorCode::InvalidThreshold);
+    assert_eq!(env.smallet_account().threshold, 1);
+    assert_eq!(env.transaction_account(&transaction).executed_at, -1);
+}
+
+#[test]
+fn execute_transfer_from_smallet() {
+    // The smallet carries data, so the system program refuses to debit it:
+    // funds are held by derived wallets instead.
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    env.fund(&smallet, LAMPORTS_PER_SOL);
+    let transaction = env.propose(
+        0,
+        vec![transfer_from(&smallet, &Pubkey::new_unique(), 1)],
+        NO_ETA,
+    );
+    assert_program_error(env.execute(&transaction, 0), ProgramError::InvalidArgument);
+}
+
+#[test]
+fn execute_at_timelock_edges() {
+    let mut env = Env::new(2, 1, 60);
+    let eta = env.now() + 60;
+    let transaction = env.propose(0, vec![], eta);
+
+    env.bank.warp_to_timestamp(eta - 1);
+    assert_error(env.execute(&transaction, 0), ErrorCode::TransactionNotReady);
+
+    env.bank.warp_to_timestamp(eta);
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.transaction_account(&transaction).executed_at, eta);
+}
+
+#[test]
+fn execute_at_grace_period_edges() {
+    let mut env = Env::new(2, 1, 60);
+    let eta = env.now() + 60;
+    let stale = env.propose(0, vec![], eta);
+    let last_chance = env.propose(0, vec![], eta);
+
+    env.bank.warp_to_timestamp(eta + DEFAULT_GRACE_PERIOD);
+    env.execute(&last_chance, 0).unwrap();
+
+    env.bank.warp_to_timestamp(eta + DEFAULT_GRACE_PERIOD + 1);
+    assert_error(env.execute(&stale, 0), ErrorCode::TransactionIsStale);
+}
+
+#[test]
+fn execute_without_eta_never_goes_stale() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.bank.advance_clock(10 * DEFAULT_GRACE_PERIOD);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn execute_with_other_smallet() {
+    let mut env = Env::new(1, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    let tx = env.transaction_account(&transaction);
+
+    let owners = env.owners.clone();
+    env.base = Pubkey::new_unique();
+    env.smallet = smallet_client::pda::find_smallet(&env.base).0;
+    env.create_smallet(1, owners, 1, 0).unwrap();
+
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+    assert_key_mismatch(env.process(
+        &[ix::execute_transaction(&smallet, &transaction, &tx, &owner)],
+        &[owner],
+    ));
+}
@@ -0,0 +1,317 @@
+//! Shared setup for the smallet tests.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use smallet::{Smallet, TXInstruction, Transaction, TransactionOptions};
+use smallet_client::events::{parse_logs, SmalletEvent};
+use smallet_client::instructions as ix;
+use smallet_client::pda;
+use smallet_program_test::{Account, Bank, ProgramTest, TransactionResult};
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// A bank holding a smallet.
+pub struct Env {
+    pub bank: Bank,
+    pub payer: Pubkey,
+    pub base: Pubkey,
+    pub smallet: Pubkey,
+    pub owners: Vec<Pubkey>,
+}
+
+/// A bank running the smallet and SPL Token programs.
+pub fn program_test() -> ProgramTest {
+    ProgramTest::default()
+        .add_program(smallet::ID, smallet::entry)
+        .add_program(spl_token::ID, spl_token::processor::Processor::process)
+}
+
+impl Env {
+    /// Creates a smallet with `num_owners` owners.
+    pub fn new(num_owners: usize, threshold: u64, minimum_delay: i64) -> Self {
+        let mut env = Self::empty();
+        let owners: Vec<Pubkey> = (0..num_owners).map(|_| Pubkey::new_unique()).collect();
+        env.create_smallet(owners.len() as u8, owners, threshold, minimum_delay)
+            .unwrap();
+        env
+    }
+
+    /// A bank with a funded payer and no smallet.
+    pub fn empty() -> Self {
+        let mut bank = program_test().start();
+        let payer = Pubkey::new_unique();
+        bank.airdrop(&payer, 100 * LAMPORTS_PER_SOL);
+        let base = Pubkey::new_unique();
+        Self {
+            bank,
+            payer,
+            base,
+            smallet: pda::find_smallet(&base).0,
+            owners: vec![],
+        }
+    }
+
+    pub fn create_smallet(
+        &mut self,
+        max_owners: u8,
+        owners: Vec<Pubkey>,
+        threshold: u64,
+        minimum_delay: i64,
+    ) -> TransactionResult {
+        self.owners = owners.clone();
+        let base = self.base;
+        self.process(
+            &[ix::create_smallet(
+                &base,
+                &self.payer.clone(),
+                max_owners,
+                owners,
+                threshold,
+                minimum_delay,
+            )],
+            &[base],
+        )
+    }
+
+    /// Processes a transaction signed by the payer and `signers`.
+    pub fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[Pubkey],
+    ) -> TransactionResult {
+        let mut all = vec![self.payer];
+        all.extend_from_slice(signers);
+        self.bank.process_transaction(instructions, &all)
+    }
+
+    pub fn now(&self) -> i64 {
+        self.bank.clock().unix_timestamp
+    }
+
+    pub fn smallet_account(&self) -> Smallet {
+        let account = self.bank.get_account(&self.smallet).unwrap();
+        Smallet::try_deserialize_versioned(&account.data).unwrap()
+    }
+
+    pub fn transaction_account(&self, transaction: &Pubkey) -> Transaction {
+        let account = self.bank.get_account(transaction).unwrap();
+        Transaction::try_deserialize_versioned(&account.data).unwrap()
+    }
+
+    /// Proposes `instructions` via `create_transaction_with_timelock` as the
+    /// owner at `proposer`.
+    pub fn try_propose(
+        &mut self,
+        proposer: usize,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+    ) -> std::result::Result<Pubkey, smallet_program_test::TransactionError> {
+        let index = self.smallet_account().num_transactions;
+        let (transaction, _) = pda::find_transaction_address(&self.smallet, index);
+        let proposer = self.owners[proposer];
+        self.process(
+            &[ix::create_transaction_with_timelock(
+                &self.smallet,
+                index,
+                &proposer,
+                &self.payer.clone(),
+                instructions,
+                eta,
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+
+    pub fn propose(
+        &mut self,
+        proposer: usize,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+    ) -> Pubkey {
+        self.try_propose(proposer, instructions, eta).unwrap()
+    }
+
+    pub fn approve(&mut self, transaction: &Pubkey, owner: usize) -> TransactionResult {
+        let owner = self.owners[owner];
+        self.process(&[ix::approve(&self.smallet, transaction, &owner)], &[owner])
+    }
+
+    pub fn unapprove(&mut self, transaction: &Pubkey, owner: usize) -> TransactionResult {
+        let owner = self.owners[owner];
+        self.process(
+            &[ix::unapprove(&self.smallet, transaction, &owner)],
+            &[owner],
+        )
+    }
+
+    pub fn execute(&mut self, transaction: &Pubkey, owner: usize) -> TransactionResult {
+        let owner = self.owners[owner];
+        let tx = self.transaction_account(transaction);
+        self.process(
+            &[ix::execute_transaction(
+                &self.smallet,
+                transaction,
+                &tx,
+                &owner,
+            )],
+            &[owner],
+        )
+    }
+
+    /// Proposes `instructions` without a tier via `create_transaction_v2`,
+    /// as smallets with timelock tiers require, using the first free nonce.
+    pub fn try_propose_untiered(
+        &mut self,
+        proposer: usize,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+    ) -> std::result::Result<Pubkey, smallet_program_test::TransactionError> {
+        let proposer = self.owners[proposer];
+        let (transaction, nonce) = (0..)
+            .map(|nonce| {
+                let (address, _) =
+                    pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+                (address, nonce)
+            })
+            .find(|(address, _)| self.bank.get_account(address).is_none())
+            .unwrap();
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                instructions,
+                eta,
+                TransactionOptions::default(),
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+
+    /// Approves `transaction` by the first owners, as many as untiered
+    /// transactions need.
+    pub fn approve_threshold(&mut self, transaction: &Pubkey) {
+        let threshold = self.smallet_account().threshold_for(None).unwrap() as usize;
+        for owner in 0..threshold {
+            self.approve(transaction, owner).unwrap();
+        }
+    }
+
+    /// Proposes, approves by enough owners and executes `instructions`,
+    /// waiting out the minimum delay of untiered transactions.
+    pub fn govern(&mut self, instructions: Vec<TXInstruction>) -> TransactionResult {
+        let smallet = self.smallet_account();
+        let minimum_delay = smallet.minimum_delay_for(None).unwrap();
+        let eta = if minimum_delay == 0 {
+            smallet::NO_ETA
+        } else {
+            self.now() + minimum_delay
+        };
+        let transaction = if smallet.tiers.is_empty() {
+            self.propose(0, instructions, eta)
+        } else {
+            self.try_propose_untiered(0, instructions, eta).unwrap()
+        };
+        self.approve_threshold(&transaction);
+        if eta != smallet::NO_ETA {
+            self.bank.warp_to_timestamp(eta);
+        }
+        self.execute(&transaction, 0)
+    }
+
+    /// Funds a system account.
+    pub fn fund(&mut self, address: &Pubkey, lamports: u64) {
+        self.bank.airdrop(address, lamports);
+    }
+
+    /// Funds the wallet derived address at index 0 and returns a transfer
+    /// out of it.
+    pub fn wallet_transfer(&mut self, to: &Pubkey, lamports: u64) -> TXInstruction {
+        let (wallet, _) = pda::find_wallet_derived_address(&self.smallet, 0);
+        self.fund(&wallet, LAMPORTS_PER_SOL);
+        transfer_from(&wallet, to, lamports)
+    }
+
+    /// Sets a raw account.
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.bank.set_account(address, account);
+    }
+
+    /// Sets a token account of `mint` owned by `owner` and holding `amount`.
+    pub fn set_token_account(&mut self, address: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.set_account(address, Account::new_data(data, &spl_token::ID));
+    }
+
+    /// Replaces the owners of the smallet through governance.
+    pub fn set_owners(&mut self, owners: Vec<Pubkey>) -> TransactionResult {
+        let smallet = self.smallet;
+        let result = self.govern(vec![ix::set_owners(&smallet, owners.clone()).into()]);
+        if result.is_ok() {
+            self.owners = owners;
+        }
+        result
+    }
+}
+
+/// Asserts that a transaction failed with the given error code.
+#[track_caller]
+pub fn assert_error(result: TransactionResult, code: impl Into<u32>) {
+    let code = code.into();
+    match result {
+        Ok(_) => panic!("expected error {}, but the transaction succeeded", code),
+        Err(err) => assert_eq!(
+            err.program_error(),
+            Some(&ProgramError::Custom(code)),
+            "{}",
+            err
+        ),
+    }
+}
+
+/// Asserts that a transaction failed with a `ProgramError`.
+#[track_caller]
+pub fn assert_program_error(result: TransactionResult, error: ProgramError) {
+    match result {
+        Ok(_) => panic!("expected {:?}, but the transaction succeeded", error),
+        Err(err) => assert_eq!(err.program_error(), Some(&error), "{}", err),
+    }
+}
+
+/// Asserts that a transaction failed a `vipers` invariant without an error
+/// code of its own.
+#[track_caller]
+pub fn assert_invariant_failed(result: TransactionResult) {
+    assert_error(result, vipers::VipersError::InvariantFailed);
+}
+
+/// Asserts that a transaction failed because two keys did not match.
+#[track_caller]
+pub fn assert_key_mismatch(result: TransactionResult) {
+    assert_error(result, vipers::VipersError::KeyMismatch);
+}
+
+/// Events emitted by the smallet program in `logs`.
+pub fn events(result: &TransactionResult) -> Vec<SmalletEvent> {
+    parse_logs(result.as_ref().unwrap())
+}
+
+/// A transfer of lamports from the smallet, as a proposed instruction.
+pub fn transfer_from(from: &Pubkey, to: &Pubkey, lamports: u64) -> TXInstruction {
+    anchor_lang::solana_program::system_instruction::transfer(from, to, lamports).into()
+}
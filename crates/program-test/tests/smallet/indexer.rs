@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use rusqlite::{params, Connection};
+use smallet::{TimelockTier, NO_ETA};
+use smallet_client::instructions as ix;
+use smallet_client::rpc::RpcAccount;
+use smallet_indexer::{db, indexer};
+
+use crate::helpers::*;
+
+/// An empty database of the indexer, in memory.
+fn database() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    db::migrate(&conn).unwrap();
+    conn
+}
+
+fn user_version(conn: &Connection) -> i64 {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .unwrap()
+}
+
+/// Rows of `pending_transactions_by_owner` for `owner`: the transaction, its
+/// threshold, its approvals and whether `owner` approved it.
+fn pending_for(conn: &Connection, owner: &Pubkey) -> Vec<(String, u64, u64, bool)> {
+    let mut statement = conn
+        .prepare(
+            "SELECT address, threshold, num_approvals, approved_by_owner
+             FROM pending_transactions_by_owner WHERE owner = ?1 ORDER BY address",
+        )
+        .unwrap();
+    let rows = statement
+        .query_map(params![owner.to_string()], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .unwrap();
+    rows.collect::<rusqlite::Result<_>>().unwrap()
+}
+
+impl Env {
+    /// Stores `addresses` like the indexer does after fetching them.
+    fn ingest(&self, conn: &Connection, addresses: &[Pubkey]) {
+        let slot = self.bank.clock().slot;
+        for address in addresses {
+            let account = self.bank.get_account(address).map(|account| RpcAccount {
+                lamports: account.lamports,
+                owner: account.owner,
+                data: account.data,
+            });
+            indexer::store_account(conn, address, account.as_ref(), slot).unwrap();
+        }
+    }
+}
+
+#[test]
+fn migrate_database() {
+    let conn = database();
+    assert_eq!(user_version(&conn), db::SCHEMA_VERSION);
+    db::migrate(&conn).unwrap();
+    assert!(pending_for(&conn, &Pubkey::new_unique()).is_empty());
+
+    // Databases of older indexers get the current views.
+    conn.execute_batch(
+        "DROP VIEW pending_transactions_by_owner;
+         CREATE VIEW pending_transactions_by_owner AS SELECT address FROM transactions;
+         PRAGMA user_version = 0;",
+    )
+    .unwrap();
+    assert!(conn
+        .prepare("SELECT owner FROM pending_transactions_by_owner")
+        .is_err());
+    db::migrate(&conn).unwrap();
+    assert_eq!(user_version(&conn), db::SCHEMA_VERSION);
+    assert!(pending_for(&conn, &Pubkey::new_unique()).is_empty());
+}
+
+#[test]
+fn index_pending_transactions() {
+    let conn = database();
+    let mut env = Env::new(3, 2, 0);
+    let smallet = env.smallet;
+    let (owner, approver) = (env.owners[0], env.owners[1]);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.ingest(&conn, &[smallet, transaction]);
+
+    let address = transaction.to_string();
+    assert_eq!(
+        pending_for(&conn, &owner),
+        vec![(address.clone(), 2, 1, true)]
+    );
+    assert_eq!(
+        pending_for(&conn, &approver),
+        vec![(address.clone(), 2, 1, false)]
+    );
+    assert!(pending_for(&conn, &Pubkey::new_unique()).is_empty());
+
+    env.approve(&transaction, 1).unwrap();
+    env.ingest(&conn, &[transaction]);
+    assert_eq!(pending_for(&conn, &approver), vec![(address, 2, 2, true)]);
+
+    let result = env.execute(&transaction, 1);
+    for (position, event) in events(&result).iter().enumerate() {
+        db::insert_event(
+            &conn,
+            "execute",
+            position,
+            1,
+            None,
+            Some(&transaction),
+            event,
+        )
+        .unwrap();
+    }
+    env.ingest(&conn, &[smallet, transaction]);
+    assert!(pending_for(&conn, &owner).is_empty());
+    let name: String = conn
+        .query_row(
+            "SELECT name FROM events WHERE transaction_address = ?1",
+            params![transaction.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(name, "TransactionExecuteEvent");
+}
+
+#[test]
+fn index_owner_set_change() {
+    let conn = database();
+    let mut env = Env::new(3, 2, 0);
+    let smallet = env.smallet;
+    let owners = env.owners.clone();
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.ingest(&conn, &[smallet, transaction]);
+    assert_eq!(pending_for(&conn, &owners[2]).len(), 1);
+
+    // Transactions of an older owner set are no longer pending, and removed
+    // owners have nothing to act on.
+    env.set_owners(owners[..2].to_vec()).unwrap();
+    env.ingest(&conn, &[smallet]);
+    assert!(pending_for(&conn, &owners[0]).is_empty());
+    assert!(pending_for(&conn, &owners[2]).is_empty());
+
+    // Closed accounts are removed.
+    env.set_account(transaction, Default::default());
+    env.ingest(&conn, &[transaction]);
+    let count: u64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM transactions WHERE address = ?1",
+            params![transaction.to_string()],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn index_untiered_transaction_threshold() {
+    let conn = database();
+    let mut env = Env::new(3, 1, 0);
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let tier = TimelockTier {
+        name: "treasury".to_string(),
+        minimum_delay: 0,
+        threshold: 2,
+        allowed_instructions: vec![],
+    };
+    env.govern(vec![
+        ix::set_timelock_tiers(&smallet, &payer, vec![tier]).into()
+    ])
+    .unwrap();
+
+    // Like the program, the view holds it to the strictest tier.
+    let transaction = env.try_propose_untiered(0, vec![], NO_ETA).unwrap();
+    env.ingest(&conn, &[smallet, transaction]);
+    assert_eq!(
+        pending_for(&conn, &env.owners[0]),
+        vec![(transaction.to_string(), 2, 1, true)]
+    );
+}
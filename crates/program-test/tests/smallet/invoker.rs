@@ -0,0 +1,424 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_instruction, system_program};
+use anchor_lang::InstructionData;
+use smallet::{AllowedInstruction, ErrorCode, InvokerPolicy};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{accounts, instructions as ix, pda};
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+const PERIOD: i64 = 100;
+
+/// Allows system transfers.
+fn allow_transfers() -> Vec<AllowedInstruction> {
+    vec![AllowedInstruction {
+        program_id: system_program::ID,
+        discriminator: 2u32.to_le_bytes().to_vec(),
+    }]
+}
+
+impl Env {
+    /// Creates the policy of owner invoker `index`, letting the first owner
+    /// make two transfers per period.
+    fn create_policy(&mut self, index: u64) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        let owners = vec![self.owners[0]];
+        self.govern(vec![ix::create_invoker_policy(
+            &smallet,
+            &payer,
+            index,
+            owners,
+            allow_transfers(),
+            2,
+            PERIOD,
+        )
+        .into()])
+    }
+
+    fn policy(&self, index: u64) -> InvokerPolicy {
+        let (address, _) = pda::find_invoker_policy_address(&self.smallet, index);
+        accounts::decode_invoker_policy(&self.bank.get_account(&address).unwrap().data).unwrap()
+    }
+
+    /// Funds owner invoker `index`.
+    fn fund_invoker(&mut self, index: u64) -> Pubkey {
+        let (invoker, _) = pda::find_owner_invoker_address(&self.smallet, index);
+        self.fund(&invoker, LAMPORTS_PER_SOL);
+        invoker
+    }
+
+    fn invoke(&mut self, owner: usize, index: u64, ix: Instruction) -> TransactionResult {
+        let owner = self.owners[owner];
+        let smallet = self.smallet;
+        self.process(
+            &[ix::owner_invoke_instruction(
+                &smallet,
+                &owner,
+                index,
+                ix.into(),
+            )],
+            &[owner],
+        )
+    }
+
+    fn invoke_v2(&mut self, owner: usize, index: u64, ix: Instruction) -> TransactionResult {
+        let owner = self.owners[owner];
+        let smallet = self.smallet;
+        self.process(
+            &[ix::owner_invoke_instruction_v2(
+                &smallet,
+                &owner,
+                index,
+                ix.into(),
+            )],
+            &[owner],
+        )
+    }
+}
+
+#[test]
+fn create_invoker_policy() {
+    let mut env = Env::new(2, 1, 0);
+    let result = env.create_policy(3);
+
+    let policy = env.policy(3);
+    let (_, bump) = pda::find_invoker_policy_address(&env.smallet, 3);
+    assert_eq!(policy.smallet, env.smallet);
+    assert_eq!(policy.index, 3);
+    assert_eq!(policy.bump, bump);
+    assert_eq!(policy.owners, vec![env.owners[0]]);
+    assert_eq!(policy.allowed_instructions, allow_transfers());
+    assert_eq!(policy.max_calls_per_period, 2);
+    assert_eq!(policy.period, PERIOD);
+    assert_eq!(policy.period_start, env.now());
+    assert_eq!(policy.calls_in_period, 0);
+
+    assert!(matches!(
+        &events(&result)[..],
+        [
+            SmalletEvent::InvokerPolicySet(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.index == 3 && event.max_calls_per_period == 2
+    ));
+}
+
+#[test]
+fn create_invoker_policy_with_negative_period() {
+    let mut env = Env::new(1, 1, 0);
+    let smallet = env.smallet;
+    let payer = env.payer;
+    assert_invariant_failed(env.govern(vec![
+        ix::create_invoker_policy(&smallet, &payer, 0, vec![], vec![], 1, -1).into(),
+    ]));
+}
+
+#[test]
+fn invoker_policy_limit_needs_period() {
+    let mut env = Env::new(1, 1, 0);
+    let smallet = env.smallet;
+    let payer = env.payer;
+    assert_invariant_failed(env.govern(vec![
+        ix::create_invoker_policy(&smallet, &payer, 0, vec![], vec![], 1, 0).into(),
+    ]));
+    // Unlimited calls need no period.
+    env.govern(vec![ix::create_invoker_policy(
+        &smallet,
+        &payer,
+        0,
+        vec![],
+        vec![],
+        0,
+        0,
+    )
+    .into()])
+        .unwrap();
+    assert_invariant_failed(env.govern(vec![
+        ix::set_invoker_policy(&smallet, &payer, 0, vec![], vec![], 1, 0).into(),
+    ]));
+    assert_eq!(env.policy(0).max_calls_per_period, 0);
+}
+
+#[test]
+fn owner_invoke_instruction() {
+    let mut env = Env::new(2, 2, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let destination = Pubkey::new_unique();
+
+    let result = env.invoke(
+        0,
+        0,
+        system_instruction::transfer(&invoker, &destination, LAMPORTS_PER_SOL / 2),
+    );
+    assert_eq!(env.bank.lamports(&destination), LAMPORTS_PER_SOL / 2);
+    assert_eq!(env.policy(0).calls_in_period, 1);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::OwnerInvoke(event)]
+            if event.invoker == invoker
+                && event.owner == env.owners[0]
+                && event.program_id == system_program::ID
+    ));
+}
+
+#[test]
+fn owner_invoke_instruction_v2() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(4).unwrap();
+    let invoker = env.fund_invoker(4);
+    let destination = Pubkey::new_unique();
+
+    let result = env.invoke_v2(
+        0,
+        4,
+        system_instruction::transfer(&invoker, &destination, LAMPORTS_PER_SOL / 4),
+    );
+    assert_eq!(env.bank.lamports(&destination), LAMPORTS_PER_SOL / 4);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::OwnerInvoke(event)] if event.invoker == invoker && event.index == 4
+    ));
+}
+
+#[test]
+fn owner_invoke_with_non_canonical_bump() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+    let (_, canonical_bump) = pda::find_owner_invoker_address(&smallet, 0);
+    let (invoker, bump) = (0..canonical_bump)
+        .rev()
+        .find_map(|bump| {
+            let invoker = Pubkey::create_program_address(
+                &[
+                    b"CosmicSmalletOwnerInvoker",
+                    smallet.as_ref(),
+                    &0u64.to_le_bytes(),
+                    &[bump],
+                ],
+                &smallet::ID,
+            )
+            .ok()?;
+            Some((invoker, bump))
+        })
+        .unwrap();
+    env.fund(&invoker, LAMPORTS_PER_SOL);
+    let transfer = system_instruction::transfer(&invoker, &Pubkey::new_unique(), 1);
+
+    let mut invoke = ix::owner_invoke_instruction(&smallet, &owner, 0, transfer.clone().into());
+    invoke.data = smallet::instruction::OwnerInvokeInstruction {
+        index: 0,
+        bump,
+        ix: transfer.clone().into(),
+    }
+    .data();
+    invoke
+        .accounts
+        .iter_mut()
+        .for_each(|meta| meta.is_signer &= meta.pubkey == owner);
+    assert_error(
+        env.process(&[invoke], &[owner]),
+        ErrorCode::InvalidSignerBump,
+    );
+
+    let mut invoke = ix::owner_invoke_instruction_v2(&smallet, &owner, 0, transfer.clone().into());
+    invoke.data = smallet::instruction::OwnerInvokeInstructionV2 {
+        index: 0,
+        bump,
+        invoker,
+        data: transfer.data,
+    }
+    .data();
+    invoke
+        .accounts
+        .iter_mut()
+        .for_each(|meta| meta.is_signer &= meta.pubkey == owner);
+    assert_error(
+        env.process(&[invoke], &[owner]),
+        ErrorCode::InvalidSignerBump,
+    );
+    assert_eq!(env.bank.lamports(&invoker), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn owner_invoke_instruction_v2_with_wrong_invoker() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let transfer = system_instruction::transfer(&invoker, &Pubkey::new_unique(), 1);
+
+    let owner = env.owners[0];
+    let smallet = env.smallet;
+    let mut invoke = ix::owner_invoke_instruction_v2(&smallet, &owner, 0, transfer.clone().into());
+    let (_, bump) = pda::find_owner_invoker_address(&smallet, 0);
+    invoke.data = smallet::instruction::OwnerInvokeInstructionV2 {
+        index: 0,
+        bump,
+        invoker: Pubkey::new_unique(),
+        data: transfer.data,
+    }
+    .data();
+    assert_error(env.process(&[invoke], &[owner]), ErrorCode::InvalidInvoker);
+}
+
+#[test]
+fn owner_invoke_by_owner_outside_policy() {
+    let mut env = Env::new(2, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let transfer = system_instruction::transfer(&invoker, &Pubkey::new_unique(), 1);
+    assert_error(
+        env.invoke(1, 0, transfer.clone()),
+        ErrorCode::InvokerNotAllowed,
+    );
+    assert_error(env.invoke_v2(1, 0, transfer), ErrorCode::InvokerNotAllowed);
+}
+
+#[test]
+fn owner_invoke_by_non_owner() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    env.owners.push(Pubkey::new_unique());
+    let transfer = system_instruction::transfer(&invoker, &Pubkey::new_unique(), 1);
+    assert_error(env.invoke(1, 0, transfer), ErrorCode::InvalidOwner);
+}
+
+#[test]
+fn owner_invoke_disallowed_instruction() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let assign = system_instruction::assign(&invoker, &Pubkey::new_unique());
+    assert_error(
+        env.invoke(0, 0, assign.clone()),
+        ErrorCode::InstructionNotAllowed,
+    );
+    assert_error(
+        env.invoke_v2(0, 0, assign),
+        ErrorCode::InstructionNotAllowed,
+    );
+}
+
+#[test]
+fn owner_invoke_instruction_v2_without_program() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let transfer = system_instruction::transfer(&invoker, &Pubkey::new_unique(), 1);
+    let owner = env.owners[0];
+    let mut instruction = ix::owner_invoke_instruction_v2(&env.smallet, &owner, 0, transfer.into());
+    // Only pass the smallet, the owner and the policy.
+    instruction.accounts.truncate(3);
+    assert_error(
+        env.process(&[instruction], &[owner]),
+        ErrorCode::MissingInvokedProgram,
+    );
+}
+
+#[test]
+fn owner_invoke_call_limit() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let period_start = env.policy(0).period_start;
+    let invoker = env.fund_invoker(0);
+    let destination = Pubkey::new_unique();
+    let transfer = |tenths| {
+        system_instruction::transfer(&invoker, &destination, tenths * LAMPORTS_PER_SOL / 10)
+    };
+
+    env.invoke(0, 0, transfer(1)).unwrap();
+    env.invoke_v2(0, 0, transfer(2)).unwrap();
+    env.bank.warp_to_timestamp(period_start + PERIOD - 1);
+    assert_error(
+        env.invoke(0, 0, transfer(3)),
+        ErrorCode::InvokerCallLimitExceeded,
+    );
+
+    // The limit resets once the period has elapsed.
+    env.bank.warp_to_timestamp(period_start + PERIOD);
+    env.invoke(0, 0, transfer(3)).unwrap();
+    let policy = env.policy(0);
+    assert_eq!(policy.period_start, period_start + PERIOD);
+    assert_eq!(policy.calls_in_period, 1);
+    assert_eq!(env.bank.lamports(&destination), 6 * LAMPORTS_PER_SOL / 10);
+}
+
+#[test]
+fn set_invoker_policy() {
+    let mut env = Env::new(2, 1, 0);
+    env.create_policy(0).unwrap();
+    let invoker = env.fund_invoker(0);
+    let destination = Pubkey::new_unique();
+    let transfer = system_instruction::transfer(&invoker, &destination, LAMPORTS_PER_SOL / 10);
+    env.invoke(0, 0, transfer.clone()).unwrap();
+
+    // Growing the policy reallocates it.
+    let mut allowed = allow_transfers();
+    allowed.push(AllowedInstruction {
+        program_id: Pubkey::new_unique(),
+        discriminator: vec![1; 8],
+    });
+    let owners = env.owners.clone();
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let result = env.govern(vec![ix::set_invoker_policy(
+        &smallet,
+        &payer,
+        0,
+        owners.clone(),
+        allowed.clone(),
+        0,
+        0,
+    )
+    .into()]);
+
+    let policy = env.policy(0);
+    assert_eq!(policy.owners, owners);
+    assert_eq!(policy.allowed_instructions, allowed);
+    assert_eq!(policy.max_calls_per_period, 0);
+    assert_eq!(policy.calls_in_period, 0);
+    assert!(matches!(
+        &events(&result)[..],
+        [
+            SmalletEvent::InvokerPolicySet(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.owners == owners
+    ));
+
+    // A limit of zero calls means no limit.
+    for _ in 0..5 {
+        env.invoke(1, 0, transfer.clone()).unwrap();
+    }
+    assert_eq!(env.bank.lamports(&destination), 6 * LAMPORTS_PER_SOL / 10);
+}
+
+#[test]
+fn set_invoker_policy_requires_smallet_signature() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_policy(0).unwrap();
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let err = env
+        .process(
+            &[ix::set_invoker_policy(
+                &smallet,
+                &payer,
+                0,
+                vec![],
+                vec![],
+                0,
+                0,
+            )],
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.error,
+        smallet_program_test::InstructionError::MissingSigner(smallet)
+    );
+}
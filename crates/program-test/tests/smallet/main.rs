@@ -0,0 +1,30 @@
+//! Tests of the smallet program, run in an in-process bank.
+//!
+//! `BufferFinalized`, `BufferBundleNotFound`, `BufferBundleOutOfRange`,
+//! `BufferBundleNotFinalized` and `BufferBundleExecuted` are not returned by
+//! any instruction of the program, so they have no tests.
+
+mod helpers;
+
+mod address_book;
+mod approval_ttl;
+mod client;
+mod commit;
+mod create_smallet;
+mod execute;
+mod indexer;
+mod invoker;
+mod migrate;
+mod optimistic;
+mod ordering;
+mod owners;
+mod post_conditions;
+mod pre_conditions;
+mod status;
+mod subaccounts;
+mod tiers;
+mod transfers;
+mod transactions;
+mod upgrade;
+mod v2;
+mod veto;
@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use smallet::{
+    ErrorCode, Smallet, SmalletV0, SubaccountInfo, SubaccountInfoV0, SubaccountType, Transaction,
+    TransactionV0, LEGACY_VERSION, NO_ETA, SMALLET_VERSION, SUBACCOUNT_INFO_VERSION,
+    TRANSACTION_VERSION,
+};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{accounts, instructions as ix, pda};
+use smallet_program_test::Account;
+
+use crate::helpers::*;
+
+/// Serializes a legacy account, padded to `len` bytes.
+fn legacy_data<T: AnchorSerialize>(discriminator: [u8; 8], value: &T, len: usize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    value.serialize(&mut data).unwrap();
+    data.resize(len.max(data.len()), 0);
+    data
+}
+
+impl Env {
+    /// Rewrites the smallet in the legacy layout.
+    fn downgrade_smallet(&mut self) -> SmalletV0 {
+        let smallet = self.smallet_account();
+        let legacy = SmalletV0 {
+            base: smallet.base,
+            bump: smallet.bump,
+            threshold: smallet.threshold,
+            minimum_delay: smallet.minimum_delay,
+            grace_period: smallet.grace_period,
+            owner_set_seqno: smallet.owner_set_seqno,
+            num_transactions: smallet.num_transactions,
+            owners: smallet.owners,
+            reserved: [0; 16],
+        };
+        let mut account = self.bank.get_account(&self.smallet).unwrap();
+        account.data = legacy_data(Smallet::discriminator(), &legacy, account.data.len());
+        self.set_account(self.smallet, account);
+        legacy
+    }
+
+    /// Rewrites a transaction in the legacy layout.
+    fn downgrade_transaction(&mut self, transaction: &Pubkey) {
+        let tx = self.transaction_account(transaction);
+        let legacy = TransactionV0 {
+            smallet: tx.smallet,
+            index: tx.index,
+            bump: tx.bump,
+            proposer: tx.proposer,
+            instructions: tx.instructions,
+            signers: tx.signers,
+            owner_set_seqno: tx.owner_set_seqno,
+            eta: tx.eta,
+            executor: tx.executor,
+            executed_at: tx.executed_at,
+        };
+        let mut account = self.bank.get_account(transaction).unwrap();
+        account.data = legacy_data(Transaction::discriminator(), &legacy, account.data.len());
+        self.set_account(*transaction, account);
+    }
+
+    /// Creates a legacy [SubaccountInfo] of `smallet`, sized for its layout.
+    fn legacy_subaccount_info(
+        &mut self,
+        smallet: Pubkey,
+        subaccount_type: SubaccountType,
+        index: u64,
+    ) -> Pubkey {
+        let (subaccount, _) = subaccount_type.find_address(&smallet, index);
+        let (info, _) = pda::find_subaccount_info_address(&subaccount);
+        let legacy = SubaccountInfoV0 {
+            smallet,
+            subaccount_type,
+            index,
+        };
+        self.set_account(
+            info,
+            Account::new_data(
+                legacy_data(SubaccountInfo::discriminator(), &legacy, 0),
+                &smallet::ID,
+            ),
+        );
+        subaccount
+    }
+}
+
+#[test]
+fn migrate_smallet() {
+    let mut env = Env::new(3, 2, 60);
+    let legacy = env.downgrade_smallet();
+    assert_eq!(env.smallet_account().version, LEGACY_VERSION);
+
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let result = env.process(&[ix::migrate_smallet(&smallet, &payer)], &[]);
+
+    let migrated = env.smallet_account();
+    assert_eq!(migrated.version, SMALLET_VERSION);
+    assert_eq!(migrated.base, legacy.base);
+    assert_eq!(migrated.bump, legacy.bump);
+    assert_eq!(migrated.threshold, legacy.threshold);
+    assert_eq!(migrated.minimum_delay, legacy.minimum_delay);
+    assert_eq!(migrated.owners, legacy.owners);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::SmalletMigrate(event)]
+            if event.smallet == smallet && event.version == SMALLET_VERSION
+    ));
+
+    assert_error(
+        env.process(&[ix::migrate_smallet(&smallet, &payer)], &[]),
+        ErrorCode::AccountAlreadyMigrated,
+    );
+}
+
+#[test]
+fn migrate_smallet_at_capacity() {
+    // A legacy smallet with no free space has to grow to fit the version.
+    let mut env = Env::new(2, 1, 0);
+    let legacy = env.downgrade_smallet();
+    let mut account = env.bank.get_account(&env.smallet).unwrap();
+    account.data = legacy_data(Smallet::discriminator(), &legacy, 0);
+    let len = account.data.len();
+    env.set_account(env.smallet, account);
+
+    let smallet = env.smallet;
+    let payer = env.payer;
+    env.process(&[ix::migrate_smallet(&smallet, &payer)], &[])
+        .unwrap();
+    let account = env.bank.get_account(&env.smallet).unwrap();
+    assert!(account.data.len() > len);
+    assert!(account.lamports >= env.bank.rent().minimum_balance(account.data.len()));
+    assert_eq!(env.smallet_account().owners, legacy.owners);
+}
+
+#[test]
+fn legacy_smallet_is_not_usable() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.downgrade_smallet();
+
+    assert_error(
+        env.try_propose(0, vec![], NO_ETA).map(|_| vec![]),
+        ErrorCode::AccountNotMigrated,
+    );
+    assert_error(env.approve(&transaction, 1), ErrorCode::AccountNotMigrated);
+    assert_error(env.execute(&transaction, 0), ErrorCode::AccountNotMigrated);
+}
+
+#[test]
+fn migrate_transaction() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let transaction = env.propose(0, vec![ix::change_threshold(&smallet, 2).into()], NO_ETA);
+    env.downgrade_transaction(&transaction);
+    assert_eq!(
+        env.transaction_account(&transaction).version,
+        LEGACY_VERSION
+    );
+    assert_error(env.approve(&transaction, 1), ErrorCode::AccountNotMigrated);
+    assert_error(env.execute(&transaction, 0), ErrorCode::AccountNotMigrated);
+
+    let payer = env.payer;
+    let result = env.process(&[ix::migrate_transaction(&transaction, &payer)], &[]);
+    let tx = env.transaction_account(&transaction);
+    assert_eq!(tx.version, TRANSACTION_VERSION);
+    assert_eq!(tx.program_upgrade, None);
+    assert!(tx.pda_signers.is_empty());
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionMigrate(event)]
+            if event.transaction == transaction && event.smallet == smallet
+    ));
+
+    assert_error(
+        env.process(&[ix::migrate_transaction(&transaction, &payer)], &[]),
+        ErrorCode::AccountAlreadyMigrated,
+    );
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.smallet_account().threshold, 2);
+}
+
+#[test]
+fn migrate_subaccount_info() {
+    let mut env = Env::new(1, 1, 0);
+    let smallet = env.smallet;
+    let subaccount = env.legacy_subaccount_info(smallet, SubaccountType::OwnerInvoker, 2);
+    let (info, _) = pda::find_subaccount_info_address(&subaccount);
+
+    // Legacy entries have no room for a label until migrated.
+    assert_error(
+        env.govern(vec![ix::update_subaccount_info(
+            &smallet,
+            &subaccount,
+            None,
+        )
+        .into()]),
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize,
+    );
+
+    let payer = env.payer;
+    let result = env.process(
+        &[ix::migrate_subaccount_info(
+            &smallet,
+            &subaccount,
+            0,
+            &payer,
+        )],
+        &[],
+    );
+    let migrated =
+        accounts::decode_subaccount_info(&env.bank.get_account(&info).unwrap().data).unwrap();
+    assert_eq!(migrated.version, SUBACCOUNT_INFO_VERSION);
+    assert_eq!(migrated.smallet, env.smallet);
+    assert_eq!(migrated.subaccount_type, SubaccountType::OwnerInvoker);
+    assert_eq!(migrated.index, 2);
+    assert_eq!(migrated.registry_index, 0);
+    assert_eq!(migrated.label, None);
+    assert_eq!(env.smallet_account().num_subaccounts, 1);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::SubaccountMigrate(event)]
+            if event.subaccount == subaccount && event.registry_index == 0
+    ));
+
+    assert_error(
+        env.process(
+            &[ix::migrate_subaccount_info(
+                &smallet,
+                &subaccount,
+                1,
+                &payer,
+            )],
+            &[],
+        ),
+        ErrorCode::AccountAlreadyMigrated,
+    );
+}
+
+#[test]
+fn migrate_subaccount_info_of_other_smallet() {
+    let mut env = Env::new(1, 1, 0);
+    let subaccount = env.legacy_subaccount_info(Pubkey::new_unique(), SubaccountType::Derived, 0);
+    let smallet = env.smallet;
+    let payer = env.payer;
+    assert_key_mismatch(env.process(
+        &[ix::migrate_subaccount_info(
+            &smallet,
+            &subaccount,
+            0,
+            &payer,
+        )],
+        &[],
+    ));
+}
+
+#[test]
+fn migrate_subaccount_info_with_legacy_smallet() {
+    let mut env = Env::new(1, 1, 0);
+    let smallet = env.smallet;
+    let subaccount = env.legacy_subaccount_info(smallet, SubaccountType::Derived, 0);
+    env.downgrade_smallet();
+    let payer = env.payer;
+    assert_error(
+        env.process(
+            &[ix::migrate_subaccount_info(
+                &smallet,
+                &subaccount,
+                0,
+                &payer,
+            )],
+            &[],
+        ),
+        ErrorCode::AccountNotMigrated,
+    );
+}
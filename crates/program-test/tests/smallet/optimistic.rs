@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use smallet::{
+    AllowedInstruction, ErrorCode, OptimisticConfig, PdaSigner, TXInstruction, TransactionOptions,
+    NO_ETA, SECONDS_PER_DAY,
+};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{TransactionError, TransactionResult};
+
+use crate::helpers::*;
+
+/// SOL transfers, the only instructions proposed optimistically below.
+fn transfers() -> Vec<AllowedInstruction> {
+    vec![AllowedInstruction {
+        program_id: system_program::ID,
+        discriminator: 2u32.to_le_bytes().to_vec(),
+    }]
+}
+
+impl Env {
+    /// A smallet of 3 owners with a threshold of 3, where `proposer` may
+    /// propose SOL transfers optimistically with a challenge period of a day
+    /// and 2 owners may object.
+    fn optimistic(proposer: Pubkey) -> Self {
+        let mut env = Env::new(3, 3, 0);
+        env.set_optimistic_mode(Some(OptimisticConfig {
+            proposers: vec![proposer],
+            objection_threshold: 2,
+            challenge_period: SECONDS_PER_DAY,
+            allowed_instructions: transfers(),
+        }))
+        .unwrap();
+        env
+    }
+
+    fn set_optimistic_mode(&mut self, config: Option<OptimisticConfig>) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.govern(vec![
+            ix::set_optimistic_mode(&smallet, &payer, config).into()
+        ])
+    }
+
+    /// Proposes `instructions` optimistically, signed by the wallet derived
+    /// address at index 0.
+    fn propose_optimistic(
+        &mut self,
+        proposer: Pubkey,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let (_, bump) = pda::find_wallet_derived_address(&self.smallet, 0);
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                instructions,
+                eta,
+                TransactionOptions {
+                    pda_signers: vec![PdaSigner::Derived { index: 0, bump }],
+                    optimistic: true,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+
+    /// Executes `transaction` as `executor`, which need not be an owner.
+    fn execute_as(&mut self, transaction: &Pubkey, executor: Pubkey) -> TransactionResult {
+        let tx = self.transaction_account(transaction);
+        let smallet = self.smallet;
+        self.process(
+            &[ix::execute_transaction(
+                &smallet,
+                transaction,
+                &tx,
+                &executor,
+            )],
+            &[executor],
+        )
+    }
+
+    fn object(&mut self, transaction: &Pubkey, owner: usize) -> TransactionResult {
+        let owner = self.owners[owner];
+        let payer = self.payer;
+        self.process(
+            &[ix::veto(&self.smallet, transaction, &owner, &payer)],
+            &[owner],
+        )
+    }
+}
+
+#[test]
+fn execute_without_objections() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![transfer], eta).unwrap();
+    let tx = env.transaction_account(&transaction);
+    assert!(tx.optimistic);
+    assert_eq!(tx.num_signers(), 0);
+
+    assert_error(
+        env.execute_as(&transaction, bot),
+        ErrorCode::TransactionNotReady,
+    );
+    env.bank.warp_to_timestamp(eta);
+    env.execute_as(&transaction, bot).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn execute_by_owner() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![], eta).unwrap();
+    env.bank.warp_to_timestamp(eta);
+    env.execute(&transaction, 2).unwrap();
+
+    // Other keys still cannot execute.
+    let transaction = env
+        .propose_optimistic(bot, vec![], env.now() + SECONDS_PER_DAY)
+        .unwrap();
+    env.bank.advance_clock(SECONDS_PER_DAY);
+    assert_error(
+        env.execute_as(&transaction, Pubkey::new_unique()),
+        ErrorCode::InvalidOwner,
+    );
+}
+
+#[test]
+fn objections_block_transaction() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![transfer], eta).unwrap();
+
+    // Vetoes are disabled on the smallet, but objections are not.
+    let result = env.object(&transaction, 0);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionVeto(e)] if !e.blocked
+    ));
+    let result = env.object(&transaction, 1);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionVeto(e)] if e.blocked
+    ));
+
+    env.bank.warp_to_timestamp(eta);
+    assert_error(
+        env.execute_as(&transaction, bot),
+        ErrorCode::TransactionVetoed,
+    );
+    assert_eq!(env.bank.lamports(&recipient), 0);
+}
+
+#[test]
+fn object_after_challenge_period() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![], eta).unwrap();
+    env.object(&transaction, 0).unwrap();
+    env.bank.warp_to_timestamp(eta);
+    assert_error(env.object(&transaction, 1), ErrorCode::VetoWindowClosed);
+    env.execute_as(&transaction, bot).unwrap();
+}
+
+#[test]
+fn propose_within_challenge_period() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let eta = env.now() + SECONDS_PER_DAY - 1;
+    assert_error(
+        env.propose_optimistic(bot, vec![], eta).map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    assert_error(
+        env.propose_optimistic(bot, vec![], NO_ETA).map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+}
+
+#[test]
+fn propose_unauthorized() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let eta = env.now() + SECONDS_PER_DAY;
+
+    // Owners are not authorized unless listed.
+    let owner = env.owners[0];
+    assert_error(
+        env.propose_optimistic(owner, vec![], eta).map(|_| vec![]),
+        ErrorCode::UnauthorizedProposer,
+    );
+
+    let mut env = Env::new(3, 3, 0);
+    assert_error(
+        env.propose_optimistic(bot, vec![], eta).map(|_| vec![]),
+        ErrorCode::OptimisticModeDisabled,
+    );
+}
+
+#[test]
+fn disable_optimistic_mode() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![], eta).unwrap();
+
+    let result = env.set_optimistic_mode(None);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::OptimisticModeSet(e) if e.config.is_none()
+    )));
+    assert!(env.smallet_account().optimistic.is_none());
+
+    // The pending transaction needs approvals again.
+    env.bank.warp_to_timestamp(eta);
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+    env.approve_threshold(&transaction);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn set_optimistic_mode_validates_config() {
+    let mut env = Env::new(2, 2, 0);
+    let config = OptimisticConfig {
+        proposers: vec![Pubkey::new_unique()],
+        objection_threshold: 0,
+        challenge_period: SECONDS_PER_DAY,
+        allowed_instructions: transfers(),
+    };
+    assert_error(
+        env.set_optimistic_mode(Some(config.clone())),
+        ErrorCode::InvalidThreshold,
+    );
+    assert_error(
+        env.set_optimistic_mode(Some(OptimisticConfig {
+            objection_threshold: 3,
+            ..config.clone()
+        })),
+        ErrorCode::InvalidThreshold,
+    );
+    assert_error(
+        env.set_optimistic_mode(Some(OptimisticConfig {
+            objection_threshold: 1,
+            challenge_period: smallet::MAX_DELAY_SECONDS,
+            ..config.clone()
+        })),
+        ErrorCode::DelayTooHigh,
+    );
+
+    // Shrinking the owner set lowers the objection threshold.
+    env.set_optimistic_mode(Some(OptimisticConfig {
+        objection_threshold: 2,
+        ..config
+    }))
+    .unwrap();
+    let owners = env.owners[..1].to_vec();
+    env.set_owners(owners).unwrap();
+    let config = env.smallet_account().optimistic.unwrap();
+    assert_eq!(config.objection_threshold, 1);
+}
+
+#[test]
+fn propose_admin_instruction_optimistically() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let smallet = env.smallet;
+    let eta = env.now() + SECONDS_PER_DAY;
+    assert_error(
+        env.propose_optimistic(bot, vec![ix::change_threshold(&smallet, 1).into()], eta)
+            .map(|_| vec![]),
+        ErrorCode::InstructionNotOptimistic,
+    );
+
+    // Even if the smallet program is allowed, e.g. by mistake.
+    env.set_optimistic_mode(Some(OptimisticConfig {
+        proposers: vec![bot],
+        objection_threshold: 2,
+        challenge_period: SECONDS_PER_DAY,
+        allowed_instructions: vec![AllowedInstruction {
+            program_id: smallet::ID,
+            discriminator: vec![],
+        }],
+    }))
+    .unwrap();
+    let owners = vec![bot];
+    for instruction in [
+        ix::set_owners(&smallet, owners).into(),
+        ix::change_threshold(&smallet, 1).into(),
+        ix::set_optimistic_mode(&smallet, &bot, None).into(),
+    ] {
+        assert_error(
+            env.propose_optimistic(bot, vec![instruction], eta)
+                .map(|_| vec![]),
+            ErrorCode::InstructionNotOptimistic,
+        );
+    }
+}
+
+#[test]
+fn restricted_instructions_apply_to_pending_transactions() {
+    let bot = Pubkey::new_unique();
+    let mut env = Env::optimistic(bot);
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose_optimistic(bot, vec![transfer], eta).unwrap();
+
+    // Owners stop allowing transfers before the transaction is executed.
+    env.set_optimistic_mode(Some(OptimisticConfig {
+        proposers: vec![bot],
+        objection_threshold: 2,
+        challenge_period: SECONDS_PER_DAY,
+        allowed_instructions: vec![],
+    }))
+    .unwrap();
+    env.bank.warp_to_timestamp(eta);
+    assert_error(
+        env.execute_as(&transaction, bot),
+        ErrorCode::InstructionNotOptimistic,
+    );
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, TransactionOptions, NO_ETA};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{TransactionError, TransactionResult};
+
+use crate::helpers::*;
+
+impl Env {
+    /// A smallet of 3 owners with a threshold of 2 that executes its
+    /// transactions in order.
+    fn fifo() -> Self {
+        let mut env = Env::new(3, 2, 0);
+        env.set_fifo_mode(true).unwrap();
+        env
+    }
+
+    fn set_fifo_mode(&mut self, enabled: bool) -> TransactionResult {
+        let smallet = self.smallet;
+        self.govern(vec![ix::set_fifo_mode(&smallet, enabled).into()])
+    }
+
+    /// Proposes an empty transaction via `create_transaction_v2`, which
+    /// leaves it unindexed.
+    fn propose_after(
+        &mut self,
+        prerequisites: Vec<u64>,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let proposer = self.owners[0];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                vec![],
+                NO_ETA,
+                TransactionOptions {
+                    prerequisites,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+
+    /// Executes `transaction`, passing the `earlier` transactions.
+    fn execute_after(&mut self, transaction: &Pubkey, earlier: &[Pubkey]) -> TransactionResult {
+        let owner = self.owners[0];
+        let tx = self.transaction_account(transaction);
+        let mut instruction = ix::execute_transaction(&self.smallet, transaction, &tx, &owner);
+        instruction
+            .accounts
+            .extend(ix::transaction_accounts(earlier));
+        self.process(&[instruction], &[owner])
+    }
+
+    fn advance_fifo_cursor(&mut self, transactions: &[Pubkey]) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(&[ix::advance_fifo_cursor(&smallet, transactions)], &[payer])
+    }
+}
+
+#[test]
+fn execute_after_prerequisite() {
+    let mut env = Env::new(2, 1, 0);
+    let first = env.propose(0, vec![], NO_ETA);
+    let index = env.transaction_account(&first).index;
+    let second = env.propose_after(vec![index]).unwrap();
+    assert_eq!(env.transaction_account(&second).prerequisites, vec![index]);
+
+    assert_error(
+        env.execute_after(&second, &[]),
+        ErrorCode::PrerequisiteNotExecuted,
+    );
+    assert_error(
+        env.execute_after(&second, &[first]),
+        ErrorCode::PrerequisiteNotExecuted,
+    );
+    env.execute(&first, 0).unwrap();
+    env.execute_after(&second, &[first]).unwrap();
+}
+
+#[test]
+fn propose_after_invalid_prerequisite() {
+    let mut env = Env::new(2, 1, 0);
+    let next = env.smallet_account().num_transactions;
+    assert_error(
+        env.propose_after(vec![next]).map(|_| vec![]),
+        ErrorCode::InvalidPrerequisite,
+    );
+}
+
+#[test]
+fn set_fifo_mode() {
+    let mut env = Env::new(3, 2, 0);
+    let result = env.set_fifo_mode(true);
+    let cursor = env.smallet_account().num_transactions;
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::FifoCursorSet(e) if e.cursor == Some(cursor)
+    )));
+    assert_eq!(env.smallet_account().fifo_cursor, Some(cursor));
+
+    let result = env.set_fifo_mode(false);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::FifoCursorSet(e) if e.cursor.is_none()
+    )));
+    assert!(env.smallet_account().fifo_cursor.is_none());
+}
+
+#[test]
+fn fifo_blocks_behind_approved_transaction() {
+    let mut env = Env::fifo();
+    let first = env.propose(0, vec![], NO_ETA);
+    env.approve(&first, 1).unwrap();
+    let second = env.propose(0, vec![], NO_ETA);
+    env.approve(&second, 1).unwrap();
+
+    assert_error(
+        env.execute_after(&second, &[]),
+        ErrorCode::EarlierTransactionPending,
+    );
+    assert_error(
+        env.execute_after(&second, &[first]),
+        ErrorCode::EarlierTransactionPending,
+    );
+    env.execute(&first, 0).unwrap();
+    env.execute_after(&second, &[first]).unwrap();
+}
+
+#[test]
+fn fifo_skips_unapproved_transaction() {
+    let mut env = Env::fifo();
+    let first = env.propose(0, vec![], NO_ETA);
+    let second = env.propose(0, vec![], NO_ETA);
+    env.approve(&second, 1).unwrap();
+    env.execute_after(&second, &[first]).unwrap();
+}
+
+#[test]
+fn fifo_rejects_unindexed_transaction() {
+    let mut env = Env::fifo();
+    let transaction = env.propose_after(vec![]).unwrap();
+    env.approve(&transaction, 1).unwrap();
+    assert_error(
+        env.execute_after(&transaction, &[]),
+        ErrorCode::TransactionNotIndexed,
+    );
+}
+
+#[test]
+fn advance_fifo_cursor() {
+    let mut env = Env::fifo();
+    let first = env.propose(0, vec![], NO_ETA);
+    env.approve(&first, 1).unwrap();
+    env.execute(&first, 0).unwrap();
+    let second = env.propose(0, vec![], NO_ETA);
+    env.approve(&second, 1).unwrap();
+    let third = env.propose(0, vec![], NO_ETA);
+    env.approve(&third, 1).unwrap();
+
+    // The cursor stops at the first transaction that may still be executed.
+    let result = env.advance_fifo_cursor(&[first, second, third]);
+    let index = env.transaction_account(&second).index;
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::FifoCursorSet(e)] if e.cursor == Some(index)
+    ));
+    assert_eq!(env.smallet_account().fifo_cursor, Some(index));
+
+    // The executed transaction need no longer be passed.
+    env.execute(&second, 0).unwrap();
+    env.execute_after(&third, &[second]).unwrap();
+}
+
+#[test]
+fn advance_fifo_cursor_disabled() {
+    let mut env = Env::new(2, 1, 0);
+    assert_invariant_failed(env.advance_fifo_cursor(&[]));
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use smallet::ErrorCode;
+use smallet_client::events::SmalletEvent;
+use smallet_client::instructions as ix;
+use smallet_program_test::InstructionError;
+
+use crate::helpers::*;
+
+#[test]
+fn set_owners() {
+    let mut env = Env::new(3, 2, 0);
+    let owners = vec![env.owners[0], Pubkey::new_unique(), Pubkey::new_unique()];
+    let result = env.set_owners(owners.clone());
+
+    let smallet = env.smallet_account();
+    assert_eq!(smallet.owners, owners);
+    assert_eq!(smallet.threshold, 2);
+    assert_eq!(smallet.owner_set_seqno, 1);
+
+    let events = events(&result);
+    assert!(matches!(
+        &events[..],
+        [
+            SmalletEvent::WalletSetOwners(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.owners == owners
+    ));
+}
+
+#[test]
+fn set_owners_lowers_threshold() {
+    let mut env = Env::new(3, 3, 0);
+    env.set_owners(vec![env.owners[0], env.owners[1]]).unwrap();
+
+    let smallet = env.smallet_account();
+    assert_eq!(smallet.owners.len(), 2);
+    assert_eq!(smallet.threshold, 2);
+}
+
+#[test]
+fn set_owners_requires_smallet_signature() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let err = env
+        .process(&[ix::set_owners(&smallet, vec![Pubkey::new_unique()])], &[])
+        .unwrap_err();
+    assert_eq!(err.error, InstructionError::MissingSigner(smallet));
+}
+
+#[test]
+fn set_owners_invalidates_pending_transactions() {
+    let mut env = Env::new(3, 2, 0);
+    let transaction = env.propose(0, vec![], smallet::NO_ETA);
+    env.approve(&transaction, 1).unwrap();
+
+    env.set_owners(env.owners.clone()).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::OwnerSetChanged);
+    assert_error(env.approve(&transaction, 2), ErrorCode::OwnerSetChanged);
+    assert_error(env.unapprove(&transaction, 1), ErrorCode::OwnerSetChanged);
+}
+
+#[test]
+fn change_threshold() {
+    let mut env = Env::new(3, 1, 0);
+    let smallet = env.smallet;
+    let result = env.govern(vec![ix::change_threshold(&smallet, 3).into()]);
+    assert_eq!(env.smallet_account().threshold, 3);
+    assert!(matches!(
+        &events(&result)[..],
+        [
+            SmalletEvent::WalletChangeThreshold(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.threshold == 3
+    ));
+
+    // The owner set is unchanged, so pending transactions stay valid.
+    assert_eq!(env.smallet_account().owner_set_seqno, 0);
+}
+
+#[test]
+fn change_threshold_above_owners() {
+    let mut env = Env::new(3, 1, 0);
+    let smallet = env.smallet;
+    assert_error(
+        env.govern(vec![ix::change_threshold(&smallet, 4).into()]),
+        ErrorCode::InvalidThreshold,
+    );
+    assert_eq!(env.smallet_account().threshold, 1);
+}
+
+#[test]
+fn change_threshold_requires_smallet_signature() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let err = env
+        .process(&[ix::change_threshold(&smallet, 2)], &[])
+        .unwrap_err();
+    assert_eq!(err.error, InstructionError::MissingSigner(smallet));
+}
@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use smallet::{
+    ErrorCode, OutflowLimit, PdaSigner, PostCondition, TXInstruction, TransactionOptions, NO_ETA,
+};
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::TransactionError;
+
+use crate::helpers::*;
+
+impl Env {
+    /// Proposes `instructions`, signed by the wallet derived address at
+    /// index 0 and limited by `post_conditions`.
+    fn propose_with_post_conditions(
+        &mut self,
+        instructions: Vec<TXInstruction>,
+        post_conditions: Vec<PostCondition>,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let (_, bump) = pda::find_wallet_derived_address(&self.smallet, 0);
+        let proposer = self.owners[0];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                instructions,
+                NO_ETA,
+                TransactionOptions {
+                    pda_signers: vec![PdaSigner::Derived { index: 0, bump }],
+                    post_conditions,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+
+    /// The wallet derived address at index 0, funded with 1 SOL.
+    fn funded_wallet(&mut self) -> Pubkey {
+        let (wallet, _) = pda::find_wallet_derived_address(&self.smallet, 0);
+        self.fund(&wallet, LAMPORTS_PER_SOL);
+        wallet
+    }
+}
+
+fn max_lamports(account: Pubkey, max_outflow: u64) -> PostCondition {
+    PostCondition {
+        account,
+        limit: OutflowLimit::Lamports { max_outflow },
+    }
+}
+
+#[test]
+fn execute_within_lamport_limit() {
+    let mut env = Env::new(2, 1, 0);
+    let wallet = env.funded_wallet();
+    let recipient = Pubkey::new_unique();
+    let transaction = env
+        .propose_with_post_conditions(
+            vec![transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 2)],
+            vec![
+                max_lamports(wallet, LAMPORTS_PER_SOL / 2),
+                // Inflows are not limited.
+                max_lamports(recipient, 0),
+            ],
+        )
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn execute_over_lamport_limit() {
+    let mut env = Env::new(2, 1, 0);
+    let wallet = env.funded_wallet();
+    let recipient = Pubkey::new_unique();
+    let transaction = env
+        .propose_with_post_conditions(
+            vec![
+                transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 4),
+                transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 4),
+            ],
+            vec![max_lamports(wallet, LAMPORTS_PER_SOL / 4)],
+        )
+        .unwrap();
+
+    // The whole execution aborts.
+    assert_error(env.execute(&transaction, 0), ErrorCode::PostConditionFailed);
+    assert_eq!(env.bank.lamports(&wallet), LAMPORTS_PER_SOL);
+    assert_eq!(env.transaction_account(&transaction).executed_at, -1);
+}
+
+#[test]
+fn execute_over_token_limit() {
+    let mut env = Env::new(2, 1, 0);
+    let (wallet, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let mint = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    env.set_token_account(vault, mint, wallet, 1_000);
+    env.set_token_account(destination, mint, Pubkey::new_unique(), 0);
+    let transfer: TXInstruction =
+        spl_token::instruction::transfer(&spl_token::ID, &vault, &destination, &wallet, &[], 600)
+            .unwrap()
+            .into();
+
+    let limit = |max_outflow| PostCondition {
+        account: vault,
+        limit: OutflowLimit::Tokens { max_outflow },
+    };
+    let transaction = env
+        .propose_with_post_conditions(vec![transfer.clone()], vec![limit(500)])
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PostConditionFailed);
+
+    env.bank.advance_clock(1);
+    let transaction = env
+        .propose_with_post_conditions(vec![transfer], vec![limit(600)])
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn execute_without_post_condition_account() {
+    let mut env = Env::new(2, 1, 0);
+    let watched = Pubkey::new_unique();
+    let transaction = env
+        .propose_with_post_conditions(vec![], vec![max_lamports(watched, 0)])
+        .unwrap();
+
+    let owner = env.owners[0];
+    let tx = env.transaction_account(&transaction);
+    let mut instruction = ix::execute_transaction(&env.smallet, &transaction, &tx, &owner);
+    instruction.accounts.pop();
+    assert_error(
+        env.process(&[instruction], &[owner]),
+        ErrorCode::MissingPostConditionAccount,
+    );
+}
+
+#[test]
+fn execute_above_minimum_lamports() {
+    let mut env = Env::new(2, 1, 0);
+    let wallet = env.funded_wallet();
+    let recipient = Pubkey::new_unique();
+    let min_lamports = |min_balance| PostCondition {
+        account: wallet,
+        limit: OutflowLimit::MinLamports { min_balance },
+    };
+
+    // Unlike outflow limits, the minimum applies to the final balance.
+    let transaction = env
+        .propose_with_post_conditions(
+            vec![transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 2)],
+            vec![min_lamports(LAMPORTS_PER_SOL / 2 + 1)],
+        )
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PostConditionFailed);
+    assert_eq!(env.bank.lamports(&wallet), LAMPORTS_PER_SOL);
+
+    env.bank.advance_clock(1);
+    let transaction = env
+        .propose_with_post_conditions(
+            vec![transfer_from(&wallet, &recipient, LAMPORTS_PER_SOL / 2)],
+            vec![min_lamports(LAMPORTS_PER_SOL / 2)],
+        )
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&wallet), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn execute_below_minimum_tokens() {
+    let mut env = Env::new(2, 1, 0);
+    let (wallet, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let mint = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+    env.set_token_account(vault, mint, wallet, 1_000);
+    env.set_token_account(destination, mint, Pubkey::new_unique(), 0);
+    let transfer: TXInstruction =
+        spl_token::instruction::transfer(&spl_token::ID, &vault, &destination, &wallet, &[], 600)
+            .unwrap()
+            .into();
+
+    let min_tokens = |min_balance| PostCondition {
+        account: vault,
+        limit: OutflowLimit::MinTokens { min_balance },
+    };
+    let transaction = env
+        .propose_with_post_conditions(vec![transfer.clone()], vec![min_tokens(500)])
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PostConditionFailed);
+
+    env.bank.advance_clock(1);
+    let transaction = env
+        .propose_with_post_conditions(vec![transfer], vec![min_tokens(400)])
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+
+    // The account must be an SPL Token account.
+    env.bank.advance_clock(1);
+    let transaction = env
+        .propose_with_post_conditions(
+            vec![],
+            vec![PostCondition {
+                account: env.payer,
+                limit: OutflowLimit::MinTokens { min_balance: 0 },
+            }],
+        )
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotATokenAccount);
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_spl::token::spl_token;
+use smallet::{AccountCheck, ErrorCode, PreCondition, TransactionOptions, NO_ETA};
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{Account, TransactionError};
+
+use crate::helpers::*;
+
+impl Env {
+    /// Proposes an empty transaction checking `pre_conditions`.
+    fn propose_with_pre_conditions(
+        &mut self,
+        pre_conditions: Vec<PreCondition>,
+    ) -> std::result::Result<Pubkey, TransactionError> {
+        let proposer = self.owners[0];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                vec![],
+                NO_ETA,
+                TransactionOptions {
+                    pre_conditions,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+}
+
+fn pre_condition(account: Pubkey, check: AccountCheck) -> PreCondition {
+    PreCondition { account, check }
+}
+
+#[test]
+fn execute_when_pre_conditions_hold() {
+    let mut env = Env::new(2, 1, 0);
+    let vault = Pubkey::new_unique();
+    env.fund(&vault, LAMPORTS_PER_SOL);
+    let state = Pubkey::new_unique();
+    env.set_account(state, Account::new_data(b"state".to_vec(), &smallet::ID));
+    let tokens = Pubkey::new_unique();
+    env.set_token_account(tokens, Pubkey::new_unique(), env.smallet, 1_000);
+
+    let pre_conditions = vec![
+        pre_condition(
+            vault,
+            AccountCheck::MinLamports {
+                lamports: LAMPORTS_PER_SOL,
+            },
+        ),
+        pre_condition(
+            state,
+            AccountCheck::DataHash {
+                hash: hash(b"state").to_bytes(),
+            },
+        ),
+        pre_condition(
+            state,
+            AccountCheck::Owner {
+                program_id: smallet::ID,
+            },
+        ),
+        pre_condition(
+            tokens,
+            AccountCheck::TokenAmount {
+                min: 1_000,
+                max: 1_000,
+            },
+        ),
+    ];
+    let transaction = env
+        .propose_with_pre_conditions(pre_conditions.clone())
+        .unwrap();
+    assert_eq!(
+        env.transaction_account(&transaction).pre_conditions,
+        pre_conditions
+    );
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn execute_below_min_lamports() {
+    let mut env = Env::new(2, 1, 0);
+    let vault = Pubkey::new_unique();
+    env.fund(&vault, LAMPORTS_PER_SOL - 1);
+    let transaction = env
+        .propose_with_pre_conditions(vec![pre_condition(
+            vault,
+            AccountCheck::MinLamports {
+                lamports: LAMPORTS_PER_SOL,
+            },
+        )])
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PreConditionFailed);
+
+    env.fund(&vault, 1);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn execute_after_data_changed() {
+    let mut env = Env::new(2, 1, 0);
+    let state = Pubkey::new_unique();
+    env.set_account(state, Account::new_data(b"state".to_vec(), &smallet::ID));
+    let transaction = env
+        .propose_with_pre_conditions(vec![pre_condition(
+            state,
+            AccountCheck::DataHash {
+                hash: hash(b"state").to_bytes(),
+            },
+        )])
+        .unwrap();
+
+    env.set_account(state, Account::new_data(b"other".to_vec(), &smallet::ID));
+    assert_error(env.execute(&transaction, 0), ErrorCode::PreConditionFailed);
+}
+
+#[test]
+fn execute_with_token_amount_out_of_range() {
+    let mut env = Env::new(2, 1, 0);
+    let tokens = Pubkey::new_unique();
+    env.set_token_account(tokens, Pubkey::new_unique(), env.smallet, 1_001);
+    let transaction = env
+        .propose_with_pre_conditions(vec![pre_condition(
+            tokens,
+            AccountCheck::TokenAmount { min: 0, max: 1_000 },
+        )])
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PreConditionFailed);
+
+    // An account of another program is not a token account.
+    env.set_account(
+        tokens,
+        Account::new_data(vec![0; smallet::TOKEN_ACCOUNT_LEN], &smallet::ID),
+    );
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotATokenAccount);
+}
+
+#[test]
+fn execute_with_other_owner() {
+    let mut env = Env::new(2, 1, 0);
+    let state = Pubkey::new_unique();
+    env.set_account(state, Account::new_data(vec![], &spl_token::ID));
+    let transaction = env
+        .propose_with_pre_conditions(vec![pre_condition(
+            state,
+            AccountCheck::Owner {
+                program_id: smallet::ID,
+            },
+        )])
+        .unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::PreConditionFailed);
+}
+
+#[test]
+fn execute_without_pre_condition_account() {
+    let mut env = Env::new(2, 1, 0);
+    let vault = Pubkey::new_unique();
+    env.fund(&vault, LAMPORTS_PER_SOL);
+    let transaction = env
+        .propose_with_pre_conditions(vec![pre_condition(
+            vault,
+            AccountCheck::MinLamports { lamports: 1 },
+        )])
+        .unwrap();
+
+    let owner = env.owners[0];
+    let tx = env.transaction_account(&transaction);
+    let mut instruction = ix::execute_transaction(&env.smallet, &transaction, &tx, &owner);
+    instruction.accounts.pop();
+    assert_error(
+        env.process(&[instruction], &[owner]),
+        ErrorCode::MissingPreConditionAccount,
+    );
+}
+
+#[test]
+fn propose_impossible_token_amount() {
+    let mut env = Env::new(2, 1, 0);
+    assert_error(
+        env.propose_with_pre_conditions(vec![pre_condition(
+            Pubkey::new_unique(),
+            AccountCheck::TokenAmount { min: 2, max: 1 },
+        )])
+        .map(|_| vec![]),
+        ErrorCode::InvalidPreCondition,
+    );
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use smallet::{DEFAULT_GRACE_PERIOD, NO_ETA};
+use smallet_client::status::TransactionStatus;
+
+use crate::helpers::*;
+
+impl Env {
+    fn status(&self, transaction: &Pubkey) -> TransactionStatus {
+        TransactionStatus::of(
+            &self.smallet_account(),
+            None,
+            &self.transaction_account(transaction),
+            self.now(),
+        )
+    }
+}
+
+#[test]
+fn status_follows_transaction_lifecycle() {
+    let mut env = Env::new(3, 2, 0);
+    let eta = env.now() + 100;
+    let transaction = env.propose(0, vec![], eta);
+    assert_eq!(
+        env.status(&transaction),
+        TransactionStatus::NeedsApprovals {
+            approvals: 1,
+            threshold: 2
+        }
+    );
+    assert_eq!(
+        env.status(&transaction).to_string(),
+        "needs approvals (1/2)"
+    );
+
+    env.approve(&transaction, 1).unwrap();
+    assert_eq!(
+        env.status(&transaction),
+        TransactionStatus::Timelocked { eta }
+    );
+    assert!(env.status(&transaction).is_pending());
+
+    env.bank.warp_to_timestamp(eta);
+    assert_eq!(env.status(&transaction), TransactionStatus::Ready);
+
+    env.execute(&transaction, 2).unwrap();
+    assert_eq!(
+        env.status(&transaction),
+        TransactionStatus::Executed { at: eta }
+    );
+    assert!(!env.status(&transaction).is_pending());
+}
+
+#[test]
+fn status_of_stale_transactions() {
+    let mut env = Env::new(3, 1, 0);
+    let eta = env.now() + 100;
+    let expiring = env.propose(0, vec![], eta);
+    env.bank.warp_to_timestamp(eta + DEFAULT_GRACE_PERIOD);
+    assert_eq!(env.status(&expiring), TransactionStatus::Ready);
+    env.bank.advance_clock(1);
+    assert_eq!(
+        env.status(&expiring),
+        TransactionStatus::Expired {
+            at: eta + DEFAULT_GRACE_PERIOD
+        }
+    );
+    assert!(!env.status(&expiring).is_pending());
+
+    let orphaned = env.propose(0, vec![], NO_ETA);
+    let owners = env.owners[..2].to_vec();
+    env.set_owners(owners).unwrap();
+    assert_eq!(env.status(&orphaned), TransactionStatus::OwnerSetChanged);
+    assert_eq!(
+        env.status(&orphaned).to_string(),
+        "stale (owner set changed)"
+    );
+}
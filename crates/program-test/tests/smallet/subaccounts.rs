@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use smallet::{ErrorCode, SubaccountType, MAX_LABEL_LEN, SUBACCOUNT_INFO_VERSION};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{accounts, instructions as ix, pda};
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+impl Env {
+    fn create_subaccount_info(
+        &mut self,
+        owner: usize,
+        subaccount_type: SubaccountType,
+        index: u64,
+        label: Option<String>,
+    ) -> TransactionResult {
+        let registry_index = self.smallet_account().num_subaccounts;
+        let owner = self.owners[owner];
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_subaccount_info(
+                &smallet,
+                registry_index,
+                &owner,
+                &payer,
+                subaccount_type,
+                index,
+                label,
+            )],
+            &[owner],
+        )
+    }
+
+    fn subaccount_info(&self, subaccount: &Pubkey) -> Option<smallet::SubaccountInfo> {
+        let (address, _) = pda::find_subaccount_info_address(subaccount);
+        let account = self.bank.get_account(&address)?;
+        Some(accounts::decode_subaccount_info(&account.data).unwrap())
+    }
+}
+
+fn label(len: usize) -> Option<String> {
+    Some("x".repeat(len))
+}
+
+#[test]
+fn create_subaccount_info() {
+    let mut env = Env::new(2, 2, 0);
+    let (subaccount, _) = pda::find_owner_invoker_address(&env.smallet, 5);
+    let result =
+        env.create_subaccount_info(1, SubaccountType::OwnerInvoker, 5, label(MAX_LABEL_LEN));
+
+    let info = env.subaccount_info(&subaccount).unwrap();
+    assert_eq!(info.smallet, env.smallet);
+    assert_eq!(info.subaccount_type, SubaccountType::OwnerInvoker);
+    assert_eq!(info.index, 5);
+    assert_eq!(info.version, SUBACCOUNT_INFO_VERSION);
+    assert_eq!(info.registry_index, 0);
+    assert_eq!(info.created_at, env.now());
+    assert_eq!(info.label, label(MAX_LABEL_LEN));
+
+    let (entry, _) = pda::find_subaccount_registry_address(&env.smallet, 0);
+    let entry =
+        accounts::decode_subaccount_registry_entry(&env.bank.get_account(&entry).unwrap().data)
+            .unwrap();
+    assert_eq!(entry.smallet, env.smallet);
+    assert_eq!(entry.registry_index, 0);
+    assert_eq!(entry.subaccount, subaccount);
+    assert_eq!(env.smallet_account().num_subaccounts, 1);
+
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::SubaccountCreate(event)]
+            if event.subaccount == subaccount && event.registry_index == 0
+    ));
+
+    // The next registry entry takes the next index.
+    env.create_subaccount_info(0, SubaccountType::Derived, 5, None)
+        .unwrap();
+    let (derived, _) = pda::find_wallet_derived_address(&env.smallet, 5);
+    assert_eq!(env.subaccount_info(&derived).unwrap().registry_index, 1);
+
+    // Each sub-account is registered once.
+    assert!(env
+        .create_subaccount_info(0, SubaccountType::Derived, 5, None)
+        .is_err());
+}
+
+#[test]
+fn create_subaccount_info_by_non_owner() {
+    let mut env = Env::new(1, 1, 0);
+    env.owners.push(Pubkey::new_unique());
+    assert_error(
+        env.create_subaccount_info(1, SubaccountType::Derived, 0, None),
+        ErrorCode::InvalidOwner,
+    );
+}
+
+#[test]
+fn create_subaccount_info_with_long_label() {
+    let mut env = Env::new(1, 1, 0);
+    assert_error(
+        env.create_subaccount_info(0, SubaccountType::Derived, 0, label(MAX_LABEL_LEN + 1)),
+        ErrorCode::LabelTooLong,
+    );
+}
+
+#[test]
+fn create_subaccount_info_of_other_address() {
+    let mut env = Env::new(1, 1, 0);
+    let owner = env.owners[0];
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let mut create = ix::create_subaccount_info(
+        &smallet,
+        0,
+        &owner,
+        &payer,
+        SubaccountType::Derived,
+        0,
+        None,
+    );
+    let (subaccount, _) = pda::find_wallet_derived_address(&smallet, 0);
+    let (_, bump) = pda::find_subaccount_info_address(&subaccount);
+    create.data = smallet::instruction::CreateSubaccountInfo {
+        _bump: bump,
+        subaccount,
+        smallet,
+        index: 0,
+        subaccount_type: SubaccountType::OwnerInvoker,
+        label: None,
+    }
+    .data();
+    assert_error(
+        env.process(&[create], &[owner]),
+        ErrorCode::SubaccountOwnerMismatch,
+    );
+}
+
+#[test]
+fn update_subaccount_info() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_subaccount_info(0, SubaccountType::Derived, 0, None)
+        .unwrap();
+    let (subaccount, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let smallet = env.smallet;
+
+    let result = env.govern(vec![ix::update_subaccount_info(
+        &smallet,
+        &subaccount,
+        Some("treasury".to_string()),
+    )
+    .into()]);
+    assert_eq!(
+        env.subaccount_info(&subaccount).unwrap().label.as_deref(),
+        Some("treasury")
+    );
+    assert!(matches!(
+        &events(&result)[..],
+        [
+            SmalletEvent::SubaccountUpdate(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.label.as_deref() == Some("treasury")
+    ));
+
+    assert_error(
+        env.govern(vec![ix::update_subaccount_info(
+            &smallet,
+            &subaccount,
+            label(MAX_LABEL_LEN + 1),
+        )
+        .into()]),
+        ErrorCode::LabelTooLong,
+    );
+
+    let err = env
+        .process(
+            &[ix::update_subaccount_info(&smallet, &subaccount, None)],
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.error,
+        smallet_program_test::InstructionError::MissingSigner(smallet)
+    );
+}
+
+#[test]
+fn close_subaccount_info() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_subaccount_info(0, SubaccountType::Derived, 0, None)
+        .unwrap();
+    let (subaccount, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let (info, _) = pda::find_subaccount_info_address(&subaccount);
+    let (entry, _) = pda::find_subaccount_registry_address(&env.smallet, 0);
+    let rent = env.bank.lamports(&info) + env.bank.lamports(&entry);
+    let receiver = Pubkey::new_unique();
+    let smallet = env.smallet;
+
+    let result = env.govern(vec![ix::close_subaccount_info(
+        &smallet,
+        &subaccount,
+        0,
+        &receiver,
+    )
+    .into()]);
+    assert!(env.bank.get_account(&info).is_none());
+    assert!(env.bank.get_account(&entry).is_none());
+    assert_eq!(env.bank.lamports(&receiver), rent);
+    assert!(matches!(
+        &events(&result)[..],
+        [
+            SmalletEvent::SubaccountClose(event),
+            SmalletEvent::TransactionExecute(_)
+        ] if event.subaccount == subaccount && event.registry_index == 0
+    ));
+
+    // Registry indices are not reused.
+    assert_eq!(env.smallet_account().num_subaccounts, 1);
+}
+
+#[test]
+fn close_subaccount_info_with_other_registry_entry() {
+    let mut env = Env::new(1, 1, 0);
+    env.create_subaccount_info(0, SubaccountType::Derived, 0, None)
+        .unwrap();
+    env.create_subaccount_info(0, SubaccountType::Derived, 1, None)
+        .unwrap();
+    let (subaccount, _) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let smallet = env.smallet;
+    assert_invariant_failed(env.govern(vec![
+        ix::close_subaccount_info(&smallet, &subaccount, 1, &Pubkey::new_unique()).into(),
+    ]));
+}
@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::system_program;
+use smallet::{
+    AllowedInstruction, ErrorCode, PdaSigner, TXInstruction, TimelockTier, TransactionOptions,
+    SECONDS_PER_DAY,
+};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+const ADMIN_DELAY: i64 = 7 * SECONDS_PER_DAY;
+const TREASURY_DELAY: i64 = 2 * SECONDS_PER_DAY;
+const OPS: u8 = 0;
+const TREASURY: u8 = 1;
+
+/// "ops" may only move SOL, without delay and with a single approval.
+/// "treasury" may call any instruction of the System program.
+fn tiers() -> Vec<TimelockTier> {
+    let transfer = AllowedInstruction {
+        program_id: system_program::ID,
+        discriminator: 2u32.to_le_bytes().to_vec(),
+    };
+    vec![
+        TimelockTier {
+            name: "ops".to_string(),
+            minimum_delay: 0,
+            threshold: 1,
+            allowed_instructions: vec![transfer],
+        },
+        TimelockTier {
+            name: "treasury".to_string(),
+            minimum_delay: TREASURY_DELAY,
+            threshold: 2,
+            allowed_instructions: vec![AllowedInstruction {
+                program_id: system_program::ID,
+                discriminator: vec![],
+            }],
+        },
+    ]
+}
+
+impl Env {
+    /// A smallet of 3 owners whose untiered transactions need 3 approvals
+    /// and a week of delay, with the [tiers] set.
+    fn with_tiers() -> Self {
+        let mut env = Env::new(3, 3, ADMIN_DELAY);
+        env.set_tiers(tiers()).unwrap();
+        env
+    }
+
+    fn set_tiers(&mut self, tiers: Vec<TimelockTier>) -> TransactionResult {
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.govern(vec![ix::set_timelock_tiers(&smallet, &payer, tiers).into()])
+    }
+
+    /// Proposes `instructions` in `tier`, signed by the wallet derived
+    /// address at index 0.
+    fn propose_in_tier(
+        &mut self,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+        tier: u8,
+    ) -> std::result::Result<Pubkey, smallet_program_test::TransactionError> {
+        let (_, bump) = pda::find_wallet_derived_address(&self.smallet, 0);
+        let proposer = self.owners[0];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                instructions,
+                eta,
+                TransactionOptions {
+                    pda_signers: vec![PdaSigner::Derived { index: 0, bump }],
+                    tier: Some(tier),
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+}
+
+#[test]
+fn set_timelock_tiers() {
+    let mut env = Env::new(3, 3, 0);
+    let len = env.bank.get_account(&env.smallet).unwrap().data.len();
+    let result = env.set_tiers(tiers());
+    assert_eq!(env.smallet_account().tiers, tiers());
+    assert!(env.bank.get_account(&env.smallet).unwrap().data.len() > len);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::TimelockTiersSet(e) if e.tiers == tiers()
+    )));
+
+    // Shrinking the tiers keeps the account size.
+    let len = env.bank.get_account(&env.smallet).unwrap().data.len();
+    env.set_tiers(vec![]).unwrap();
+    assert!(env.smallet_account().tiers.is_empty());
+    assert_eq!(env.bank.get_account(&env.smallet).unwrap().data.len(), len);
+}
+
+#[test]
+fn set_timelock_tiers_validates_tiers() {
+    let mut env = Env::new(2, 1, 0);
+    let mut too_high = tiers();
+    too_high[1].threshold = 3;
+    assert_error(env.set_tiers(too_high), ErrorCode::InvalidThreshold);
+
+    let mut too_long = tiers();
+    too_long[0].name = "o".repeat(smallet::MAX_LABEL_LEN + 1);
+    assert_error(env.set_tiers(too_long), ErrorCode::LabelTooLong);
+
+    let mut too_slow = tiers();
+    too_slow[1].minimum_delay = smallet::MAX_DELAY_SECONDS;
+    assert_error(env.set_tiers(too_slow), ErrorCode::DelayTooHigh);
+}
+
+#[test]
+fn execute_ops_tier_without_delay() {
+    let mut env = Env::with_tiers();
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let transaction = env.propose_in_tier(vec![transfer], -1, OPS).unwrap();
+
+    // The proposer's approval meets the threshold of the tier.
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+    assert_eq!(env.transaction_account(&transaction).tier, Some(OPS));
+}
+
+#[test]
+fn execute_treasury_tier() {
+    let mut env = Env::with_tiers();
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+
+    // The delay of the tier applies instead of the one of the smallet.
+    let eta = env.now() + TREASURY_DELAY - 1;
+    assert_error(
+        env.propose_in_tier(vec![transfer.clone()], eta, TREASURY)
+            .map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    let eta = env.now() + TREASURY_DELAY;
+    let transaction = env.propose_in_tier(vec![transfer], eta, TREASURY).unwrap();
+    env.bank.warp_to_timestamp(eta);
+
+    // So does the threshold.
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+    env.approve(&transaction, 1).unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn propose_under_classified_instruction() {
+    let mut env = Env::with_tiers();
+    let smallet = env.smallet;
+    let owners = env.owners[..1].to_vec();
+    assert_error(
+        env.propose_in_tier(vec![ix::set_owners(&smallet, owners).into()], -1, OPS)
+            .map(|_| vec![]),
+        ErrorCode::InstructionNotInTier,
+    );
+
+    // A transfer bundled with an instruction outside the tier is rejected too.
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let (wallet, _) = pda::find_wallet_derived_address(&smallet, 0);
+    let assign: TXInstruction =
+        anchor_lang::solana_program::system_instruction::assign(&wallet, &smallet::ID).into();
+    assert_error(
+        env.propose_in_tier(vec![transfer, assign], -1, OPS)
+            .map(|_| vec![]),
+        ErrorCode::InstructionNotInTier,
+    );
+}
+
+#[test]
+fn propose_in_missing_tier() {
+    let mut env = Env::with_tiers();
+    assert_error(
+        env.propose_in_tier(vec![], -1, 2).map(|_| vec![]),
+        ErrorCode::InvalidTier,
+    );
+}
+
+#[test]
+fn replaced_tier_applies_to_pending_transactions() {
+    let mut env = Env::with_tiers();
+    let recipient = Pubkey::new_unique();
+    let transfer = env.wallet_transfer(&recipient, LAMPORTS_PER_SOL / 2);
+    let transaction = env.propose_in_tier(vec![transfer], -1, OPS).unwrap();
+
+    // Owners restrict the ops tier to memos before the transfer is executed.
+    let mut restricted = tiers();
+    restricted[0].allowed_instructions = vec![AllowedInstruction {
+        program_id: smallet_client::decode::MEMO_PROGRAM_ID,
+        discriminator: vec![],
+    }];
+    env.set_tiers(restricted).unwrap();
+    assert_error(
+        env.execute(&transaction, 0),
+        ErrorCode::InstructionNotInTier,
+    );
+
+    // Removing the tier blocks it as well.
+    env.set_tiers(vec![]).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::InvalidTier);
+}
+
+#[test]
+fn set_owners_lowers_tier_thresholds() {
+    let mut env = Env::new(3, 2, 0);
+    let mut high = tiers();
+    high[1].threshold = 3;
+    env.set_tiers(high).unwrap();
+
+    let owners = env.owners[..2].to_vec();
+    env.set_owners(owners).unwrap();
+    let thresholds: Vec<u64> = env
+        .smallet_account()
+        .tiers
+        .iter()
+        .map(|tier| tier.threshold)
+        .collect();
+    assert_eq!(thresholds, vec![1, 2]);
+}
+
+#[test]
+fn propose_set_owners_without_tier() {
+    // Tiers may be stricter than the smallet's defaults.
+    let mut env = Env::new(3, 1, 0);
+    env.set_tiers(tiers()).unwrap();
+    let smallet = env.smallet;
+    let owners = env.owners[..1].to_vec();
+
+    // Omitting the tier holds the proposal to the strictest one.
+    let set_owners: TXInstruction = ix::set_owners(&smallet, owners).into();
+    assert_error(
+        env.try_propose_untiered(0, vec![set_owners.clone()], -1)
+            .map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    let eta = env.now() + TREASURY_DELAY;
+    let transaction = env.try_propose_untiered(0, vec![set_owners], eta).unwrap();
+    env.bank.warp_to_timestamp(eta);
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+
+    env.approve(&transaction, 1).unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.smallet_account().owners.len(), 1);
+}
+
+#[test]
+fn propose_legacy_transaction_with_tiers() {
+    let mut env = Env::with_tiers();
+    let smallet = env.smallet;
+    let index = env.smallet_account().num_transactions;
+    let proposer = env.owners[0];
+    let payer = env.payer;
+    assert_error(
+        env.process(
+            &[ix::create_transaction(
+                &smallet,
+                index,
+                &proposer,
+                &payer,
+                vec![ix::set_owners(&smallet, vec![proposer]).into()],
+            )],
+            &[proposer],
+        ),
+        ErrorCode::TierRequired,
+    );
+    assert_error(
+        env.try_propose(0, vec![], env.now() + ADMIN_DELAY)
+            .map(|_| vec![]),
+        ErrorCode::TierRequired,
+    );
+}
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, MAX_DELAY_SECONDS, NO_ETA, TRANSACTION_VERSION};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+
+use crate::helpers::*;
+
+#[test]
+fn create_transaction() {
+    let mut env = Env::new(3, 2, 0);
+    let destination = Pubkey::new_unique();
+    let instructions = vec![transfer_from(&env.smallet, &destination, 1)];
+    let (transaction, bump) = pda::find_transaction_address(&env.smallet, 0);
+
+    let proposer = env.owners[1];
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let result = env.process(
+        &[ix::create_transaction(
+            &smallet,
+            0,
+            &proposer,
+            &payer,
+            instructions.clone(),
+        )],
+        &[proposer],
+    );
+
+    let tx = env.transaction_account(&transaction);
+    assert_eq!(tx.smallet, env.smallet);
+    assert_eq!(tx.index, 0);
+    assert_eq!(tx.bump, bump);
+    assert_eq!(tx.proposer, proposer);
+    assert_eq!(tx.instructions, instructions);
+    assert_eq!(tx.signers, vec![false, true, false]);
+    assert_eq!(tx.owner_set_seqno, 0);
+    assert_eq!(tx.eta, NO_ETA);
+    assert_eq!(tx.executor, Pubkey::default());
+    assert_eq!(tx.executed_at, -1);
+    assert_eq!(tx.version, TRANSACTION_VERSION);
+    assert_eq!(tx.program_upgrade, None);
+    assert!(tx.pda_signers.is_empty());
+    assert_eq!(env.smallet_account().num_transactions, 1);
+
+    match &events(&result)[..] {
+        [SmalletEvent::TransactionCreate(event)] => {
+            assert_eq!(event.transaction, transaction);
+            assert_eq!(event.proposer, proposer);
+            assert_eq!(event.instructions, instructions);
+            assert_eq!(event.eta, NO_ETA);
+        }
+        events => panic!("unexpected events {:?}", events),
+    }
+}
+
+#[test]
+fn create_transaction_with_timelock() {
+    let mut env = Env::new(2, 1, 100);
+    let eta = env.now() + 100;
+    let transaction = env.propose(0, vec![], eta);
+    assert_eq!(env.transaction_account(&transaction).eta, eta);
+
+    // Transactions are indexed in proposal order.
+    let next = env.propose(1, vec![], eta + 1);
+    assert_eq!(env.transaction_account(&next).index, 1);
+    assert_eq!(env.smallet_account().num_transactions, 2);
+}
+
+#[test]
+fn create_transaction_by_non_owner() {
+    let mut env = Env::new(2, 1, 0);
+    let stranger = Pubkey::new_unique();
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let result = env.process(
+        &[ix::create_transaction(
+            &smallet,
+            0,
+            &stranger,
+            &payer,
+            vec![],
+        )],
+        &[stranger],
+    );
+    assert_error(result, ErrorCode::InvalidOwner);
+    assert_eq!(env.smallet_account().num_transactions, 0);
+}
+
+#[test]
+fn create_transaction_before_minimum_delay() {
+    let mut env = Env::new(2, 1, 100);
+    let now = env.now();
+    assert_error(
+        env.try_propose(0, vec![], now + 99).map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    assert_error(
+        env.try_propose(0, vec![], NO_ETA).map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+    env.propose(0, vec![], now + 100);
+}
+
+#[test]
+fn create_transaction_delay_too_high() {
+    let mut env = Env::new(2, 1, 0);
+    let now = env.now();
+    assert_error(
+        env.try_propose(0, vec![], now + MAX_DELAY_SECONDS + 1)
+            .map(|_| vec![]),
+        ErrorCode::DelayTooHigh,
+    );
+    env.propose(0, vec![], now + MAX_DELAY_SECONDS);
+}
+
+#[test]
+fn create_transaction_eta_in_past() {
+    let mut env = Env::new(2, 1, 0);
+    let now = env.now();
+    assert_invariant_failed(env.try_propose(0, vec![], now - 1).map(|_| vec![]));
+    assert_invariant_failed(env.try_propose(0, vec![], -2).map(|_| vec![]));
+}
+
+#[test]
+fn approve_and_unapprove() {
+    let mut env = Env::new(3, 2, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+
+    let result = env.approve(&transaction, 2);
+    assert_eq!(
+        env.transaction_account(&transaction).signers,
+        vec![true, false, true]
+    );
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionApprove(event)] if event.owner == env.owners[2]
+    ));
+
+    // Approving twice is idempotent.
+    env.approve(&transaction, 2).unwrap();
+    assert_eq!(env.transaction_account(&transaction).num_signers(), 2);
+
+    let result = env.unapprove(&transaction, 0);
+    assert_eq!(
+        env.transaction_account(&transaction).signers,
+        vec![false, false, true]
+    );
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionUnapprove(event)] if event.owner == env.owners[0]
+    ));
+}
+
+#[test]
+fn approve_by_non_owner() {
+    let mut env = Env::new(2, 2, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    let stranger = Pubkey::new_unique();
+    let smallet = env.smallet;
+    assert_error(
+        env.process(
+            &[ix::approve(&smallet, &transaction, &stranger)],
+            &[stranger],
+        ),
+        ErrorCode::InvalidOwner,
+    );
+    assert_error(
+        env.process(
+            &[ix::unapprove(&smallet, &transaction, &stranger)],
+            &[stranger],
+        ),
+        ErrorCode::InvalidOwner,
+    );
+}
+
+#[test]
+fn approve_transaction_of_other_smallet() {
+    let mut env = Env::new(2, 2, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+
+    let mut other = Env::new(1, 1, 0);
+    other.bank = env.bank;
+    other.fund(&other.payer.clone(), 10 * LAMPORTS_PER_SOL);
+    let (base, owners) = (other.base, other.owners.clone());
+    other.create_smallet(1, owners, 1, 0).unwrap();
+    assert_eq!(pda::find_smallet(&base).0, other.smallet);
+
+    let owner = other.owners[0];
+    let smallet = other.smallet;
+    let result = other.process(&[ix::approve(&smallet, &transaction, &owner)], &[owner]);
+    assert_error(result, anchor_lang::error::ErrorCode::ConstraintHasOne);
+}
+
+#[test]
+fn approve_executed_transaction() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    env.execute(&transaction, 0).unwrap();
+    assert_error(env.approve(&transaction, 1), ErrorCode::AlreadyExecuted);
+    assert_error(env.unapprove(&transaction, 0), ErrorCode::AlreadyExecuted);
+}
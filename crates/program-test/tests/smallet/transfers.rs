@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, SubaccountType, TransferAsset, VaultTransfer, NO_ETA};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::{TransactionError, TransactionResult};
+
+use crate::helpers::*;
+
+impl Env {
+    /// Proposes `transfer` by owner `proposer`.
+    fn propose_transfer(
+        &mut self,
+        proposer: usize,
+        transfer: VaultTransfer,
+        eta: i64,
+    ) -> std::result::Result<(Pubkey, TransactionResult), TransactionError> {
+        let proposer = self.owners[proposer];
+        let nonce = self.now() as u64;
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        let logs = self.process(
+            &[ix::propose_transfer(
+                &smallet, &proposer, &payer, nonce, transfer, eta,
+            )],
+            &[proposer],
+        )?;
+        Ok((transaction, Ok(logs)))
+    }
+}
+
+fn sol_transfer(amount: u64, recipient: Pubkey) -> VaultTransfer {
+    VaultTransfer {
+        asset: TransferAsset::Sol,
+        amount,
+        source_index: 0,
+        recipient,
+    }
+}
+
+#[test]
+fn transfer_sol() {
+    let mut env = Env::new(3, 2, 0);
+    let (vault, _) = SubaccountType::Derived.find_address(&env.smallet, 0);
+    env.fund(&vault, LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    let transfer = sol_transfer(LAMPORTS_PER_SOL / 2, recipient);
+
+    let (transaction, result) = env.propose_transfer(0, transfer, NO_ETA).unwrap();
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::TransferPropose(e) if e.transaction == transaction && e.transfer == transfer
+    )));
+    assert_eq!(
+        env.transaction_account(&transaction).transfer,
+        Some(transfer)
+    );
+
+    // Transfers go through the usual approvals.
+    assert_error(env.execute(&transaction, 0), ErrorCode::NotEnoughSigners);
+    env.approve_threshold(&transaction);
+    let result = env.execute(&transaction, 0);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::TransferExecute(e) if e.transaction == transaction
+            && e.asset == TransferAsset::Sol
+            && e.amount == LAMPORTS_PER_SOL / 2
+            && e.source == vault
+            && e.destination == recipient
+    )));
+    assert_eq!(env.bank.lamports(&recipient), LAMPORTS_PER_SOL / 2);
+    assert_eq!(env.bank.lamports(&vault), LAMPORTS_PER_SOL / 2);
+}
+
+#[test]
+fn transfer_tokens() {
+    let mut env = Env::new(2, 1, 0);
+    let (vault, _) = SubaccountType::Derived.find_address(&env.smallet, 1);
+    let mint = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let source = smallet::associated_token_address(&vault, &mint);
+    let destination = smallet::associated_token_address(&recipient, &mint);
+    env.set_token_account(source, mint, vault, 1_000);
+    env.set_token_account(destination, mint, recipient, 0);
+
+    let transfer = VaultTransfer {
+        asset: TransferAsset::Token { mint },
+        amount: 600,
+        source_index: 1,
+        recipient,
+    };
+    let (transaction, _) = env.propose_transfer(0, transfer, NO_ETA).unwrap();
+    let result = env.execute(&transaction, 0);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::TransferExecute(e) if e.source == source && e.destination == destination
+    )));
+
+    // The source no longer holds enough tokens.
+    env.bank.advance_clock(1);
+    let (transaction, _) = env.propose_transfer(0, transfer, NO_ETA).unwrap();
+    assert!(env.execute(&transaction, 0).is_err());
+}
+
+#[test]
+fn transfer_respects_timelock() {
+    let mut env = Env::new(2, 1, 100);
+    let transfer = sol_transfer(LAMPORTS_PER_SOL / 2, Pubkey::new_unique());
+    assert_error(
+        env.propose_transfer(0, transfer, NO_ETA).map(|_| vec![]),
+        ErrorCode::InvalidETA,
+    );
+
+    let (vault, _) = SubaccountType::Derived.find_address(&env.smallet, 0);
+    env.fund(&vault, LAMPORTS_PER_SOL);
+    let eta = env.now() + 100;
+    let (transaction, _) = env.propose_transfer(0, transfer, eta).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::TransactionNotReady);
+    env.bank.warp_to_timestamp(eta);
+    env.execute(&transaction, 0).unwrap();
+}
+
+#[test]
+fn transfer_zero_amount() {
+    let mut env = Env::new(2, 1, 0);
+    assert_error(
+        env.propose_transfer(0, sol_transfer(0, Pubkey::new_unique()), NO_ETA)
+            .map(|_| vec![]),
+        ErrorCode::InvalidTransferAmount,
+    );
+}
+
+#[test]
+fn transfer_by_non_owner() {
+    let mut env = Env::new(2, 1, 0);
+    let outsider = Pubkey::new_unique();
+    let nonce = env.now() as u64;
+    let smallet = env.smallet;
+    let payer = env.payer;
+    assert_error(
+        env.process(
+            &[ix::propose_transfer(
+                &smallet,
+                &outsider,
+                &payer,
+                nonce,
+                sol_transfer(1, Pubkey::new_unique()),
+                NO_ETA,
+            )],
+            &[outsider],
+        ),
+        ErrorCode::InvalidOwner,
+    );
+}
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use anchor_lang::solana_program::hash::hash;
+use smallet::{ErrorCode, ProgramUpgrade, NO_ETA};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+use smallet_program_test::Account;
+use solana_program_test::programs::spl_programs;
+
+use crate::helpers::*;
+
+/// The bytes of an SPL program bundled with the runtime.
+fn spl_program(program_id: &str) -> Vec<u8> {
+    let program_id: Pubkey = program_id.parse().unwrap();
+    spl_programs(&Rent::default())
+        .into_iter()
+        .find(|(id, _)| *id == program_id)
+        .map(|(_, account)| Account::from(account).data)
+        .unwrap()
+}
+
+/// An upgradeable program whose upgrade authority is the smallet, and a
+/// buffer holding its next version: versions 1 and 3 of SPL Memo.
+struct Upgrade {
+    program: Pubkey,
+    programdata: Pubkey,
+    buffer: Pubkey,
+    spill: Pubkey,
+    bytes: Vec<u8>,
+}
+
+fn loader_account(state: UpgradeableLoaderState, bytes: &[u8]) -> Account {
+    let mut data = bincode::serialize(&state).unwrap();
+    let offset = match state {
+        UpgradeableLoaderState::Buffer { .. } => UpgradeableLoaderState::buffer_data_offset(),
+        UpgradeableLoaderState::ProgramData { .. } => {
+            UpgradeableLoaderState::programdata_data_offset()
+        }
+        _ => Ok(data.len()),
+    }
+    .unwrap();
+    data.resize(offset, 0);
+    data.extend_from_slice(bytes);
+    Account::new_data(data, &bpf_loader_upgradeable::ID)
+}
+
+impl Upgrade {
+    fn new(env: &mut Env) -> Self {
+        let program = Pubkey::new_unique();
+        let (programdata, _) =
+            Pubkey::find_program_address(&[program.as_ref()], &bpf_loader_upgradeable::ID);
+        let bytes = spl_program("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+        // The program data account must be large enough for the new version.
+        let mut old = spl_program("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+        old.resize(old.len().max(bytes.len()), 0);
+        let mut program_account = loader_account(
+            UpgradeableLoaderState::Program {
+                programdata_address: programdata,
+            },
+            &[],
+        );
+        program_account.executable = true;
+        env.set_account(program, program_account);
+        env.set_account(
+            programdata,
+            loader_account(
+                UpgradeableLoaderState::ProgramData {
+                    slot: 0,
+                    upgrade_authority_address: Some(env.smallet),
+                },
+                &old,
+            ),
+        );
+
+        let buffer = Pubkey::new_unique();
+        env.set_account(
+            buffer,
+            loader_account(
+                UpgradeableLoaderState::Buffer {
+                    authority_address: Some(env.smallet),
+                },
+                &bytes,
+            ),
+        );
+        Self {
+            program,
+            programdata,
+            buffer,
+            spill: Pubkey::new_unique(),
+            bytes,
+        }
+    }
+
+    fn propose(&self, env: &mut Env, buffer_hash: [u8; 32]) -> TransactionResultWith<Pubkey> {
+        let proposer = env.owners[0];
+        let (transaction, _) = pda::find_transaction_v2_address(&env.smallet, &proposer, 0);
+        let smallet = env.smallet;
+        let payer = env.payer;
+        let logs = env.process(
+            &[ix::propose_program_upgrade(
+                &smallet,
+                &proposer,
+                &payer,
+                0,
+                &self.program,
+                &self.buffer,
+                &self.spill,
+                buffer_hash,
+                NO_ETA,
+            )],
+            &[proposer],
+        )?;
+        Ok((transaction, logs))
+    }
+
+    fn hash(&self) -> [u8; 32] {
+        hash(&self.bytes).to_bytes()
+    }
+}
+
+type TransactionResultWith<T> =
+    std::result::Result<(T, Vec<String>), smallet_program_test::TransactionError>;
+
+#[test]
+fn upgrade_program() {
+    let mut env = Env::new(2, 2, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let (transaction, logs) = upgrade.propose(&mut env, upgrade.hash()).unwrap();
+
+    let tx = env.transaction_account(&transaction);
+    let expected = ProgramUpgrade {
+        program: upgrade.program,
+        buffer: upgrade.buffer,
+        buffer_hash: upgrade.hash(),
+    };
+    assert_eq!(tx.program_upgrade, Some(expected));
+    assert_eq!(
+        tx.instructions,
+        vec![expected.to_instruction(&env.smallet, &upgrade.spill)]
+    );
+    assert!(matches!(
+        &events(&Ok(logs))[..],
+        [
+            SmalletEvent::TransactionCreate(_),
+            SmalletEvent::ProgramUpgradePropose(event)
+        ] if event.program == upgrade.program && event.buffer_hash == upgrade.hash()
+    ));
+
+    let buffer_lamports = env.bank.lamports(&upgrade.buffer);
+    env.approve(&transaction, 1).unwrap();
+    env.execute(&transaction, 0).unwrap();
+
+    let programdata = env.bank.get_account(&upgrade.programdata).unwrap();
+    let offset = UpgradeableLoaderState::programdata_data_offset().unwrap();
+    assert_eq!(
+        &programdata.data[offset..offset + upgrade.bytes.len()],
+        &upgrade.bytes[..]
+    );
+    assert!(env.bank.get_account(&upgrade.buffer).is_none());
+    assert_eq!(env.bank.lamports(&upgrade.spill), buffer_lamports);
+}
+
+#[test]
+fn propose_upgrade_with_invalid_buffer() {
+    let mut env = Env::new(1, 1, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let mut buffer = env.bank.get_account(&upgrade.buffer).unwrap();
+    buffer.owner = Pubkey::new_unique();
+    env.set_account(upgrade.buffer, buffer);
+    assert_error(
+        upgrade.propose(&mut env, upgrade.hash()).map(|_| vec![]),
+        ErrorCode::InvalidProgramBuffer,
+    );
+}
+
+#[test]
+fn propose_upgrade_of_non_executable_program() {
+    let mut env = Env::new(1, 1, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let mut program = env.bank.get_account(&upgrade.program).unwrap();
+    program.executable = false;
+    env.set_account(upgrade.program, program);
+    assert_invariant_failed(upgrade.propose(&mut env, upgrade.hash()).map(|_| vec![]));
+}
+
+#[test]
+fn execute_upgrade_with_changed_buffer() {
+    let mut env = Env::new(1, 1, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let (transaction, _) = upgrade.propose(&mut env, upgrade.hash()).unwrap();
+
+    let mut buffer = env.bank.get_account(&upgrade.buffer).unwrap();
+    *buffer.data.last_mut().unwrap() ^= 1;
+    env.set_account(upgrade.buffer, buffer);
+    assert_error(env.execute(&transaction, 0), ErrorCode::BufferHashMismatch);
+}
+
+#[test]
+fn execute_upgrade_with_truncated_buffer() {
+    let mut env = Env::new(1, 1, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let (transaction, _) = upgrade.propose(&mut env, upgrade.hash()).unwrap();
+
+    let mut buffer = env.bank.get_account(&upgrade.buffer).unwrap();
+    buffer.data.truncate(1);
+    env.set_account(upgrade.buffer, buffer);
+    assert_error(
+        env.execute(&transaction, 0),
+        ErrorCode::InvalidProgramBuffer,
+    );
+}
+
+#[test]
+fn execute_upgrade_without_buffer() {
+    let mut env = Env::new(1, 1, 0);
+    let upgrade = Upgrade::new(&mut env);
+    let (transaction, _) = upgrade.propose(&mut env, upgrade.hash()).unwrap();
+
+    let tx = env.transaction_account(&transaction);
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+    let mut execute = ix::execute_transaction(&smallet, &transaction, &tx, &owner);
+    execute
+        .accounts
+        .retain(|meta| meta.pubkey != upgrade.buffer);
+    assert_error(
+        env.process(&[execute], &[owner]),
+        ErrorCode::MissingProgramBuffer,
+    );
+}
@@ -0,0 +1,392 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::InstructionError;
+use smallet::{ErrorCode, PdaSigner, TXInstruction, TransactionOptions, NO_ETA, NO_INDEX};
+use smallet_client::events::SmalletEvent;
+use smallet_client::{instructions as ix, pda};
+
+use crate::helpers::*;
+
+impl Env {
+    /// Proposes `instructions` via `create_transaction_v2`.
+    fn propose_v2(
+        &mut self,
+        proposer: usize,
+        nonce: u64,
+        instructions: Vec<TXInstruction>,
+        pda_signers: Vec<PdaSigner>,
+    ) -> std::result::Result<Pubkey, smallet_program_test::TransactionError> {
+        let proposer = self.owners[proposer];
+        let (transaction, _) = pda::find_transaction_v2_address(&self.smallet, &proposer, nonce);
+        let smallet = self.smallet;
+        let payer = self.payer;
+        self.process(
+            &[ix::create_transaction_v2(
+                &smallet,
+                &proposer,
+                &payer,
+                nonce,
+                instructions,
+                NO_ETA,
+                TransactionOptions {
+                    pda_signers,
+                    ..Default::default()
+                },
+            )],
+            &[proposer],
+        )?;
+        Ok(transaction)
+    }
+}
+
+fn derived(env: &Env, index: u64) -> (Pubkey, PdaSigner) {
+    let (address, bump) = pda::find_wallet_derived_address(&env.smallet, index);
+    (address, PdaSigner::Derived { index, bump })
+}
+
+#[test]
+fn create_transaction_v2() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose_v2(1, 42, vec![], vec![]).unwrap();
+    let (expected, _) = pda::find_transaction_v2_address(&env.smallet, &env.owners[1], 42);
+    assert_eq!(transaction, expected);
+
+    let tx = env.transaction_account(&transaction);
+    assert_eq!(tx.index, NO_INDEX);
+    assert_eq!(tx.proposer, env.owners[1]);
+    assert_eq!(tx.signers, vec![false, true]);
+    // Unlike `create_transaction`, no index is reserved.
+    assert_eq!(env.smallet_account().num_transactions, 0);
+
+    // Nonces are per proposer.
+    env.propose_v2(0, 42, vec![], vec![]).unwrap();
+    assert!(env.propose_v2(1, 42, vec![], vec![]).is_err());
+}
+
+#[test]
+fn create_transaction_v2_does_not_lock_smallet() {
+    let mut env = Env::new(2, 1, 0);
+    let smallet = env.smallet;
+    let payer = env.payer;
+    let proposals: Vec<_> = env
+        .owners
+        .iter()
+        .map(|proposer| {
+            ix::create_transaction_v2(
+                &smallet,
+                proposer,
+                &payer,
+                0,
+                vec![],
+                NO_ETA,
+                TransactionOptions::default(),
+            )
+        })
+        .collect();
+    for proposal in &proposals {
+        let meta = proposal
+            .accounts
+            .iter()
+            .find(|meta| meta.pubkey == smallet)
+            .unwrap();
+        assert!(!meta.is_writable);
+    }
+
+    // Both owners propose in the same transaction without touching the smallet.
+    let data = env.bank.get_account(&smallet).unwrap().data;
+    let owners = env.owners.clone();
+    env.process(&proposals, &owners).unwrap();
+    assert_eq!(env.bank.get_account(&smallet).unwrap().data, data);
+}
+
+#[test]
+fn create_transaction_v2_by_non_owner() {
+    let mut env = Env::new(1, 1, 0);
+    env.owners.push(Pubkey::new_unique());
+    assert_error(
+        env.propose_v2(1, 0, vec![], vec![]).map(|_| vec![]),
+        ErrorCode::InvalidOwner,
+    );
+}
+
+#[test]
+fn assign_transaction_index() {
+    let mut env = Env::new(2, 1, 0);
+    env.propose(0, vec![], NO_ETA);
+    let transaction = env.propose_v2(0, 0, vec![], vec![]).unwrap();
+
+    let smallet = env.smallet;
+    let result = env.process(&[ix::assign_transaction_index(&smallet, &transaction)], &[]);
+    assert_eq!(env.transaction_account(&transaction).index, 1);
+    assert_eq!(env.smallet_account().num_transactions, 2);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionIndexAssign(event)]
+            if event.transaction == transaction && event.index == 1
+    ));
+
+    assert_error(
+        env.process(&[ix::assign_transaction_index(&smallet, &transaction)], &[]),
+        ErrorCode::TransactionIndexAlreadyAssigned,
+    );
+}
+
+#[test]
+fn assign_transaction_index_to_indexed_transaction() {
+    let mut env = Env::new(2, 1, 0);
+    let transaction = env.propose(0, vec![], NO_ETA);
+    let smallet = env.smallet;
+    assert_error(
+        env.process(&[ix::assign_transaction_index(&smallet, &transaction)], &[]),
+        ErrorCode::TransactionIndexAlreadyAssigned,
+    );
+}
+
+#[test]
+fn execute_with_derived_signer() {
+    let mut env = Env::new(2, 2, 0);
+    let (wallet, signer) = derived(&env, 3);
+    env.fund(&wallet, LAMPORTS_PER_SOL);
+    let destination = Pubkey::new_unique();
+    let transaction = env
+        .propose_v2(
+            0,
+            0,
+            vec![transfer_from(&wallet, &destination, LAMPORTS_PER_SOL)],
+            vec![signer],
+        )
+        .unwrap();
+    assert_eq!(
+        env.transaction_account(&transaction).pda_signers,
+        vec![signer]
+    );
+    env.approve(&transaction, 1).unwrap();
+
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&destination), LAMPORTS_PER_SOL);
+    assert_eq!(env.bank.lamports(&wallet), 0);
+}
+
+#[test]
+fn execute_with_several_pda_signers() {
+    let mut env = Env::new(1, 1, 0);
+    let (first, first_signer) = derived(&env, 0);
+    let (second, second_signer) = derived(&env, 1);
+    env.fund(&first, 2 * LAMPORTS_PER_SOL);
+    env.fund(&second, 3 * LAMPORTS_PER_SOL);
+    let smallet = env.smallet;
+    let destination = Pubkey::new_unique();
+    let transaction = env
+        .propose_v2(
+            0,
+            0,
+            vec![
+                transfer_from(&first, &destination, 2 * LAMPORTS_PER_SOL),
+                transfer_from(&second, &destination, 3 * LAMPORTS_PER_SOL),
+                ix::change_threshold(&smallet, 1).into(),
+            ],
+            vec![first_signer, second_signer, PdaSigner::Smallet],
+        )
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&destination), 5 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn execute_with_owner_invoker_signer() {
+    let mut env = Env::new(2, 1, 0);
+    let (wallet, wallet_signer) = derived(&env, 0);
+    let (invoker, bump) = pda::find_owner_invoker_address(&env.smallet, 4);
+    env.fund(&wallet, LAMPORTS_PER_SOL);
+    env.fund(&invoker, 2 * LAMPORTS_PER_SOL);
+    let destination = Pubkey::new_unique();
+    let transaction = env
+        .propose_v2(
+            0,
+            0,
+            vec![
+                transfer_from(&wallet, &destination, LAMPORTS_PER_SOL),
+                transfer_from(&invoker, &destination, 2 * LAMPORTS_PER_SOL),
+            ],
+            vec![wallet_signer, PdaSigner::OwnerInvoker { index: 4, bump }],
+        )
+        .unwrap();
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.bank.lamports(&destination), 3 * LAMPORTS_PER_SOL);
+    assert_eq!(env.bank.lamports(&invoker), 0);
+}
+
+#[test]
+fn execute_without_declared_pda_signer() {
+    // Declaring a derived signer drops the implicit smallet signature.
+    let mut env = Env::new(1, 1, 0);
+    let (_, signer) = derived(&env, 0);
+    let smallet = env.smallet;
+    let transaction = env
+        .propose_v2(
+            0,
+            0,
+            vec![ix::change_threshold(&smallet, 1).into()],
+            vec![signer],
+        )
+        .unwrap();
+    let tx = env.transaction_account(&transaction);
+    let owner = env.owners[0];
+    let mut execute = ix::execute_transaction(&smallet, &transaction, &tx, &owner);
+    for meta in execute
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == smallet)
+    {
+        meta.is_signer = false;
+    }
+    let err = env.process(&[execute], &[owner]).unwrap_err();
+    assert_eq!(
+        err.error,
+        smallet_program_test::InstructionError::Runtime(InstructionError::PrivilegeEscalation),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn create_transaction_v2_with_non_canonical_bump() {
+    let mut env = Env::new(1, 1, 0);
+    let (_, bump) = pda::find_wallet_derived_address(&env.smallet, 0);
+    let signer = PdaSigner::Derived {
+        index: 0,
+        bump: bump.wrapping_sub(1),
+    };
+    assert_error(
+        env.propose_v2(0, 0, vec![], vec![signer]).map(|_| vec![]),
+        ErrorCode::InvalidSignerBump,
+    );
+
+    let (_, bump) = pda::find_owner_invoker_address(&env.smallet, 0);
+    let signer = PdaSigner::OwnerInvoker {
+        index: 0,
+        bump: bump.wrapping_sub(1),
+    };
+    assert_error(
+        env.propose_v2(0, 0, vec![], vec![signer]).map(|_| vec![]),
+        ErrorCode::InvalidSignerBump,
+    );
+}
+
+#[test]
+fn execute_transaction_derived() {
+    let mut env = Env::new(1, 1, 0);
+    let (wallet, signer) = derived(&env, 7);
+    env.fund(&wallet, LAMPORTS_PER_SOL);
+    let destination = Pubkey::new_unique();
+    let transaction = env
+        .propose_v2(
+            0,
+            0,
+            vec![transfer_from(&wallet, &destination, LAMPORTS_PER_SOL)],
+            vec![signer],
+        )
+        .unwrap();
+    let tx = env.transaction_account(&transaction);
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+
+    assert_error(
+        env.process(
+            &[ix::execute_transaction_derived(
+                &smallet,
+                &transaction,
+                &tx,
+                &owner,
+                6,
+            )],
+            &[owner],
+        ),
+        ErrorCode::DerivedSignerNotDeclared,
+    );
+    env.process(
+        &[ix::execute_transaction_derived(
+            &smallet,
+            &transaction,
+            &tx,
+            &owner,
+            7,
+        )],
+        &[owner],
+    )
+    .unwrap();
+    assert_eq!(env.bank.lamports(&destination), LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn execute_legacy_transaction_derived() {
+    // Transactions proposed via `create_transaction` declare no signers, and
+    // are signed by the derived address they are executed with.
+    let mut env = Env::new(1, 1, 0);
+    let (wallet, _) = derived(&env, 7);
+    env.fund(&wallet, LAMPORTS_PER_SOL);
+    let destination = Pubkey::new_unique();
+    let transaction = env.propose(
+        0,
+        vec![transfer_from(&wallet, &destination, LAMPORTS_PER_SOL)],
+        NO_ETA,
+    );
+    let tx = env.transaction_account(&transaction);
+    assert!(tx.pda_signers.is_empty());
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+    env.process(
+        &[ix::execute_transaction_derived(
+            &smallet,
+            &transaction,
+            &tx,
+            &owner,
+            7,
+        )],
+        &[owner],
+    )
+    .unwrap();
+    assert_eq!(env.bank.lamports(&destination), LAMPORTS_PER_SOL);
+    assert!(env.transaction_account(&transaction).pda_signers.is_empty());
+
+    // Transactions proposed via `create_transaction_v2` must declare it.
+    let transaction = env
+        .propose_v2(0, 0, vec![transfer_from(&wallet, &destination, 0)], vec![])
+        .unwrap();
+    let tx = env.transaction_account(&transaction);
+    assert_error(
+        env.process(
+            &[ix::execute_transaction_derived(
+                &smallet,
+                &transaction,
+                &tx,
+                &owner,
+                7,
+            )],
+            &[owner],
+        ),
+        ErrorCode::DerivedSignerNotDeclared,
+    );
+}
+
+#[test]
+fn execute_transaction_derived_checks_transaction() {
+    let mut env = Env::new(2, 2, 0);
+    let (_, signer) = derived(&env, 0);
+    let transaction = env.propose_v2(0, 0, vec![], vec![signer]).unwrap();
+    let tx = env.transaction_account(&transaction);
+    let smallet = env.smallet;
+    let owner = env.owners[0];
+    assert_error(
+        env.process(
+            &[ix::execute_transaction_derived(
+                &smallet,
+                &transaction,
+                &tx,
+                &owner,
+                0,
+            )],
+            &[owner],
+        ),
+        ErrorCode::NotEnoughSigners,
+    );
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use smallet::{ErrorCode, NO_ETA, SECONDS_PER_DAY};
+use smallet_client::events::SmalletEvent;
+use smallet_client::instructions as ix;
+use smallet_program_test::TransactionResult;
+
+use crate::helpers::*;
+
+impl Env {
+    /// A smallet of 5 owners with a threshold of 3, a day of delay and a veto
+    /// threshold of 2, with a transaction lowering the threshold to 1
+    /// approved by the first 3 owners.
+    fn with_approved_transaction() -> (Self, Pubkey) {
+        let mut env = Env::new(5, 3, SECONDS_PER_DAY);
+        env.change_veto_threshold(2).unwrap();
+        let smallet = env.smallet;
+        let eta = env.now() + SECONDS_PER_DAY;
+        let transaction = env.propose(0, vec![ix::change_threshold(&smallet, 1).into()], eta);
+        env.approve_threshold(&transaction);
+        (env, transaction)
+    }
+
+    fn change_veto_threshold(&mut self, veto_threshold: u64) -> TransactionResult {
+        let smallet = self.smallet;
+        self.govern(vec![
+            ix::change_veto_threshold(&smallet, veto_threshold).into()
+        ])
+    }
+
+    fn veto(&mut self, transaction: &Pubkey, owner: usize) -> TransactionResult {
+        let owner = self.owners[owner];
+        let payer = self.payer;
+        self.process(
+            &[ix::veto(&self.smallet, transaction, &owner, &payer)],
+            &[owner],
+        )
+    }
+}
+
+#[test]
+fn veto_blocks_transaction() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    let result = env.veto(&transaction, 3);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionVeto(e)] if e.owner == env.owners[3] && !e.blocked
+    ));
+    let result = env.veto(&transaction, 4);
+    assert!(matches!(
+        &events(&result)[..],
+        [SmalletEvent::TransactionVeto(e)] if e.blocked
+    ));
+    let tx = env.transaction_account(&transaction);
+    assert_eq!(tx.vetoes, vec![false, false, false, true, true]);
+    assert_eq!(tx.vetoed_at, Some(env.now()));
+
+    env.bank.warp_to_timestamp(tx.eta);
+    assert_error(env.execute(&transaction, 0), ErrorCode::TransactionVetoed);
+
+    // The block is permanent, even if the veto threshold is raised.
+    env.change_veto_threshold(5).unwrap();
+    assert_error(env.execute(&transaction, 0), ErrorCode::TransactionVetoed);
+    assert_eq!(env.smallet_account().threshold, 3);
+}
+
+#[test]
+fn veto_below_threshold() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    env.veto(&transaction, 4).unwrap();
+
+    let eta = env.transaction_account(&transaction).eta;
+    env.bank.warp_to_timestamp(eta);
+    env.execute(&transaction, 0).unwrap();
+    assert_eq!(env.smallet_account().threshold, 1);
+}
+
+#[test]
+fn veto_by_approver() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    env.veto(&transaction, 0).unwrap();
+    env.veto(&transaction, 1).unwrap();
+    assert!(env.transaction_account(&transaction).vetoed_at.is_some());
+}
+
+#[test]
+fn veto_after_eta() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    let eta = env.transaction_account(&transaction).eta;
+    env.bank.warp_to_timestamp(eta);
+    assert_error(env.veto(&transaction, 3), ErrorCode::VetoWindowClosed);
+}
+
+#[test]
+fn veto_without_timelock() {
+    let mut env = Env::new(3, 2, 0);
+    env.change_veto_threshold(1).unwrap();
+    let transaction = env.propose(0, vec![], NO_ETA);
+    assert_error(env.veto(&transaction, 2), ErrorCode::VetoWindowClosed);
+}
+
+#[test]
+fn veto_disabled() {
+    let mut env = Env::new(3, 2, SECONDS_PER_DAY);
+    let eta = env.now() + SECONDS_PER_DAY;
+    let transaction = env.propose(0, vec![], eta);
+    assert_error(env.veto(&transaction, 2), ErrorCode::VetoDisabled);
+}
+
+#[test]
+fn veto_twice() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    env.veto(&transaction, 3).unwrap();
+    assert_invariant_failed(env.veto(&transaction, 3));
+
+    env.veto(&transaction, 4).unwrap();
+    assert_error(env.veto(&transaction, 2), ErrorCode::TransactionVetoed);
+}
+
+#[test]
+fn veto_by_non_owner() {
+    let (mut env, transaction) = Env::with_approved_transaction();
+    let stranger = Pubkey::new_unique();
+    let payer = env.payer;
+    let smallet = env.smallet;
+    let result = env.process(
+        &[ix::veto(&smallet, &transaction, &stranger, &payer)],
+        &[stranger],
+    );
+    assert_error(result, ErrorCode::InvalidOwner);
+}
+
+#[test]
+fn change_veto_threshold() {
+    let mut env = Env::new(3, 2, 0);
+    let result = env.change_veto_threshold(3);
+    assert!(events(&result).iter().any(|event| matches!(
+        event,
+        SmalletEvent::WalletChangeVetoThreshold(e) if e.veto_threshold == 3
+    )));
+    assert_eq!(env.smallet_account().veto_threshold, 3);
+    assert_error(env.change_veto_threshold(4), ErrorCode::InvalidThreshold);
+
+    // Shrinking the owner set lowers the veto threshold.
+    let owners = env.owners[..2].to_vec();
+    env.set_owners(owners).unwrap();
+    assert_eq!(env.smallet_account().veto_threshold, 2);
+}
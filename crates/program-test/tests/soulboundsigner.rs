@@ -0,0 +1,170 @@
+//! Tests of the soulboundsigner program, run in an in-process bank.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use smallet_program_test::{Account, Bank, ProgramTest, TransactionResult};
+use soulboundsigner::ErrorCode;
+
+/// Program invoked through the soulbound signer: succeeds only if its second
+/// account signed, the first being the program itself.
+fn process_require_signer(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _data: &[u8],
+) -> ProgramResult {
+    let signer = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    msg!("signed by {}", signer.key);
+    Ok(())
+}
+
+struct Env {
+    bank: Bank,
+    payer: Pubkey,
+    target: Pubkey,
+    holder: Pubkey,
+    mint: Pubkey,
+    nft_account: Pubkey,
+    nft_pda: Pubkey,
+    bump: u8,
+}
+
+impl Env {
+    fn new(amount: u64) -> Self {
+        let target = Pubkey::new_unique();
+        let mut bank = ProgramTest::default()
+            .add_program(soulboundsigner::ID, soulboundsigner::entry)
+            .add_program(target, process_require_signer)
+            .start();
+        let payer = Pubkey::new_unique();
+        bank.airdrop(&payer, 1_000_000_000);
+        let holder = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (nft_pda, bump) = Pubkey::find_program_address(
+            &[b"Soulboundsigner", mint.as_ref()],
+            &soulboundsigner::ID,
+        );
+        bank.airdrop(&nft_pda, 1_000_000);
+
+        let mut env = Self {
+            bank,
+            payer,
+            target,
+            holder,
+            mint,
+            nft_account: Pubkey::new_unique(),
+            nft_pda,
+            bump,
+        };
+        env.set_token_account(holder, amount);
+        env
+    }
+
+    fn set_token_account(&mut self, owner: Pubkey, amount: u64) {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: self.mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        self.bank
+            .set_account(self.nft_account, Account::new_data(data, &spl_token::ID));
+    }
+
+    /// Invokes the target program signed by the NFT PDA.
+    ///
+    /// The accounts read their `bump` from the first byte of the instruction
+    /// data, which is the low byte of the length of `data`: the payload is
+    /// sized to match.
+    fn invoke(&mut self, authority: Pubkey, nft_pda: Pubkey) -> TransactionResult {
+        let mut accounts = soulboundsigner::accounts::InvokeSignedInstruction {
+            owner_authority: authority,
+            nft_account: self.nft_account,
+            nft_pda,
+        }
+        .to_account_metas(None);
+        accounts.push(AccountMeta::new_readonly(self.target, false));
+        accounts.push(AccountMeta::new_readonly(nft_pda, false));
+        let ix = Instruction {
+            program_id: soulboundsigner::ID,
+            accounts,
+            data: soulboundsigner::instruction::InvokeSignedInstruction {
+                data: vec![0; self.bump as usize],
+            }
+            .data(),
+        };
+        self.bank
+            .process_transaction(&[ix], &[self.payer, authority])
+    }
+}
+
+#[track_caller]
+fn assert_error(result: TransactionResult, code: impl Into<u32>) {
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.program_error(),
+        Some(&ProgramError::Custom(code.into())),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn invoke_signed_instruction() {
+    let mut env = Env::new(1);
+    let (holder, nft_pda) = (env.holder, env.nft_pda);
+    let logs = env.invoke(holder, nft_pda).unwrap();
+    assert!(logs.contains(&format!("Program log: signed by {}", nft_pda)));
+}
+
+#[test]
+fn invoke_without_token() {
+    let mut env = Env::new(0);
+    let (holder, nft_pda) = (env.holder, env.nft_pda);
+    assert_error(env.invoke(holder, nft_pda), ErrorCode::Unauthorized);
+
+    env.set_token_account(holder, 2);
+    assert_error(env.invoke(holder, nft_pda), ErrorCode::Unauthorized);
+}
+
+#[test]
+fn invoke_by_other_authority() {
+    let mut env = Env::new(1);
+    let nft_pda = env.nft_pda;
+    assert_error(
+        env.invoke(Pubkey::new_unique(), nft_pda),
+        vipers::VipersError::KeyMismatch,
+    );
+}
+
+#[test]
+fn invoke_with_other_pda() {
+    let mut env = Env::new(1);
+    let holder = env.holder;
+    assert_error(
+        env.invoke(holder, Pubkey::new_unique()),
+        anchor_lang::error::ErrorCode::ConstraintSeeds,
+    );
+}
+
+#[test]
+fn invoke_with_other_token_program() {
+    let mut env = Env::new(1);
+    let mut account = env.bank.get_account(&env.nft_account).unwrap();
+    account.owner = Pubkey::new_unique();
+    env.bank.set_account(env.nft_account, account);
+    let (holder, nft_pda) = (env.holder, env.nft_pda);
+    assert_error(
+        env.invoke(holder, nft_pda),
+        anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram,
+    );
+}
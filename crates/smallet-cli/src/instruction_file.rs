@@ -0,0 +1,92 @@
+//! Reading the instructions of a proposal from a file.
+//!
+//! Two formats are supported:
+//!
+//! - `json`: an array of instructions, each with a `programId`, `keys` (each
+//!   with `pubkey`, `isSigner` and `isWritable`) and base64 encoded `data`.
+//! - `base64`: the base64 encoding of the borsh serialized `Vec<TXInstruction>`,
+//!   as stored in a transaction account.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+use smallet_client::smallet::{TXAccountMeta, TXInstruction};
+
+/// Format of an instruction file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ArgEnum)]
+pub enum Format {
+    Json,
+    Base64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonAccountMeta {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonInstruction {
+    program_id: String,
+    keys: Vec<JsonAccountMeta>,
+    data: String,
+}
+
+fn parse_pubkey(s: &str) -> anyhow::Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|_| anyhow!("invalid pubkey {}", s))
+}
+
+impl TryFrom<JsonInstruction> for TXInstruction {
+    type Error = anyhow::Error;
+
+    fn try_from(ix: JsonInstruction) -> anyhow::Result<Self> {
+        Ok(TXInstruction {
+            program_id: parse_pubkey(&ix.program_id)?,
+            keys: ix
+                .keys
+                .iter()
+                .map(|key| {
+                    Ok(TXAccountMeta {
+                        pubkey: parse_pubkey(&key.pubkey)?,
+                        is_signer: key.is_signer,
+                        is_writable: key.is_writable,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?,
+            data: base64::decode(ix.data.trim()).context("invalid instruction data")?,
+        })
+    }
+}
+
+/// Parses the contents of an instruction file.
+pub fn parse(contents: &str, format: Format) -> anyhow::Result<Vec<TXInstruction>> {
+    match format {
+        Format::Json => {
+            let instructions: Vec<JsonInstruction> =
+                serde_json::from_str(contents).context("invalid instruction JSON")?;
+            instructions.into_iter().map(TryInto::try_into).collect()
+        }
+        Format::Base64 => {
+            let data = base64::decode(contents.trim()).context("invalid base64")?;
+            Vec::<TXInstruction>::try_from_slice(&data).context("invalid instructions")
+        }
+    }
+}
+
+/// Reads an instruction file. If no format is given, it is guessed from the
+/// extension of the file.
+pub fn read(path: &Path, format: Option<Format>) -> anyhow::Result<Vec<TXInstruction>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("could not read {}", path.display()))?;
+    let format = format.unwrap_or_else(|| match path.extension() {
+        Some(ext) if ext == "json" => Format::Json,
+        _ => Format::Base64,
+    });
+    parse(&contents, format)
+}
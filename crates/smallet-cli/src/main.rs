@@ -0,0 +1,981 @@
+//! `smallet`: command-line tool for operating smallets.
+
+mod instruction_file;
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Context};
+use clap::{Parser, Subcommand};
+use smallet_client::decode::{
+    describe, describe_post_condition, describe_pre_condition, Labels,
+};
+use smallet_client::instructions as ix;
+use smallet_client::pda;
+use smallet_client::rpc::{Keypair, RpcClient, RpcFilter};
+use smallet_client::smallet::{
+    AccountCheck, AddressBook, OutflowLimit, PdaSigner, PostCondition, PreCondition, Smallet,
+    SubaccountType, TXInstruction, Transaction, TransactionOptions, TransferAsset, VaultTransfer,
+    NO_ETA, NO_INDEX,
+};
+use smallet_client::status::TransactionStatus;
+
+use crate::instruction_file::Format;
+
+#[derive(Parser)]
+#[clap(name = "smallet", version, about = "Operate Cosmic smallets")]
+struct Cli {
+    /// RPC URL, or one of `localnet`, `devnet`, `testnet`, `mainnet-beta`.
+    #[clap(long, short, global = true, env = "SMALLET_RPC_URL", default_value = "localnet")]
+    url: String,
+    /// Keypair signing and paying for transactions.
+    /// Defaults to `~/.config/solana/id.json`.
+    #[clap(long, short, global = true, env = "SMALLET_KEYPAIR")]
+    keypair: Option<PathBuf>,
+    /// Commitment level used for reads and confirmations.
+    #[clap(long, global = true, default_value = "confirmed")]
+    commitment: String,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates a smallet.
+    Create {
+        /// Keypair of the base the smallet is derived from.
+        #[clap(long)]
+        base: PathBuf,
+        /// Owners of the smallet.
+        #[clap(long, required = true, multiple_values = true, use_value_delimiter = true)]
+        owners: Vec<String>,
+        /// Number of approvals needed to execute a transaction.
+        #[clap(long)]
+        threshold: u64,
+        /// Minimum delay between proposal and execution, in seconds.
+        #[clap(long, default_value = "0")]
+        minimum_delay: i64,
+        /// Maximum number of owners. Defaults to the number of owners.
+        #[clap(long)]
+        max_owners: Option<u8>,
+    },
+    /// Shows a smallet.
+    Show { smallet: String },
+    /// Proposes a transaction from an instruction file.
+    Propose {
+        smallet: String,
+        /// JSON or base64 instruction file.
+        file: PathBuf,
+        /// Format of the file. Guessed from the extension if omitted.
+        #[clap(long, arg_enum)]
+        format: Option<Format>,
+        /// Unix timestamp after which the transaction may be executed.
+        #[clap(long)]
+        eta: Option<i64>,
+        /// Nonce of the transaction address. Defaults to the current time.
+        #[clap(long)]
+        nonce: Option<u64>,
+        /// Indices of wallet derived addresses signing the instructions.
+        #[clap(long, multiple_values = true, use_value_delimiter = true)]
+        derived_signers: Vec<u64>,
+        /// Timelock tier of the transaction, by name or index. Without a tier,
+        /// the highest threshold and delay among the tiers apply.
+        #[clap(long)]
+        tier: Option<String>,
+        /// Publishes only the salted hash of the instructions. The file and
+        /// the printed salt must be shared with the owners and passed to
+        /// `execute --reveal`.
+        #[clap(long)]
+        commit: bool,
+        /// Salt of the committed hash, as 64 hex digits. Random if omitted.
+        #[clap(long, requires = "commit")]
+        salt: Option<String>,
+        /// Proposes optimistically: the transaction needs no approvals and
+        /// may be executed after its ETA unless enough owners object. Only
+        /// the instructions allowed by the optimistic mode may be proposed.
+        #[clap(long)]
+        optimistic: bool,
+        /// Indexes of transactions that must be executed before this one.
+        #[clap(long, multiple_values = true, use_value_delimiter = true)]
+        after: Vec<u64>,
+        /// Account state checked before execution, as
+        /// `ACCOUNT:lamports>=N`, `ACCOUNT:amount=MIN..MAX`,
+        /// `ACCOUNT:owner=PROGRAM` or `ACCOUNT:hash[=HEX]`. Without a hex
+        /// hash, the current data of the account is hashed.
+        #[clap(long)]
+        pre_condition: Vec<String>,
+        /// Most that the execution may move out of an account, as
+        /// `ACCOUNT:lamports=N` or `ACCOUNT:tokens=N`.
+        #[clap(long)]
+        max_outflow: Vec<String>,
+        /// Least that an account must hold after the execution, as
+        /// `ACCOUNT:lamports=N` or `ACCOUNT:tokens=N`.
+        #[clap(long)]
+        min_balance: Vec<String>,
+        /// Pays address book entries only, so that the threshold and delay
+        /// of the address book apply.
+        #[clap(long, conflicts_with_all = &["tier", "optimistic"])]
+        address_book: bool,
+    },
+    /// Proposes a transfer of SOL or SPL tokens out of a wallet derived
+    /// address.
+    Transfer {
+        smallet: String,
+        /// Receiver of SOL, or owner of the associated token account
+        /// receiving tokens.
+        recipient: String,
+        /// Amount, in lamports or token base units.
+        amount: u64,
+        /// Index of the wallet derived address paying the transfer.
+        #[clap(long, default_value = "0")]
+        from: u64,
+        /// Mint of the tokens to transfer. Transfers SOL if omitted.
+        #[clap(long)]
+        mint: Option<String>,
+        /// Unix timestamp after which the transaction may be executed.
+        #[clap(long)]
+        eta: Option<i64>,
+        /// Nonce of the transaction address. Defaults to the current time.
+        #[clap(long)]
+        nonce: Option<u64>,
+        /// Passes the address book, so that a transfer to a trusted entry
+        /// uses its threshold and delay.
+        #[clap(long)]
+        address_book: bool,
+    },
+    /// Approves a transaction.
+    Approve {
+        transaction: String,
+        /// Instruction file that a committed transaction must hash to.
+        #[clap(long, requires = "salt")]
+        verify: Option<PathBuf>,
+        /// Salt of the committed hash, as 64 hex digits.
+        #[clap(long)]
+        salt: Option<String>,
+        /// Format of the file. Guessed from the extension if omitted.
+        #[clap(long, arg_enum)]
+        format: Option<Format>,
+    },
+    /// Revokes an approval of a transaction.
+    Unapprove { transaction: String },
+    /// Vetoes a timelocked transaction before its ETA, or objects to an
+    /// optimistic one.
+    Veto { transaction: String },
+    /// Executes a transaction, if it is ready.
+    Execute {
+        transaction: String,
+        /// Executes as the wallet derived address at this index.
+        #[clap(long)]
+        derived: Option<u64>,
+        /// Instruction file revealing the instructions of a committed transaction.
+        #[clap(long, conflicts_with = "derived", requires = "salt")]
+        reveal: Option<PathBuf>,
+        /// Salt of the committed hash, as 64 hex digits.
+        #[clap(long)]
+        salt: Option<String>,
+        /// Format of the file. Guessed from the extension if omitted.
+        #[clap(long, arg_enum)]
+        format: Option<Format>,
+    },
+    /// Lists the transactions of a smallet with their status and instructions.
+    List {
+        smallet: String,
+        /// Also lists executed and stale transactions.
+        #[clap(long)]
+        all: bool,
+    },
+    /// Moves the FIFO cursor of a smallet past its settled transactions.
+    AdvanceFifo { smallet: String },
+    /// Derives program addresses.
+    #[clap(subcommand)]
+    Derive(Derive),
+}
+
+#[derive(Subcommand)]
+enum Derive {
+    /// Derives the smallet of a base.
+    Smallet { base: String },
+    /// Derives a sub-account of a smallet.
+    Subaccount {
+        smallet: String,
+        index: u64,
+        /// Derives the owner invoker instead of the wallet derived address.
+        #[clap(long)]
+        owner_invoker: bool,
+    },
+    /// Derives the address book of a smallet.
+    AddressBook { smallet: String },
+    /// Derives a transaction of a smallet, by index or by proposer and nonce.
+    Transaction {
+        smallet: String,
+        #[clap(long, required_unless_present = "proposer")]
+        index: Option<u64>,
+        #[clap(long, requires = "nonce")]
+        proposer: Option<String>,
+        #[clap(long)]
+        nonce: Option<u64>,
+    },
+}
+
+fn rpc_url(url: &str) -> String {
+    match url {
+        "localnet" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
+
+fn parse_pubkey(s: &str) -> anyhow::Result<Pubkey> {
+    Pubkey::from_str(s).map_err(|_| anyhow!("invalid pubkey {}", s))
+}
+
+fn read_keypair(path: &Path) -> anyhow::Result<Keypair> {
+    Keypair::read_from_file(path).with_context(|| format!("could not read {}", path.display()))
+}
+
+struct Cluster {
+    client: RpcClient,
+    keypair: Option<PathBuf>,
+}
+
+impl Cluster {
+    fn payer(&self) -> anyhow::Result<Keypair> {
+        let path = match &self.keypair {
+            Some(path) => path.clone(),
+            None => {
+                let home = std::env::var("HOME").context("HOME is not set")?;
+                Path::new(&home).join(".config/solana/id.json")
+            }
+        };
+        read_keypair(&path)
+    }
+
+    fn now(&self) -> anyhow::Result<i64> {
+        Ok(self.client.get_clock()?.unix_timestamp)
+    }
+
+    fn smallet(&self, address: &Pubkey) -> anyhow::Result<Smallet> {
+        let account = self
+            .client
+            .get_account(address)?
+            .ok_or_else(|| anyhow!("smallet {} not found", address))?;
+        smallet_client::accounts::decode_smallet(&account.data)
+            .map_err(|e| anyhow!("{} is not a smallet: {}", address, e))
+    }
+
+    fn transaction(&self, address: &Pubkey) -> anyhow::Result<Transaction> {
+        let account = self
+            .client
+            .get_account(address)?
+            .ok_or_else(|| anyhow!("transaction {} not found", address))?;
+        smallet_client::accounts::decode_transaction(&account.data)
+            .map_err(|e| anyhow!("{} is not a transaction: {}", address, e))
+    }
+
+    fn address_book(&self, smallet: &Pubkey) -> anyhow::Result<Option<AddressBook>> {
+        let (address, _) = pda::find_address_book_address(smallet);
+        match self.client.get_account(&address)? {
+            Some(account) => Ok(Some(
+                smallet_client::accounts::decode_address_book(&account.data)
+                    .map_err(|e| anyhow!("{} is not an address book: {}", address, e))?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn transactions(&self, smallet: &Pubkey) -> anyhow::Result<Vec<(Pubkey, Transaction)>> {
+        let accounts = self.client.get_program_accounts(
+            &smallet_client::smallet::ID,
+            &[
+                RpcFilter::discriminator::<Transaction>(),
+                RpcFilter::Memcmp {
+                    offset: Transaction::discriminator().len(),
+                    bytes: smallet.to_bytes().to_vec(),
+                },
+            ],
+        )?;
+        let mut transactions: Vec<_> = accounts
+            .into_iter()
+            .filter_map(|(key, account)| {
+                smallet_client::accounts::decode_transaction(&account.data)
+                    .ok()
+                    .map(|tx| (key, tx))
+            })
+            .collect();
+        transactions.sort_by_key(|(key, tx)| (tx.index, *key));
+        Ok(transactions)
+    }
+
+    fn send(
+        &self,
+        instructions: &[anchor_lang::solana_program::instruction::Instruction],
+        signers: &[&Keypair],
+    ) -> anyhow::Result<()> {
+        let signature = self
+            .client
+            .send_and_confirm_transaction(instructions, signers)?;
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+}
+
+/// Parses a timelock tier of `smallet`, by name or index.
+fn parse_tier(smallet: &Smallet, tier: &str) -> anyhow::Result<u8> {
+    let index = match smallet.tiers.iter().position(|t| t.name == tier) {
+        Some(index) => index,
+        None => tier
+            .parse()
+            .map_err(|_| anyhow!("unknown timelock tier: {}", tier))?,
+    };
+    if index >= smallet.tiers.len() {
+        bail!("unknown timelock tier: {}", tier);
+    }
+    Ok(index as u8)
+}
+
+/// `nonce`, or the current time in milliseconds.
+fn nonce_or_now(nonce: Option<u64>) -> anyhow::Result<u64> {
+    match nonce {
+        Some(nonce) => Ok(nonce),
+        None => Ok(std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_millis() as u64),
+    }
+}
+
+fn format_hash(hash: &[u8; 32]) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn parse_hash(s: &str) -> anyhow::Result<[u8; 32]> {
+    let invalid = || anyhow!("invalid hash {}", s);
+    if s.len() != 64 {
+        return Err(invalid());
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(s.get(2 * i..2 * i + 2).ok_or_else(invalid)?, 16)
+            .map_err(|_| invalid())?;
+    }
+    Ok(hash)
+}
+
+/// Parses a pre-condition given as `ACCOUNT:CHECK`. See `propose --help`.
+fn parse_pre_condition(ctx: &Cluster, spec: &str) -> anyhow::Result<PreCondition> {
+    let invalid = || anyhow!("invalid pre-condition {}", spec);
+    let (account, check) = spec.split_once(':').ok_or_else(invalid)?;
+    let account = parse_pubkey(account)?;
+    let check = if let Some(lamports) = check.strip_prefix("lamports>=") {
+        AccountCheck::MinLamports {
+            lamports: lamports.parse().map_err(|_| invalid())?,
+        }
+    } else if let Some(range) = check.strip_prefix("amount=") {
+        let (min, max) = range.split_once("..").ok_or_else(invalid)?;
+        AccountCheck::TokenAmount {
+            min: min.parse().map_err(|_| invalid())?,
+            max: max.parse().map_err(|_| invalid())?,
+        }
+    } else if let Some(program_id) = check.strip_prefix("owner=") {
+        AccountCheck::Owner {
+            program_id: parse_pubkey(program_id)?,
+        }
+    } else if let Some(hash) = check.strip_prefix("hash=") {
+        AccountCheck::DataHash {
+            hash: parse_hash(hash)?,
+        }
+    } else if check == "hash" {
+        let data = ctx
+            .client
+            .get_account(&account)?
+            .ok_or_else(|| anyhow!("account {} not found", account))?
+            .data;
+        AccountCheck::DataHash {
+            hash: hash(&data).to_bytes(),
+        }
+    } else {
+        return Err(invalid());
+    };
+    Ok(PreCondition { account, check })
+}
+
+/// Parses a post-condition given as `ACCOUNT:lamports=N` or
+/// `ACCOUNT:tokens=N`, an outflow limit or a minimum balance.
+fn parse_post_condition(spec: &str, min_balance: bool) -> anyhow::Result<PostCondition> {
+    let invalid = || anyhow!("invalid post-condition {}", spec);
+    let (account, limit) = spec.split_once(':').ok_or_else(invalid)?;
+    let (kind, amount) = limit.split_once('=').ok_or_else(invalid)?;
+    let amount = amount.parse().map_err(|_| invalid())?;
+    let limit = match (kind, min_balance) {
+        ("lamports", false) => OutflowLimit::Lamports {
+            max_outflow: amount,
+        },
+        ("tokens", false) => OutflowLimit::Tokens {
+            max_outflow: amount,
+        },
+        ("lamports", true) => OutflowLimit::MinLamports {
+            min_balance: amount,
+        },
+        ("tokens", true) => OutflowLimit::MinTokens {
+            min_balance: amount,
+        },
+        _ => return Err(invalid()),
+    };
+    Ok(PostCondition {
+        account: parse_pubkey(account)?,
+        limit,
+    })
+}
+
+/// Checks that `instructions` salted with `salt` are the ones a committed
+/// transaction hashes to.
+fn check_commitment(
+    tx: &Transaction,
+    salt: &[u8; 32],
+    instructions: &[TXInstruction],
+) -> anyhow::Result<()> {
+    let committed = tx
+        .instructions_hash
+        .ok_or_else(|| anyhow!("transaction did not commit to the hash of its instructions"))?;
+    let hash = Transaction::hash_instructions(salt, instructions)?;
+    if hash != committed {
+        bail!(
+            "instructions hash to {}, but the transaction committed to {}",
+            format_hash(&hash),
+            format_hash(&committed)
+        );
+    }
+    Ok(())
+}
+
+fn format_index(index: u64) -> String {
+    if index == NO_INDEX {
+        "-".to_string()
+    } else {
+        index.to_string()
+    }
+}
+
+fn format_eta(eta: i64, now: i64) -> String {
+    if eta == NO_ETA {
+        "none".to_string()
+    } else if eta > now {
+        format!("{} (in {}s)", eta, eta - now)
+    } else {
+        format!("{} ({}s ago)", eta, now - eta)
+    }
+}
+
+fn run(cli: Cli) -> anyhow::Result<()> {
+    let ctx = Cluster {
+        client: RpcClient::new_with_commitment(rpc_url(&cli.url), cli.commitment),
+        keypair: cli.keypair,
+    };
+    match cli.command {
+        Command::Create {
+            base,
+            owners,
+            threshold,
+            minimum_delay,
+            max_owners,
+        } => {
+            let payer = ctx.payer()?;
+            let base = read_keypair(&base)?;
+            let owners = owners
+                .iter()
+                .map(|owner| parse_pubkey(owner))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let max_owners = match max_owners {
+                Some(max_owners) => max_owners,
+                None => u8::try_from(owners.len()).context("too many owners")?,
+            };
+            let (smallet, _) = pda::find_smallet(&base.pubkey());
+            ctx.send(
+                &[ix::create_smallet(
+                    &base.pubkey(),
+                    &payer.pubkey(),
+                    max_owners,
+                    owners,
+                    threshold,
+                    minimum_delay,
+                )],
+                &[&payer, &base],
+            )?;
+            println!("Smallet: {}", smallet);
+        }
+        Command::Show { smallet } => {
+            let key = parse_pubkey(&smallet)?;
+            let smallet = ctx.smallet(&key)?;
+            println!("Smallet: {}", key);
+            println!("Base: {}", smallet.base);
+            println!("Version: {}", smallet.version);
+            println!("Threshold: {}/{}", smallet.threshold, smallet.owners.len());
+            if smallet.veto_threshold > 0 {
+                println!(
+                    "Veto threshold: {}/{}",
+                    smallet.veto_threshold,
+                    smallet.owners.len()
+                );
+            }
+            println!("Minimum delay: {}s", smallet.minimum_delay);
+            println!("Grace period: {}s", smallet.grace_period);
+            if smallet.approval_ttl > 0 {
+                println!("Approval TTL: {}s", smallet.approval_ttl);
+            }
+            println!("Owner set seqno: {}", smallet.owner_set_seqno);
+            println!("Transactions: {}", smallet.num_transactions);
+            if let Some(cursor) = smallet.fifo_cursor {
+                println!("FIFO cursor: {}", cursor);
+            }
+            println!("Sub-accounts: {}", smallet.num_subaccounts);
+            println!("Owners:");
+            for owner in &smallet.owners {
+                println!("  {}", owner);
+            }
+            if let Some(config) = &smallet.optimistic {
+                println!(
+                    "Optimistic mode: objection threshold {}/{}, challenge period {}s, {} allowed instructions",
+                    config.objection_threshold,
+                    smallet.owners.len(),
+                    config.challenge_period,
+                    config.allowed_instructions.len()
+                );
+                println!("Optimistic proposers:");
+                for proposer in &config.proposers {
+                    println!("  {}", proposer);
+                }
+            }
+            if !smallet.tiers.is_empty() {
+                println!("Timelock tiers:");
+            }
+            for (index, tier) in smallet.tiers.iter().enumerate() {
+                println!(
+                    "  #{} {}: threshold {}/{}, minimum delay {}s, {}",
+                    index,
+                    tier.name,
+                    tier.threshold,
+                    smallet.owners.len(),
+                    tier.minimum_delay,
+                    match tier.allowed_instructions.len() {
+                        0 => "any instruction".to_string(),
+                        n => format!("{} allowed instructions", n),
+                    }
+                );
+            }
+            if let Some(address_book) = ctx.address_book(&key)? {
+                println!(
+                    "Address book: threshold {}/{}, minimum delay {}s, entry delay {}s",
+                    address_book.threshold,
+                    smallet.owners.len(),
+                    address_book.minimum_delay,
+                    address_book.entry_delay
+                );
+                let now = ctx.now()?;
+                for entry in &address_book.entries {
+                    println!(
+                        "  {} {:?}{}",
+                        entry.recipient,
+                        entry.label,
+                        if entry.trusted_at > now {
+                            format!(" (trusted from {})", entry.trusted_at)
+                        } else {
+                            String::new()
+                        }
+                    );
+                }
+            }
+        }
+        Command::Propose {
+            smallet,
+            file,
+            format,
+            eta,
+            nonce,
+            derived_signers,
+            tier,
+            commit,
+            salt,
+            optimistic,
+            after,
+            pre_condition,
+            max_outflow,
+            min_balance,
+            address_book,
+        } => {
+            let payer = ctx.payer()?;
+            let smallet = parse_pubkey(&smallet)?;
+            let tier = match tier {
+                Some(tier) => Some(parse_tier(&ctx.smallet(&smallet)?, &tier)?),
+                None => None,
+            };
+            let instructions = instruction_file::read(&file, format)?;
+            let nonce = nonce_or_now(nonce)?;
+            let pda_signers = derived_signers
+                .iter()
+                .map(|&index| {
+                    let (_, bump) = pda::find_wallet_derived_address(&smallet, index);
+                    PdaSigner::Derived { index, bump }
+                })
+                .collect();
+            let (transaction, _) =
+                pda::find_transaction_v2_address(&smallet, &payer.pubkey(), nonce);
+            let options = TransactionOptions {
+                pda_signers,
+                tier,
+                optimistic,
+                prerequisites: after,
+                pre_conditions: pre_condition
+                    .iter()
+                    .map(|spec| parse_pre_condition(&ctx, spec))
+                    .collect::<anyhow::Result<_>>()?,
+                post_conditions: max_outflow
+                    .iter()
+                    .map(|spec| parse_post_condition(spec, false))
+                    .chain(
+                        min_balance
+                            .iter()
+                            .map(|spec| parse_post_condition(spec, true)),
+                    )
+                    .collect::<anyhow::Result<_>>()?,
+                address_book,
+            };
+            let salt = match salt {
+                Some(salt) => parse_hash(&salt)?,
+                None => rand::random(),
+            };
+            let mut instruction = if commit {
+                ix::create_committed_transaction(
+                    &smallet,
+                    &payer.pubkey(),
+                    &payer.pubkey(),
+                    nonce,
+                    &salt,
+                    &instructions,
+                    eta.unwrap_or(NO_ETA),
+                    options,
+                )
+            } else {
+                ix::create_transaction_v2(
+                    &smallet,
+                    &payer.pubkey(),
+                    &payer.pubkey(),
+                    nonce,
+                    instructions,
+                    eta.unwrap_or(NO_ETA),
+                    options,
+                )
+            };
+            if address_book {
+                instruction
+                    .accounts
+                    .push(ix::address_book_account(&smallet));
+            }
+            ctx.send(&[instruction], &[&payer])?;
+            println!("Transaction: {}", transaction);
+            if commit {
+                println!("Salt: {}", format_hash(&salt));
+            }
+        }
+        Command::Transfer {
+            smallet,
+            recipient,
+            amount,
+            from,
+            mint,
+            eta,
+            nonce,
+            address_book,
+        } => {
+            let payer = ctx.payer()?;
+            let smallet = parse_pubkey(&smallet)?;
+            let asset = match mint {
+                Some(mint) => TransferAsset::Token {
+                    mint: parse_pubkey(&mint)?,
+                },
+                None => TransferAsset::Sol,
+            };
+            let transfer = VaultTransfer {
+                asset,
+                amount,
+                source_index: from,
+                recipient: parse_pubkey(&recipient)?,
+            };
+            let nonce = nonce_or_now(nonce)?;
+            let (transaction, _) =
+                pda::find_transaction_v2_address(&smallet, &payer.pubkey(), nonce);
+            let mut instruction = ix::propose_transfer(
+                &smallet,
+                &payer.pubkey(),
+                &payer.pubkey(),
+                nonce,
+                transfer,
+                eta.unwrap_or(NO_ETA),
+            );
+            if address_book {
+                instruction
+                    .accounts
+                    .push(ix::address_book_account(&smallet));
+            }
+            ctx.send(&[instruction], &[&payer])?;
+            println!("Transaction: {}", transaction);
+        }
+        Command::Approve {
+            transaction,
+            verify,
+            salt,
+            format,
+        } => {
+            let owner = ctx.payer()?;
+            let transaction = parse_pubkey(&transaction)?;
+            let tx = ctx.transaction(&transaction)?;
+            if let (Some(file), Some(salt)) = (verify, salt) {
+                check_commitment(
+                    &tx,
+                    &parse_hash(&salt)?,
+                    &instruction_file::read(&file, format)?,
+                )?;
+            }
+            ctx.send(
+                &[ix::approve(&tx.smallet, &transaction, &owner.pubkey())],
+                &[&owner],
+            )?;
+        }
+        Command::Unapprove { transaction } => {
+            let owner = ctx.payer()?;
+            let transaction = parse_pubkey(&transaction)?;
+            let tx = ctx.transaction(&transaction)?;
+            ctx.send(
+                &[ix::unapprove(&tx.smallet, &transaction, &owner.pubkey())],
+                &[&owner],
+            )?;
+        }
+        Command::Veto { transaction } => {
+            let owner = ctx.payer()?;
+            let transaction = parse_pubkey(&transaction)?;
+            let tx = ctx.transaction(&transaction)?;
+            ctx.send(
+                &[ix::veto(
+                    &tx.smallet,
+                    &transaction,
+                    &owner.pubkey(),
+                    &owner.pubkey(),
+                )],
+                &[&owner],
+            )?;
+        }
+        Command::Execute {
+            transaction,
+            derived,
+            reveal,
+            salt,
+            format,
+        } => {
+            let owner = ctx.payer()?;
+            let transaction = parse_pubkey(&transaction)?;
+            let tx = ctx.transaction(&transaction)?;
+            let smallet = ctx.smallet(&tx.smallet)?;
+            let address_book = ctx.address_book(&tx.smallet)?;
+            let status = TransactionStatus::of(&smallet, address_book.as_ref(), &tx, ctx.now()?);
+            if status != TransactionStatus::Ready {
+                bail!("transaction {} is not ready: {}", transaction, status);
+            }
+            let mut instruction = match (derived, reveal.zip(salt)) {
+                (_, Some((file, salt))) => {
+                    let salt = parse_hash(&salt)?;
+                    let instructions = instruction_file::read(&file, format)?;
+                    check_commitment(&tx, &salt, &instructions)?;
+                    ix::execute_committed_transaction(
+                        &tx.smallet,
+                        &transaction,
+                        &tx,
+                        &owner.pubkey(),
+                        salt,
+                        instructions,
+                    )
+                }
+                (Some(index), None) => ix::execute_transaction_derived(
+                    &tx.smallet,
+                    &transaction,
+                    &tx,
+                    &owner.pubkey(),
+                    index,
+                ),
+                (None, None) => {
+                    ix::execute_transaction(&tx.smallet, &transaction, &tx, &owner.pubkey())
+                }
+            };
+            let mut earlier = tx.prerequisites.clone();
+            if let Some(cursor) = smallet.fifo_cursor {
+                if tx.index == NO_INDEX {
+                    bail!("transaction {} must be assigned an index first", transaction);
+                }
+                earlier.extend(cursor..tx.index);
+            }
+            if !earlier.is_empty() {
+                let transactions = ctx.transactions(&tx.smallet)?;
+                let addresses = earlier
+                    .iter()
+                    .map(|&index| {
+                        transactions
+                            .iter()
+                            .find(|(_, earlier)| earlier.index == index)
+                            .map(|(address, _)| *address)
+                            .ok_or_else(|| anyhow!("transaction #{} not found", index))
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                instruction
+                    .accounts
+                    .extend(ix::transaction_accounts(&addresses));
+                // Earlier payments of address book entries are checked
+                // against its threshold.
+                let pays_address_book = transactions.iter().any(|(_, earlier_tx)| {
+                    earlier_tx.address_book && earlier.contains(&earlier_tx.index)
+                });
+                if pays_address_book && !tx.address_book {
+                    instruction
+                        .accounts
+                        .push(ix::address_book_account(&tx.smallet));
+                }
+            }
+            ctx.send(&[instruction], &[&owner])?;
+        }
+        Command::List { smallet, all } => {
+            let key = parse_pubkey(&smallet)?;
+            let smallet = ctx.smallet(&key)?;
+            let now = ctx.now()?;
+            let labels = Labels::for_smallet(&key, &smallet);
+            let address_book = ctx.address_book(&key)?;
+            for (address, tx) in ctx.transactions(&key)? {
+                let status = TransactionStatus::of(&smallet, address_book.as_ref(), &tx, now);
+                if !all && !status.is_pending() {
+                    continue;
+                }
+                println!("{}", address);
+                println!("  index:     {}", format_index(tx.index));
+                println!("  proposer:  {}", tx.proposer);
+                println!("  eta:       {}", format_eta(tx.eta, now));
+                if let Some(tier) = tx.tier {
+                    let name = smallet.tiers.get(usize::from(tier)).map(|t| t.name.as_str());
+                    println!("  tier:      #{} {}", tier, name.unwrap_or("(removed)"));
+                }
+                if smallet.is_optimistic(&tx) {
+                    println!("  approvals: not needed (optimistic)");
+                } else {
+                    let fresh = tx.num_fresh_signers(smallet.approval_ttl, now);
+                    let expired = tx.num_signers() - fresh;
+                    println!(
+                        "  approvals: {}/{}{}",
+                        fresh,
+                        match (&address_book, tx.address_book) {
+                            (Some(address_book), true) => address_book.threshold,
+                            _ => smallet.threshold_for(tx.tier).unwrap_or(smallet.threshold),
+                        },
+                        match expired {
+                            0 => String::new(),
+                            n => format!(" ({} expired)", n),
+                        }
+                    );
+                }
+                let veto_threshold = smallet.veto_threshold_for(&tx);
+                if veto_threshold > 0 {
+                    let vetoes = tx.vetoes.iter().filter(|&vetoed| *vetoed).count();
+                    println!("  vetoes:    {}/{}", vetoes, veto_threshold);
+                }
+                println!("  status:    {}", status);
+                if !tx.prerequisites.is_empty() {
+                    let after: Vec<String> =
+                        tx.prerequisites.iter().map(|index| format!("#{}", index)).collect();
+                    println!("  after:     {}", after.join(", "));
+                }
+                for pre_condition in tx.pre_conditions.iter() {
+                    println!(
+                        "  requires:  {}",
+                        describe_pre_condition(pre_condition, &labels)
+                    );
+                }
+                for post_condition in tx.post_conditions.iter() {
+                    println!(
+                        "  limits:    {}",
+                        describe_post_condition(post_condition, &labels)
+                    );
+                }
+                if tx.address_book {
+                    println!("  pays:      address book entries");
+                }
+                if let Some(hash) = tx.instructions_hash {
+                    println!("  committed: {}", format_hash(&hash));
+                }
+                for (i, instruction) in tx.instructions.iter().enumerate() {
+                    println!("  #{}: {}", i, describe(instruction, &labels));
+                }
+            }
+        }
+        Command::AdvanceFifo { smallet } => {
+            let payer = ctx.payer()?;
+            let key = parse_pubkey(&smallet)?;
+            let cursor = match ctx.smallet(&key)?.fifo_cursor {
+                Some(cursor) => cursor,
+                None => bail!("smallet {} does not execute in FIFO order", key),
+            };
+            let transactions: Vec<Pubkey> = ctx
+                .transactions(&key)?
+                .into_iter()
+                .filter(|(_, tx)| tx.index >= cursor && tx.index != NO_INDEX)
+                .map(|(address, _)| address)
+                .collect();
+            ctx.send(&[ix::advance_fifo_cursor(&key, &transactions)], &[&payer])?;
+        }
+        Command::Derive(derive) => match derive {
+            Derive::Smallet { base } => {
+                let (address, bump) = pda::find_smallet(&parse_pubkey(&base)?);
+                println!("{} (bump {})", address, bump);
+            }
+            Derive::Subaccount {
+                smallet,
+                index,
+                owner_invoker,
+            } => {
+                let subaccount_type = if owner_invoker {
+                    SubaccountType::OwnerInvoker
+                } else {
+                    SubaccountType::Derived
+                };
+                let (address, bump) = subaccount_type.find_address(&parse_pubkey(&smallet)?, index);
+                println!("{} (bump {})", address, bump);
+            }
+            Derive::AddressBook { smallet } => {
+                let (address, bump) = pda::find_address_book_address(&parse_pubkey(&smallet)?);
+                println!("{} (bump {})", address, bump);
+            }
+            Derive::Transaction {
+                smallet,
+                index,
+                proposer,
+                nonce,
+            } => {
+                let smallet = parse_pubkey(&smallet)?;
+                let (address, bump) = match (index, proposer, nonce) {
+                    (_, Some(proposer), Some(nonce)) => {
+                        pda::find_transaction_v2_address(&smallet, &parse_pubkey(&proposer)?, nonce)
+                    }
+                    (Some(index), _, _) => pda::find_transaction_address(&smallet, index),
+                    _ => bail!("either --index or --proposer and --nonce is required"),
+                };
+                println!("{} (bump {})", address, bump);
+            }
+        },
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("Error: {:#}", err);
+        std::process::exit(1);
+    }
+}
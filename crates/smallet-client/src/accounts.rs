@@ -0,0 +1,36 @@
+//! Decoders for the accounts of the smallet program.
+
+use anchor_lang::prelude::*;
+use smallet::{
+    AddressBook, InvokerPolicy, Smallet, SubaccountInfo, SubaccountRegistryEntry, Transaction,
+};
+
+/// Decodes a [Smallet] in either the legacy or the current layout.
+pub fn decode_smallet(data: &[u8]) -> Result<Smallet> {
+    Smallet::try_deserialize_versioned(data)
+}
+
+/// Decodes a [Transaction] in either the legacy or the current layout.
+pub fn decode_transaction(data: &[u8]) -> Result<Transaction> {
+    Transaction::try_deserialize_versioned(data)
+}
+
+/// Decodes a [SubaccountInfo] in either the legacy or the current layout.
+pub fn decode_subaccount_info(data: &[u8]) -> Result<SubaccountInfo> {
+    SubaccountInfo::try_deserialize_versioned(data)
+}
+
+/// Decodes a [SubaccountRegistryEntry].
+pub fn decode_subaccount_registry_entry(data: &[u8]) -> Result<SubaccountRegistryEntry> {
+    SubaccountRegistryEntry::try_deserialize(&mut &data[..])
+}
+
+/// Decodes an [InvokerPolicy].
+pub fn decode_invoker_policy(data: &[u8]) -> Result<InvokerPolicy> {
+    InvokerPolicy::try_deserialize(&mut &data[..])
+}
+
+/// Decodes an [AddressBook].
+pub fn decode_address_book(data: &[u8]) -> Result<AddressBook> {
+    AddressBook::try_deserialize(&mut &data[..])
+}
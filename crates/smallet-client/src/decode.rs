@@ -0,0 +1,857 @@
+//! Human-readable rendering of the [TXInstruction]s of a [smallet::Transaction].
+//!
+//! Instructions of the System, SPL Token, Associated Token Account, BPF
+//! upgradeable loader and Memo programs are decoded, as well as the smallet's
+//! own `set_owners` and `change_threshold`. Addresses are rendered with the
+//! names given to a [Labels], e.g. `transfer 500 USDC from derived #2 to
+//! 9xQe...`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable, loader_upgradeable_instruction::UpgradeableLoaderInstruction,
+    program_option::COption, system_instruction::SystemInstruction, system_program, sysvar,
+};
+use anchor_lang::InstructionData;
+use smallet::{AccountCheck, OutflowLimit, PostCondition, PreCondition, Smallet, TXInstruction};
+use spl_token::instruction::{AuthorityType, TokenInstruction};
+
+use crate::pda::{find_owner_invoker_address, find_wallet_derived_address};
+
+/// The Associated Token Account program.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// The SPL Memo program.
+pub const MEMO_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// The first version of the SPL Memo program.
+pub const MEMO_V1_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+/// Number of wallet derived addresses and owner invokers named by
+/// [Labels::for_smallet].
+pub const SUBACCOUNT_LABELS: u64 = 16;
+
+/// Decimals of SOL.
+const SOL_DECIMALS: u8 = 9;
+
+/// A mint, as rendered in token amounts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintInfo {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// A token account, as rendered in token instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenAccountInfo {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Names of the addresses, mints and token accounts known to the reader.
+#[derive(Clone, Debug)]
+pub struct Labels {
+    names: BTreeMap<Pubkey, String>,
+    mints: BTreeMap<Pubkey, MintInfo>,
+    token_accounts: BTreeMap<Pubkey, TokenAccountInfo>,
+}
+
+impl Default for Labels {
+    /// Labels naming well-known programs and sysvars.
+    fn default() -> Self {
+        let names = [
+            (system_program::ID, "System Program"),
+            (spl_token::ID, "Token Program"),
+            (ASSOCIATED_TOKEN_PROGRAM_ID, "Associated Token Program"),
+            (bpf_loader_upgradeable::ID, "BPF Upgradeable Loader"),
+            (MEMO_PROGRAM_ID, "Memo Program"),
+            (MEMO_V1_PROGRAM_ID, "Memo Program v1"),
+            (smallet::ID, "Smallet Program"),
+            (sysvar::rent::ID, "Rent Sysvar"),
+            (sysvar::clock::ID, "Clock Sysvar"),
+            (sysvar::recent_blockhashes::ID, "Recent Blockhashes Sysvar"),
+        ];
+        Self {
+            names: names
+                .iter()
+                .map(|(address, name)| (*address, name.to_string()))
+                .collect(),
+            mints: BTreeMap::new(),
+            token_accounts: BTreeMap::new(),
+        }
+    }
+}
+
+impl Labels {
+    /// Labels naming a smallet and its first [SUBACCOUNT_LABELS]
+    /// sub-accounts of each type.
+    pub fn for_smallet_address(address: &Pubkey) -> Self {
+        let mut labels = Self::default().with_name(*address, "smallet");
+        for index in 0..SUBACCOUNT_LABELS {
+            let (derived, _) = find_wallet_derived_address(address, index);
+            let (invoker, _) = find_owner_invoker_address(address, index);
+            labels = labels
+                .with_name(derived, format!("derived #{}", index))
+                .with_name(invoker, format!("invoker #{}", index));
+        }
+        labels
+    }
+
+    /// Labels naming a smallet, its owners and its first
+    /// [SUBACCOUNT_LABELS] sub-accounts of each type.
+    pub fn for_smallet(address: &Pubkey, smallet: &Smallet) -> Self {
+        let mut labels = Self::for_smallet_address(address);
+        for (i, owner) in smallet.owners.iter().enumerate() {
+            labels = labels.with_name(*owner, format!("owner #{}", i));
+        }
+        labels
+    }
+
+    /// Names an address.
+    pub fn with_name(mut self, address: Pubkey, name: impl Into<String>) -> Self {
+        self.names.insert(address, name.into());
+        self
+    }
+
+    /// Describes a mint, so that its amounts are rendered in its units.
+    pub fn with_mint(mut self, mint: Pubkey, symbol: impl Into<String>, decimals: u8) -> Self {
+        self.mints.insert(
+            mint,
+            MintInfo {
+                symbol: symbol.into(),
+                decimals,
+            },
+        );
+        self
+    }
+
+    /// Describes a token account, so that it is rendered as its owner.
+    pub fn with_token_account(mut self, address: Pubkey, mint: Pubkey, owner: Pubkey) -> Self {
+        self.token_accounts
+            .insert(address, TokenAccountInfo { mint, owner });
+        self
+    }
+
+    /// Renders an address: its name, the name of the owner of the token
+    /// account, or the address itself.
+    pub fn address(&self, address: &Pubkey) -> String {
+        if let Some(name) = self.names.get(address) {
+            return name.clone();
+        }
+        match self.token_accounts.get(address) {
+            Some(account) => match self.names.get(&account.owner) {
+                Some(owner) => owner.clone(),
+                None => format!("token account of {}", account.owner),
+            },
+            None => address.to_string(),
+        }
+    }
+
+    /// Renders an amount of lamports in SOL.
+    pub fn lamports(&self, lamports: u64) -> String {
+        format!("{} SOL", format_units(lamports, SOL_DECIMALS))
+    }
+
+    /// Renders a token amount, in the units of `mint` if it is known.
+    pub fn tokens(&self, mint: Option<&Pubkey>, amount: u64, decimals: Option<u8>) -> String {
+        match mint.and_then(|mint| self.mints.get(mint)) {
+            Some(info) => format!(
+                "{} {}",
+                format_units(amount, decimals.unwrap_or(info.decimals)),
+                info.symbol
+            ),
+            None => match decimals {
+                Some(decimals) => format!("{} tokens", format_units(amount, decimals)),
+                None => format!("{} base units", amount),
+            },
+        }
+    }
+
+    fn token_mint(&self, token_account: &Pubkey) -> Option<&Pubkey> {
+        self.token_accounts
+            .get(token_account)
+            .map(|account| &account.mint)
+    }
+
+    fn optional(&self, address: &COption<Pubkey>) -> String {
+        match address {
+            COption::Some(address) => self.address(address),
+            COption::None => "none".to_string(),
+        }
+    }
+}
+
+/// Renders `amount` with `decimals` decimals, without trailing zeroes.
+fn format_units(amount: u64, decimals: u8) -> String {
+    let unit = 10u128.pow(u32::from(decimals));
+    let whole = u128::from(amount) / unit;
+    let fraction = u128::from(amount) % unit;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:0width$}", fraction, width = usize::from(decimals));
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// An account of a decoded instruction, with its role.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountRole {
+    pub role: &'static str,
+    pub address: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+/// A decoded instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    /// Name of the program.
+    pub program: &'static str,
+    /// Name of the instruction.
+    pub name: &'static str,
+    /// One line description of what the instruction does.
+    pub summary: String,
+    /// The accounts of the instruction, with their roles.
+    pub accounts: Vec<AccountRole>,
+}
+
+impl fmt::Display for DecodedInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.summary)
+    }
+}
+
+/// Names the accounts of `ix` by `roles`. Accounts past the roles are named
+/// `extra`.
+fn roles(ix: &TXInstruction, roles: &[&'static str], extra: &'static str) -> Vec<AccountRole> {
+    ix.keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| AccountRole {
+            role: roles.get(i).copied().unwrap_or(extra),
+            address: key.pubkey,
+            is_signer: key.is_signer,
+            is_writable: key.is_writable,
+        })
+        .collect()
+}
+
+/// Address of the account at `index`, rendered.
+fn account(ix: &TXInstruction, labels: &Labels, index: usize) -> String {
+    match ix.keys.get(index) {
+        Some(key) => labels.address(&key.pubkey),
+        None => "?".to_string(),
+    }
+}
+
+fn key(ix: &TXInstruction, index: usize) -> Option<&Pubkey> {
+    ix.keys.get(index).map(|key| &key.pubkey)
+}
+
+/// Decodes an instruction of a known program.
+pub fn decode(ix: &TXInstruction, labels: &Labels) -> Option<DecodedInstruction> {
+    let (program, name, summary, account_roles, extra) = if ix.program_id == system_program::ID {
+        decode_system(ix, labels)?
+    } else if ix.program_id == spl_token::ID {
+        decode_token(ix, labels)?
+    } else if ix.program_id == ASSOCIATED_TOKEN_PROGRAM_ID {
+        decode_associated_token(ix, labels)?
+    } else if ix.program_id == bpf_loader_upgradeable::ID {
+        decode_upgradeable_loader(ix, labels)?
+    } else if ix.program_id == MEMO_PROGRAM_ID || ix.program_id == MEMO_V1_PROGRAM_ID {
+        let memo = std::str::from_utf8(&ix.data).ok()?;
+        (
+            "Memo",
+            "memo",
+            format!("memo {:?}", memo),
+            &[][..],
+            "signer",
+        )
+    } else if ix.program_id == smallet::ID {
+        decode_smallet(ix, labels)?
+    } else {
+        return None;
+    };
+    Some(DecodedInstruction {
+        program,
+        name,
+        summary,
+        accounts: roles(ix, account_roles, extra),
+    })
+}
+
+/// Describes an instruction: its summary if it is decoded, otherwise its
+/// program and data in hex.
+pub fn describe(ix: &TXInstruction, labels: &Labels) -> String {
+    match decode(ix, labels) {
+        Some(decoded) => decoded.summary,
+        None => {
+            let data: String = ix.data.iter().map(|byte| format!("{:02x}", byte)).collect();
+            format!(
+                "unknown instruction of {} with data 0x{}",
+                labels.address(&ix.program_id),
+                data
+            )
+        }
+    }
+}
+
+/// Describes a pre-condition of a transaction, e.g. `derived #0 holds at
+/// least 1 SOL`.
+pub fn describe_pre_condition(pre_condition: &PreCondition, labels: &Labels) -> String {
+    let account = labels.address(&pre_condition.account);
+    match pre_condition.check {
+        AccountCheck::DataHash { hash } => {
+            let hash: String = hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+            format!("data of {} hashes to {}", account, hash)
+        }
+        AccountCheck::MinLamports { lamports } => {
+            format!("{} holds at least {}", account, labels.lamports(lamports))
+        }
+        AccountCheck::TokenAmount { min, max } => {
+            let mint = labels.token_mint(&pre_condition.account);
+            format!(
+                "{} holds between {} and {}",
+                account,
+                labels.tokens(mint, min, None),
+                labels.tokens(mint, max, None)
+            )
+        }
+        AccountCheck::Owner { program_id } => {
+            format!("{} is owned by {}", account, labels.address(&program_id))
+        }
+    }
+}
+
+/// Describes a post-condition of a transaction, e.g. `at most 1 SOL leaves
+/// derived #0` or `derived #0 keeps at least 1 SOL`.
+pub fn describe_post_condition(post_condition: &PostCondition, labels: &Labels) -> String {
+    let account = labels.address(&post_condition.account);
+    let tokens = |amount| {
+        let mint = labels.token_mint(&post_condition.account);
+        labels.tokens(mint, amount, None)
+    };
+    match post_condition.limit {
+        OutflowLimit::Lamports { max_outflow } => {
+            format!("at most {} leaves {}", labels.lamports(max_outflow), account)
+        }
+        OutflowLimit::Tokens { max_outflow } => {
+            format!("at most {} leaves {}", tokens(max_outflow), account)
+        }
+        OutflowLimit::MinLamports { min_balance } => {
+            format!("{} keeps at least {}", account, labels.lamports(min_balance))
+        }
+        OutflowLimit::MinTokens { min_balance } => {
+            format!("{} keeps at least {}", account, tokens(min_balance))
+        }
+    }
+}
+
+type Decoded = (
+    &'static str,
+    &'static str,
+    String,
+    &'static [&'static str],
+    &'static str,
+);
+
+fn decode_system(ix: &TXInstruction, labels: &Labels) -> Option<Decoded> {
+    let instruction: SystemInstruction = bincode::deserialize(&ix.data).ok()?;
+    let a = |index| account(ix, labels, index);
+    let (name, summary, roles): (_, _, &'static [&'static str]) = match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => (
+            "create_account",
+            format!(
+                "create account {} with {} and {} bytes, owned by {}, funded by {}",
+                a(1),
+                labels.lamports(lamports),
+                space,
+                labels.address(&owner),
+                a(0)
+            ),
+            &["funder", "new account"],
+        ),
+        SystemInstruction::Assign { owner } => (
+            "assign",
+            format!("assign {} to {}", a(0), labels.address(&owner)),
+            &["account"],
+        ),
+        SystemInstruction::Transfer { lamports } => (
+            "transfer",
+            format!(
+                "transfer {} from {} to {}",
+                labels.lamports(lamports),
+                a(0),
+                a(1)
+            ),
+            &["source", "destination"],
+        ),
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => (
+            "create_account_with_seed",
+            format!(
+                "create account {} from seed {:?} of {} with {} and {} bytes, owned by {}, funded by {}",
+                a(1),
+                seed,
+                labels.address(&base),
+                labels.lamports(lamports),
+                space,
+                labels.address(&owner),
+                a(0)
+            ),
+            &["funder", "new account", "base"],
+        ),
+        SystemInstruction::AdvanceNonceAccount => (
+            "advance_nonce_account",
+            format!("advance nonce account {}", a(0)),
+            &["nonce account", "recent blockhashes sysvar", "nonce authority"],
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => (
+            "withdraw_nonce_account",
+            format!(
+                "withdraw {} from nonce account {} to {}",
+                labels.lamports(lamports),
+                a(0),
+                a(1)
+            ),
+            &[
+                "nonce account",
+                "destination",
+                "recent blockhashes sysvar",
+                "rent sysvar",
+                "nonce authority",
+            ],
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => (
+            "initialize_nonce_account",
+            format!(
+                "initialize nonce account {} with authority {}",
+                a(0),
+                labels.address(&authority)
+            ),
+            &["nonce account", "recent blockhashes sysvar", "rent sysvar"],
+        ),
+        SystemInstruction::AuthorizeNonceAccount(authority) => (
+            "authorize_nonce_account",
+            format!(
+                "set authority of nonce account {} to {}",
+                a(0),
+                labels.address(&authority)
+            ),
+            &["nonce account", "nonce authority"],
+        ),
+        SystemInstruction::UpgradeNonceAccount => (
+            "upgrade_nonce_account",
+            format!("upgrade nonce account {}", a(0)),
+            &["nonce account"],
+        ),
+        SystemInstruction::Allocate { space } => (
+            "allocate",
+            format!("allocate {} bytes to {}", space, a(0)),
+            &["account"],
+        ),
+        SystemInstruction::AllocateWithSeed {
+            base,
+            seed,
+            space,
+            owner,
+        } => (
+            "allocate_with_seed",
+            format!(
+                "allocate {} bytes to {} from seed {:?} of {}, owned by {}",
+                space,
+                a(0),
+                seed,
+                labels.address(&base),
+                labels.address(&owner)
+            ),
+            &["account", "base"],
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => (
+            "assign_with_seed",
+            format!(
+                "assign {} from seed {:?} of {} to {}",
+                a(0),
+                seed,
+                labels.address(&base),
+                labels.address(&owner)
+            ),
+            &["account", "base"],
+        ),
+        SystemInstruction::TransferWithSeed {
+            lamports,
+            from_seed,
+            from_owner,
+        } => (
+            "transfer_with_seed",
+            format!(
+                "transfer {} from {} (seed {:?}, owned by {}) to {}",
+                labels.lamports(lamports),
+                a(0),
+                from_seed,
+                labels.address(&from_owner),
+                a(2)
+            ),
+            &["source", "base", "destination"],
+        ),
+    };
+    Some(("System", name, summary, roles, "account"))
+}
+
+fn authority_type(authority_type: &AuthorityType) -> &'static str {
+    match authority_type {
+        AuthorityType::MintTokens => "mint authority",
+        AuthorityType::FreezeAccount => "freeze authority",
+        AuthorityType::AccountOwner => "owner",
+        AuthorityType::CloseAccount => "close authority",
+    }
+}
+
+fn decode_token(ix: &TXInstruction, labels: &Labels) -> Option<Decoded> {
+    let instruction = TokenInstruction::unpack(&ix.data).ok()?;
+    let a = |index| account(ix, labels, index);
+    let (name, summary, roles): (_, _, &'static [&'static str]) = match instruction {
+        TokenInstruction::InitializeMint {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        }
+        | TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        } => (
+            "initialize_mint",
+            format!(
+                "initialize mint {} with {} decimals, mint authority {} and freeze authority {}",
+                a(0),
+                decimals,
+                labels.address(&mint_authority),
+                labels.optional(&freeze_authority)
+            ),
+            &["mint", "rent sysvar"],
+        ),
+        TokenInstruction::InitializeAccount => (
+            "initialize_account",
+            format!("initialize token account {} of {} for {}", a(0), a(1), a(2)),
+            &["account", "mint", "owner", "rent sysvar"],
+        ),
+        TokenInstruction::InitializeAccount2 { owner }
+        | TokenInstruction::InitializeAccount3 { owner } => (
+            "initialize_account",
+            format!(
+                "initialize token account {} of {} for {}",
+                a(0),
+                a(1),
+                labels.address(&owner)
+            ),
+            &["account", "mint", "rent sysvar"],
+        ),
+        TokenInstruction::InitializeMultisig { m }
+        | TokenInstruction::InitializeMultisig2 { m } => (
+            "initialize_multisig",
+            format!("initialize token multisig {} requiring {} signers", a(0), m),
+            &["multisig"],
+        ),
+        TokenInstruction::Transfer { amount } => (
+            "transfer",
+            format!(
+                "transfer {} from {} to {}",
+                labels.tokens(key(ix, 0).and_then(|k| labels.token_mint(k)), amount, None),
+                a(0),
+                a(1)
+            ),
+            &["source", "destination", "owner"],
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => (
+            "transfer_checked",
+            format!(
+                "transfer {} from {} to {}",
+                labels.tokens(key(ix, 1), amount, Some(decimals)),
+                a(0),
+                a(2)
+            ),
+            &["source", "mint", "destination", "owner"],
+        ),
+        TokenInstruction::Approve { amount } => (
+            "approve",
+            format!(
+                "approve {} to spend {} from {}",
+                a(1),
+                labels.tokens(key(ix, 0).and_then(|k| labels.token_mint(k)), amount, None),
+                a(0)
+            ),
+            &["source", "delegate", "owner"],
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => (
+            "approve_checked",
+            format!(
+                "approve {} to spend {} from {}",
+                a(2),
+                labels.tokens(key(ix, 1), amount, Some(decimals)),
+                a(0)
+            ),
+            &["source", "mint", "delegate", "owner"],
+        ),
+        TokenInstruction::Revoke => (
+            "revoke",
+            format!("revoke the delegate of {}", a(0)),
+            &["source", "owner"],
+        ),
+        TokenInstruction::SetAuthority {
+            authority_type: kind,
+            new_authority,
+        } => (
+            "set_authority",
+            format!(
+                "set {} of {} to {}",
+                authority_type(&kind),
+                a(0),
+                labels.optional(&new_authority)
+            ),
+            &["account", "current authority"],
+        ),
+        TokenInstruction::MintTo { amount } => (
+            "mint_to",
+            format!(
+                "mint {} to {}",
+                labels.tokens(key(ix, 0), amount, None),
+                a(1)
+            ),
+            &["mint", "destination", "mint authority"],
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => (
+            "mint_to_checked",
+            format!(
+                "mint {} to {}",
+                labels.tokens(key(ix, 0), amount, Some(decimals)),
+                a(1)
+            ),
+            &["mint", "destination", "mint authority"],
+        ),
+        TokenInstruction::Burn { amount } => (
+            "burn",
+            format!(
+                "burn {} from {}",
+                labels.tokens(key(ix, 1), amount, None),
+                a(0)
+            ),
+            &["account", "mint", "owner"],
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => (
+            "burn_checked",
+            format!(
+                "burn {} from {}",
+                labels.tokens(key(ix, 1), amount, Some(decimals)),
+                a(0)
+            ),
+            &["account", "mint", "owner"],
+        ),
+        TokenInstruction::CloseAccount => (
+            "close_account",
+            format!("close token account {}, sending its rent to {}", a(0), a(1)),
+            &["account", "destination", "owner"],
+        ),
+        TokenInstruction::FreezeAccount => (
+            "freeze_account",
+            format!("freeze token account {}", a(0)),
+            &["account", "mint", "freeze authority"],
+        ),
+        TokenInstruction::ThawAccount => (
+            "thaw_account",
+            format!("thaw token account {}", a(0)),
+            &["account", "mint", "freeze authority"],
+        ),
+        TokenInstruction::SyncNative => (
+            "sync_native",
+            format!("sync the SOL balance of {}", a(0)),
+            &["account"],
+        ),
+    };
+    Some(("SPL Token", name, summary, roles, "signer"))
+}
+
+fn decode_associated_token(ix: &TXInstruction, labels: &Labels) -> Option<Decoded> {
+    let a = |index| account(ix, labels, index);
+    let name = match ix.data.as_slice() {
+        [] | [0] => "create",
+        [1] => "create_idempotent",
+        _ => return None,
+    };
+    Some((
+        "Associated Token Account",
+        name,
+        format!(
+            "create the {} token account of {}, funded by {}",
+            key(ix, 3).map_or_else(
+                || "?".to_string(),
+                |mint| match labels.mints.get(mint) {
+                    Some(info) => info.symbol.clone(),
+                    None => labels.address(mint),
+                }
+            ),
+            a(2),
+            a(0)
+        ),
+        &[
+            "funder",
+            "associated token account",
+            "wallet",
+            "mint",
+            "system program",
+            "token program",
+            "rent sysvar",
+        ],
+        "account",
+    ))
+}
+
+fn decode_upgradeable_loader(ix: &TXInstruction, labels: &Labels) -> Option<Decoded> {
+    let instruction: UpgradeableLoaderInstruction = bincode::deserialize(&ix.data).ok()?;
+    let a = |index| account(ix, labels, index);
+    let (name, summary, roles): (_, _, &'static [&'static str]) = match instruction {
+        UpgradeableLoaderInstruction::InitializeBuffer => (
+            "initialize_buffer",
+            format!("initialize buffer {} with authority {}", a(0), a(1)),
+            &["buffer", "authority"],
+        ),
+        UpgradeableLoaderInstruction::Write { offset, bytes } => (
+            "write",
+            format!(
+                "write {} bytes at offset {} of buffer {}",
+                bytes.len(),
+                offset,
+                a(0)
+            ),
+            &["buffer", "authority"],
+        ),
+        UpgradeableLoaderInstruction::DeployWithMaxDataLen { max_data_len } => (
+            "deploy_with_max_data_len",
+            format!(
+                "deploy program {} from buffer {} with at most {} bytes, with upgrade authority {}",
+                a(2),
+                a(3),
+                max_data_len,
+                a(7)
+            ),
+            &[
+                "payer",
+                "program data",
+                "program",
+                "buffer",
+                "rent sysvar",
+                "clock sysvar",
+                "system program",
+                "upgrade authority",
+            ],
+        ),
+        UpgradeableLoaderInstruction::Upgrade => (
+            "upgrade",
+            format!(
+                "upgrade program {} from buffer {}, sending the buffer rent to {}",
+                a(1),
+                a(2),
+                a(3)
+            ),
+            &[
+                "program data",
+                "program",
+                "buffer",
+                "spill",
+                "rent sysvar",
+                "clock sysvar",
+                "upgrade authority",
+            ],
+        ),
+        UpgradeableLoaderInstruction::SetAuthority => (
+            "set_authority",
+            match ix.keys.get(2) {
+                Some(_) => format!("set authority of {} to {}", a(0), a(2)),
+                None => format!("make {} immutable", a(0)),
+            },
+            &["account", "current authority", "new authority"],
+        ),
+        UpgradeableLoaderInstruction::Close => (
+            "close",
+            format!("close {}, sending its rent to {}", a(0), a(1)),
+            &["account", "recipient", "authority", "program"],
+        ),
+    };
+    Some(("BPF Upgradeable Loader", name, summary, roles, "account"))
+}
+
+fn decode_smallet(ix: &TXInstruction, labels: &Labels) -> Option<Decoded> {
+    let (discriminator, mut args) = ix.data.split_at(ix.data.len().min(8));
+    let set_owners = smallet::instruction::SetOwners { owners: vec![] }.data();
+    let change_threshold = smallet::instruction::ChangeThreshold { threshold: 0 }.data();
+    let add_entry = smallet::instruction::AddAddressBookEntry {
+        recipient: Pubkey::default(),
+        label: String::new(),
+    }
+    .data();
+    let remove_entry = smallet::instruction::RemoveAddressBookEntry {
+        recipient: Pubkey::default(),
+    }
+    .data();
+    let (name, summary) = if discriminator == &set_owners[..8] {
+        let owners = Vec::<Pubkey>::deserialize(&mut args).ok()?;
+        let owners: Vec<String> = owners.iter().map(|owner| labels.address(owner)).collect();
+        (
+            "set_owners",
+            format!(
+                "set the owners of {} to [{}]",
+                account(ix, labels, 0),
+                owners.join(", ")
+            ),
+        )
+    } else if discriminator == &change_threshold[..8] {
+        let threshold = u64::deserialize(&mut args).ok()?;
+        (
+            "change_threshold",
+            format!(
+                "change the threshold of {} to {}",
+                account(ix, labels, 0),
+                threshold
+            ),
+        )
+    } else if discriminator == &add_entry[..8] {
+        let recipient = Pubkey::deserialize(&mut args).ok()?;
+        let label = String::deserialize(&mut args).ok()?;
+        (
+            "add_address_book_entry",
+            format!(
+                "add {} ({:?}) to the address book of {}",
+                labels.address(&recipient),
+                label,
+                account(ix, labels, 0)
+            ),
+        )
+    } else if discriminator == &remove_entry[..8] {
+        let recipient = Pubkey::deserialize(&mut args).ok()?;
+        (
+            "remove_address_book_entry",
+            format!(
+                "remove {} from the address book of {}",
+                labels.address(&recipient),
+                account(ix, labels, 0)
+            ),
+        )
+    } else {
+        return None;
+    };
+    Some(("Smallet", name, summary, &["smallet"], "account"))
+}
@@ -0,0 +1,145 @@
+//! Decoding of the events emitted by the smallet program from transaction logs.
+//!
+//! Anchor emits each event as a `Program data: <base64>` log line, where the
+//! data is the event discriminator followed by the borsh serialized event.
+
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, Event};
+use ::smallet::*;
+
+macro_rules! smallet_events {
+    ($($variant:ident($event:ident)),* $(,)?) => {
+        /// An event emitted by the smallet program.
+        #[derive(Clone, Debug)]
+        pub enum SmalletEvent {
+            $($variant($event)),*
+        }
+
+        impl SmalletEvent {
+            /// Decodes an event from its discriminator and borsh serialized data.
+            /// Returns [None] if the data is not a known smallet event.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                if data.len() < 8 {
+                    return None;
+                }
+                let (discriminator, mut rest) = data.split_at(8);
+                $(
+                    if discriminator == <$event as Discriminator>::discriminator() {
+                        return $event::deserialize(&mut rest).ok().map(SmalletEvent::$variant);
+                    }
+                )*
+                None
+            }
+
+            /// The discriminator and borsh serialized data of the event, as logged.
+            pub fn data(&self) -> Vec<u8> {
+                match self {
+                    $(SmalletEvent::$variant(event) => event.data()),*
+                }
+            }
+
+            /// Name of the event type, e.g. `WalletCreateEvent`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SmalletEvent::$variant(_) => stringify!($event)),*
+                }
+            }
+
+            /// The smallet the event relates to.
+            pub fn smallet(&self) -> Pubkey {
+                match self {
+                    $(SmalletEvent::$variant(event) => event.smallet),*
+                }
+            }
+
+            /// Unix timestamp at which the event was emitted.
+            pub fn timestamp(&self) -> i64 {
+                match self {
+                    $(SmalletEvent::$variant(event) => event.timestamp),*
+                }
+            }
+        }
+    };
+}
+
+smallet_events! {
+    WalletCreate(WalletCreateEvent),
+    WalletSetOwners(WalletSetOwnersEvent),
+    WalletChangeThreshold(WalletChangeThresholdEvent),
+    WalletChangeVetoThreshold(WalletChangeVetoThresholdEvent),
+    WalletChangeApprovalTtl(WalletChangeApprovalTtlEvent),
+    FifoCursorSet(FifoCursorSetEvent),
+    TransactionCreate(TransactionCreateEvent),
+    TransactionApprove(TransactionApproveEvent),
+    TransactionUnapprove(TransactionUnapproveEvent),
+    TransactionVeto(TransactionVetoEvent),
+    TransactionExecute(TransactionExecuteEvent),
+    TransactionIndexAssign(TransactionIndexAssignEvent),
+    SmalletMigrate(SmalletMigrateEvent),
+    TransactionMigrate(TransactionMigrateEvent),
+    ProgramUpgradePropose(ProgramUpgradeProposeEvent),
+    TransferPropose(TransferProposeEvent),
+    TransferExecute(TransferExecuteEvent),
+    TransactionCommit(TransactionCommitEvent),
+    TransactionReveal(TransactionRevealEvent),
+    TimelockTiersSet(TimelockTiersSetEvent),
+    OptimisticModeSet(OptimisticModeSetEvent),
+    InvokerPolicySet(InvokerPolicySetEvent),
+    OwnerInvoke(OwnerInvokeEvent),
+    AddressBookPolicySet(AddressBookPolicySetEvent),
+    AddressBookEntryAdd(AddressBookEntryAddEvent),
+    AddressBookEntryRemove(AddressBookEntryRemoveEvent),
+    SubaccountCreate(SubaccountCreateEvent),
+    SubaccountUpdate(SubaccountUpdateEvent),
+    SubaccountClose(SubaccountCloseEvent),
+    SubaccountMigrate(SubaccountMigrateEvent),
+}
+
+const PROGRAM_DATA: &str = "Program data: ";
+const PROGRAM_LOG: &str = "Program log: ";
+
+/// Parses the events emitted by the smallet program from the log messages of
+/// a transaction, in emission order.
+///
+/// Only data logged while the smallet program is executing is decoded, so
+/// other programs cannot spoof events. Events of failed transactions were
+/// rolled back; callers should only pass the logs of successful transactions.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<SmalletEvent> {
+    let mut stack: Vec<&str> = vec![];
+    let mut events = vec![];
+    let program_id = ::smallet::ID.to_string();
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(data) = line
+            .strip_prefix(PROGRAM_DATA)
+            .or_else(|| line.strip_prefix(PROGRAM_LOG))
+        {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            // `sol_log_data` logs each field separated by a space.
+            for field in data.split(' ') {
+                if let Some(event) = base64::decode(field)
+                    .ok()
+                    .and_then(|data| SmalletEvent::decode(&data))
+                {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let program = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => stack.push(program),
+                Some("success") => {
+                    stack.pop();
+                }
+                Some(word) if word.starts_with("failed") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
@@ -0,0 +1,419 @@
+//! Reconstruction of the history of smallets from their events.
+//!
+//! Feed every [SmalletEvent] to [History::apply] in the order it was emitted,
+//! e.g. by walking the transactions of the program from oldest to newest and
+//! passing the output of [crate::events::parse_logs].
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use smallet::{
+    AddressBookEntry, OptimisticConfig, SubaccountType, TXInstruction, TimelockTier, VaultTransfer,
+};
+
+use crate::events::SmalletEvent;
+
+/// A version of the owner set of a smallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnerSet {
+    /// Owner set sequence number. Matches [smallet::Smallet::owner_set_seqno].
+    /// None if the creation of the smallet was not observed, as the number of
+    /// earlier owner sets is then unknown.
+    pub seqno: Option<u32>,
+    pub owners: Vec<Pubkey>,
+    /// When the owner set became active.
+    pub since: i64,
+}
+
+/// A change of the threshold of a smallet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThresholdChange {
+    pub threshold: u64,
+    pub since: i64,
+    /// True if the threshold was lowered implicitly because the new owner set
+    /// was smaller than the previous threshold.
+    pub implicit: bool,
+}
+
+/// An approval or revocation of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ApprovalChange {
+    pub owner: Pubkey,
+    /// True for an approval, false for a revocation.
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+/// The lifecycle of a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionLifecycle {
+    pub transaction: Pubkey,
+    pub proposer: Pubkey,
+    pub instructions: Vec<TXInstruction>,
+    pub eta: i64,
+    pub created_at: i64,
+    /// Owner set sequence number when the transaction was proposed, if known.
+    pub owner_set_seqno: Option<u32>,
+    /// Index assigned via [smallet::smallet::assign_transaction_index].
+    pub index: Option<u64>,
+    /// Whether the transaction was proposed as a program upgrade.
+    pub program_upgrade: bool,
+    /// The transfer, if the transaction was proposed via
+    /// [smallet::smallet::propose_transfer].
+    pub transfer: Option<VaultTransfer>,
+    /// Hash of the instructions committed to via
+    /// [smallet::smallet::create_committed_transaction]. [Self::instructions]
+    /// stays empty until they are revealed at execution.
+    pub instructions_hash: Option<[u8; 32]>,
+    pub approvals: Vec<ApprovalChange>,
+    /// Owners who vetoed the transaction, in veto order.
+    pub vetoers: Vec<Pubkey>,
+    /// When the veto threshold was reached, blocking the transaction.
+    pub vetoed_at: Option<i64>,
+    /// The executor and time of execution.
+    pub executed: Option<(Pubkey, i64)>,
+}
+
+impl TransactionLifecycle {
+    /// Owners currently approving the transaction.
+    pub fn approvers(&self) -> Vec<Pubkey> {
+        let mut approvers: Vec<Pubkey> = vec![];
+        for change in &self.approvals {
+            if change.approved {
+                if !approvers.contains(&change.owner) {
+                    approvers.push(change.owner);
+                }
+            } else {
+                approvers.retain(|owner| *owner != change.owner);
+            }
+        }
+        approvers
+    }
+
+    /// Owners whose approval is younger than `approval_ttl` at `now`.
+    /// All current approvers if `approval_ttl` is 0.
+    pub fn fresh_approvers(&self, approval_ttl: i64, now: i64) -> Vec<Pubkey> {
+        let mut approvers = self.approvers();
+        if approval_ttl != 0 {
+            approvers.retain(|owner| {
+                self.approvals.iter().rev().any(|change| {
+                    change.owner == *owner
+                        && change.approved
+                        && now <= change.timestamp.saturating_add(approval_ttl)
+                })
+            });
+        }
+        approvers
+    }
+}
+
+/// A registered sub-account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubaccountHistory {
+    pub subaccount: Pubkey,
+    /// Type and index, if the sub-account was created rather than migrated.
+    pub subaccount_type: Option<(SubaccountType, u64)>,
+    pub registry_index: u64,
+    pub label: Option<String>,
+    pub created_at: i64,
+    pub closed_at: Option<i64>,
+}
+
+/// The full history of a smallet.
+#[derive(Clone, Debug, Default)]
+pub struct SmalletTimeline {
+    pub smallet: Pubkey,
+    /// When the smallet was created, if its creation was observed.
+    pub created_at: Option<i64>,
+    pub minimum_delay: Option<i64>,
+    /// Every owner set, oldest first.
+    pub owner_sets: Vec<OwnerSet>,
+    /// Every threshold, oldest first.
+    pub thresholds: Vec<ThresholdChange>,
+    /// The current timelock tiers.
+    pub tiers: Vec<TimelockTier>,
+    /// The current veto threshold. 0 if vetoes are disabled.
+    pub veto_threshold: u64,
+    /// The current optimistic mode, if enabled.
+    pub optimistic: Option<OptimisticConfig>,
+    /// The current approval TTL, in seconds. 0 if approvals never expire.
+    pub approval_ttl: i64,
+    /// The index of the first transaction that may still be pending, if
+    /// transactions are executed in order.
+    pub fifo_cursor: Option<u64>,
+    /// Transactions, in proposal order.
+    pub transactions: Vec<TransactionLifecycle>,
+    /// Registered sub-accounts, in registration order.
+    pub subaccounts: Vec<SubaccountHistory>,
+    /// The current entries of the address book, in insertion order.
+    pub address_book: Vec<AddressBookEntry>,
+    /// Every event applied to this timeline, in order.
+    pub events: Vec<SmalletEvent>,
+}
+
+impl SmalletTimeline {
+    /// The current owner set.
+    pub fn owner_set(&self) -> Option<&OwnerSet> {
+        self.owner_sets.last()
+    }
+
+    /// The current threshold.
+    pub fn threshold(&self) -> Option<u64> {
+        self.thresholds.last().map(|change| change.threshold)
+    }
+
+    /// The owner set that was active at `timestamp`.
+    pub fn owner_set_at(&self, timestamp: i64) -> Option<&OwnerSet> {
+        self.owner_sets.iter().rev().find(|set| set.since <= timestamp)
+    }
+
+    /// Looks up a transaction.
+    pub fn transaction(&self, transaction: &Pubkey) -> Option<&TransactionLifecycle> {
+        self.transactions
+            .iter()
+            .find(|tx| tx.transaction == *transaction)
+    }
+
+    fn transaction_mut(&mut self, transaction: &Pubkey) -> Option<&mut TransactionLifecycle> {
+        self.transactions
+            .iter_mut()
+            .find(|tx| tx.transaction == *transaction)
+    }
+
+    fn subaccount_mut(&mut self, subaccount: &Pubkey) -> Option<&mut SubaccountHistory> {
+        self.subaccounts
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.subaccount == *subaccount)
+    }
+
+    /// Applies an event of this smallet.
+    pub fn apply(&mut self, event: SmalletEvent) {
+        match &event {
+            SmalletEvent::WalletCreate(e) => {
+                self.created_at = Some(e.timestamp);
+                self.minimum_delay = Some(e.minimum_delay);
+                self.owner_sets.push(OwnerSet {
+                    seqno: Some(0),
+                    owners: e.owners.clone(),
+                    since: e.timestamp,
+                });
+                self.thresholds.push(ThresholdChange {
+                    threshold: e.threshold,
+                    since: e.timestamp,
+                    implicit: false,
+                });
+            }
+            SmalletEvent::WalletSetOwners(e) => {
+                let seqno = self
+                    .owner_set()
+                    .and_then(|set| set.seqno)
+                    .map(|seqno| seqno + 1);
+                self.owner_sets.push(OwnerSet {
+                    seqno,
+                    owners: e.owners.clone(),
+                    since: e.timestamp,
+                });
+                // `set_owners` lowers the thresholds to the number of owners.
+                for tier in self.tiers.iter_mut() {
+                    tier.threshold = tier.threshold.min(e.owners.len() as u64);
+                }
+                self.veto_threshold = self.veto_threshold.min(e.owners.len() as u64);
+                if let Some(config) = self.optimistic.as_mut() {
+                    config.objection_threshold =
+                        config.objection_threshold.min(e.owners.len() as u64);
+                }
+                if let Some(threshold) = self.threshold() {
+                    if (e.owners.len() as u64) < threshold {
+                        self.thresholds.push(ThresholdChange {
+                            threshold: e.owners.len() as u64,
+                            since: e.timestamp,
+                            implicit: true,
+                        });
+                    }
+                }
+            }
+            SmalletEvent::WalletChangeThreshold(e) => {
+                self.thresholds.push(ThresholdChange {
+                    threshold: e.threshold,
+                    since: e.timestamp,
+                    implicit: false,
+                });
+            }
+            SmalletEvent::WalletChangeVetoThreshold(e) => {
+                self.veto_threshold = e.veto_threshold;
+            }
+            SmalletEvent::WalletChangeApprovalTtl(e) => {
+                self.approval_ttl = e.approval_ttl;
+            }
+            SmalletEvent::FifoCursorSet(e) => {
+                self.fifo_cursor = e.cursor;
+            }
+            SmalletEvent::TimelockTiersSet(e) => {
+                self.tiers = e.tiers.clone();
+            }
+            SmalletEvent::OptimisticModeSet(e) => {
+                self.optimistic = e.config.clone();
+            }
+            SmalletEvent::TransactionCreate(e) => {
+                let owner_set_seqno = self.owner_set().and_then(|set| set.seqno);
+                self.transactions.push(TransactionLifecycle {
+                    transaction: e.transaction,
+                    proposer: e.proposer,
+                    instructions: e.instructions.clone(),
+                    eta: e.eta,
+                    created_at: e.timestamp,
+                    owner_set_seqno,
+                    index: None,
+                    program_upgrade: false,
+                    transfer: None,
+                    instructions_hash: None,
+                    approvals: vec![],
+                    vetoers: vec![],
+                    vetoed_at: None,
+                    executed: None,
+                });
+            }
+            SmalletEvent::ProgramUpgradePropose(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.program_upgrade = true;
+                }
+            }
+            SmalletEvent::TransferPropose(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.transfer = Some(e.transfer);
+                }
+            }
+            SmalletEvent::TransactionCommit(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.instructions_hash = Some(e.instructions_hash);
+                }
+            }
+            SmalletEvent::TransactionReveal(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.instructions = e.instructions.clone();
+                }
+            }
+            SmalletEvent::TransactionApprove(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.approvals.push(ApprovalChange {
+                        owner: e.owner,
+                        approved: true,
+                        timestamp: e.timestamp,
+                    });
+                }
+            }
+            SmalletEvent::TransactionUnapprove(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.approvals.push(ApprovalChange {
+                        owner: e.owner,
+                        approved: false,
+                        timestamp: e.timestamp,
+                    });
+                }
+            }
+            SmalletEvent::TransactionVeto(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.vetoers.push(e.owner);
+                    if e.blocked {
+                        tx.vetoed_at = Some(e.timestamp);
+                    }
+                }
+            }
+            SmalletEvent::TransactionExecute(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.executed = Some((e.executor, e.timestamp));
+                }
+            }
+            SmalletEvent::TransactionIndexAssign(e) => {
+                if let Some(tx) = self.transaction_mut(&e.transaction) {
+                    tx.index = Some(e.index);
+                }
+            }
+            SmalletEvent::SubaccountCreate(e) => {
+                self.subaccounts.push(SubaccountHistory {
+                    subaccount: e.subaccount,
+                    subaccount_type: Some((e.subaccount_type, e.index)),
+                    registry_index: e.registry_index,
+                    label: e.label.clone(),
+                    created_at: e.timestamp,
+                    closed_at: None,
+                });
+            }
+            SmalletEvent::SubaccountMigrate(e) => {
+                self.subaccounts.push(SubaccountHistory {
+                    subaccount: e.subaccount,
+                    subaccount_type: None,
+                    registry_index: e.registry_index,
+                    label: None,
+                    created_at: e.timestamp,
+                    closed_at: None,
+                });
+            }
+            SmalletEvent::SubaccountUpdate(e) => {
+                // The event refers to the info account; match it to its sub-account.
+                if let Some(entry) = self.subaccounts.iter_mut().rev().find(|entry| {
+                    crate::pda::find_subaccount_info_address(&entry.subaccount).0
+                        == e.subaccount_info
+                }) {
+                    entry.label = e.label.clone();
+                }
+            }
+            SmalletEvent::SubaccountClose(e) => {
+                if let Some(entry) = self.subaccount_mut(&e.subaccount) {
+                    entry.closed_at = Some(e.timestamp);
+                }
+            }
+            SmalletEvent::AddressBookEntryAdd(e) => {
+                self.address_book.push(AddressBookEntry {
+                    recipient: e.recipient,
+                    label: e.label.clone(),
+                    trusted_at: e.trusted_at,
+                });
+            }
+            SmalletEvent::AddressBookEntryRemove(e) => {
+                self.address_book
+                    .retain(|entry| entry.recipient != e.recipient);
+            }
+            SmalletEvent::SmalletMigrate(_)
+            | SmalletEvent::TransactionMigrate(_)
+            | SmalletEvent::InvokerPolicySet(_)
+            | SmalletEvent::OwnerInvoke(_)
+            | SmalletEvent::TransferExecute(_)
+            | SmalletEvent::AddressBookPolicySet(_) => {}
+        }
+        self.events.push(event);
+    }
+}
+
+/// Timelines of every smallet observed.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub smallets: BTreeMap<Pubkey, SmalletTimeline>,
+}
+
+impl History {
+    /// Applies an event to the timeline of its smallet.
+    pub fn apply(&mut self, event: SmalletEvent) {
+        let smallet = event.smallet();
+        self.smallets
+            .entry(smallet)
+            .or_insert_with(|| SmalletTimeline {
+                smallet,
+                ..Default::default()
+            })
+            .apply(event);
+    }
+
+    /// Applies every event parsed from the logs of a successful transaction.
+    pub fn apply_logs<S: AsRef<str>>(&mut self, logs: &[S]) {
+        for event in crate::events::parse_logs(logs) {
+            self.apply(event);
+        }
+    }
+
+    /// The timeline of a smallet.
+    pub fn smallet(&self, smallet: &Pubkey) -> Option<&SmalletTimeline> {
+        self.smallets.get(smallet)
+    }
+}
@@ -0,0 +1,813 @@
+//! Builders for the instructions of the smallet program.
+//!
+//! Instructions that may only be invoked by the smallet itself, such as
+//! [set_owners], are meant to be converted into [TXInstruction]s and proposed.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::InstructionData;
+use smallet::{
+    AllowedInstruction, OptimisticConfig, PdaSigner, SubaccountType, TXInstruction, TimelockTier,
+    Transaction, TransactionOptions, VaultTransfer,
+};
+
+use crate::pda::*;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: smallet::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Addresses of the PDAs signing the instructions of a [Transaction].
+pub fn pda_signer_addresses(smallet: &Pubkey, tx: &Transaction) -> Vec<Pubkey> {
+    if tx.pda_signers.is_empty() {
+        return vec![*smallet];
+    }
+    tx.pda_signers
+        .iter()
+        .filter_map(|signer| match *signer {
+            PdaSigner::Smallet => Some(*smallet),
+            PdaSigner::Derived { index, bump } => Pubkey::create_program_address(
+                &[
+                    b"CosmicSmalletDerived",
+                    smallet.as_ref(),
+                    &index.to_le_bytes(),
+                    &[bump],
+                ],
+                &smallet::ID,
+            )
+            .ok(),
+            PdaSigner::OwnerInvoker { index, bump } => Pubkey::create_program_address(
+                &[
+                    b"CosmicSmalletOwnerInvoker",
+                    smallet.as_ref(),
+                    &index.to_le_bytes(),
+                    &[bump],
+                ],
+                &smallet::ID,
+            )
+            .ok(),
+        })
+        .collect()
+}
+
+/// Accounts of `instructions`, as passed in the remaining accounts of an
+/// instruction invoking them signed by `pdas`.
+pub fn invoked_accounts(instructions: &[TXInstruction], pdas: &[Pubkey]) -> Vec<AccountMeta> {
+    instructions
+        .iter()
+        .flat_map(|ix| {
+            std::iter::once(AccountMeta::new_readonly(ix.program_id, false)).chain(
+                ix.keys.iter().map(|key| AccountMeta {
+                    pubkey: key.pubkey,
+                    is_signer: key.is_signer && !pdas.contains(&key.pubkey),
+                    is_writable: key.is_writable,
+                }),
+            )
+        })
+        .collect()
+}
+
+/// Read-only metas of `transactions`, passed in the remaining accounts of
+/// [execute_transaction] to prove that prerequisites and, in FIFO mode,
+/// earlier transactions are settled.
+pub fn transaction_accounts(transactions: &[Pubkey]) -> Vec<AccountMeta> {
+    transactions
+        .iter()
+        .map(|transaction| AccountMeta::new_readonly(*transaction, false))
+        .collect()
+}
+
+/// Read-only metas of the accounts of the pre- and post-conditions of `tx`,
+/// and of the address book vouching for its recipients, passed in the
+/// remaining accounts of its execution.
+pub fn condition_accounts(tx: &Transaction) -> Vec<AccountMeta> {
+    let pre_conditions = tx.pre_conditions.iter().map(|condition| condition.account);
+    let post_conditions = tx.post_conditions.iter().map(|condition| condition.account);
+    let address_book = tx
+        .address_book
+        .then(|| find_address_book_address(&tx.smallet).0);
+    pre_conditions
+        .chain(post_conditions)
+        .chain(address_book)
+        .map(|account| AccountMeta::new_readonly(account, false))
+        .collect()
+}
+
+/// Read-only meta of the address book of `smallet`, passed in the remaining
+/// accounts of a proposal paying its entries.
+pub fn address_book_account(smallet: &Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(find_address_book_address(smallet).0, false)
+}
+
+/// Builds [smallet::smallet::create_smallet].
+pub fn create_smallet(
+    base: &Pubkey,
+    payer: &Pubkey,
+    max_owners: u8,
+    owners: Vec<Pubkey>,
+    threshold: u64,
+    minimum_delay: i64,
+) -> Instruction {
+    let (smallet, bump) = find_smallet(base);
+    build(
+        smallet::accounts::CreateSmallet {
+            base: *base,
+            smallet,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateSmallet {
+            _bump: bump,
+            max_owners,
+            owners,
+            threshold,
+            minimum_delay,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::set_owners], to be proposed. The address of the
+/// [smallet::AddressBook] is passed whether it exists or not.
+pub fn set_owners(smallet: &Pubkey, owners: Vec<Pubkey>) -> Instruction {
+    let mut ix = build(
+        smallet::accounts::Auth { smallet: *smallet },
+        smallet::instruction::SetOwners { owners },
+    );
+    ix.accounts.push(AccountMeta::new(
+        find_address_book_address(smallet).0,
+        false,
+    ));
+    ix
+}
+
+/// Builds [smallet::smallet::change_threshold], to be proposed.
+pub fn change_threshold(smallet: &Pubkey, threshold: u64) -> Instruction {
+    build(
+        smallet::accounts::Auth { smallet: *smallet },
+        smallet::instruction::ChangeThreshold { threshold },
+    )
+}
+
+/// Builds [smallet::smallet::change_veto_threshold], to be proposed.
+pub fn change_veto_threshold(smallet: &Pubkey, veto_threshold: u64) -> Instruction {
+    build(
+        smallet::accounts::Auth { smallet: *smallet },
+        smallet::instruction::ChangeVetoThreshold { veto_threshold },
+    )
+}
+
+/// Builds [smallet::smallet::change_approval_ttl], to be proposed.
+pub fn change_approval_ttl(smallet: &Pubkey, approval_ttl: i64) -> Instruction {
+    build(
+        smallet::accounts::Auth { smallet: *smallet },
+        smallet::instruction::ChangeApprovalTtl { approval_ttl },
+    )
+}
+
+/// Builds [smallet::smallet::set_fifo_mode].
+pub fn set_fifo_mode(smallet: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        smallet::accounts::Auth { smallet: *smallet },
+        smallet::instruction::SetFifoMode { enabled },
+    )
+}
+
+/// Builds [smallet::smallet::advance_fifo_cursor] over the transactions at
+/// and after the cursor.
+pub fn advance_fifo_cursor(smallet: &Pubkey, transactions: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        smallet::accounts::AdvanceFifoCursor { smallet: *smallet },
+        smallet::instruction::AdvanceFifoCursor {},
+    );
+    ix.accounts.extend(transaction_accounts(transactions));
+    ix
+}
+
+/// Builds [smallet::smallet::create_transaction].
+/// `index` must be the current [smallet::Smallet::num_transactions].
+pub fn create_transaction(
+    smallet: &Pubkey,
+    index: u64,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    instructions: Vec<TXInstruction>,
+) -> Instruction {
+    let (transaction, bump) = find_transaction_address(smallet, index);
+    build(
+        smallet::accounts::CreateTransaction {
+            smallet: *smallet,
+            transaction,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateTransaction { bump, instructions },
+    )
+}
+
+/// Builds [smallet::smallet::create_transaction_with_timelock].
+/// `index` must be the current [smallet::Smallet::num_transactions].
+pub fn create_transaction_with_timelock(
+    smallet: &Pubkey,
+    index: u64,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    instructions: Vec<TXInstruction>,
+    eta: i64,
+) -> Instruction {
+    let (transaction, bump) = find_transaction_address(smallet, index);
+    build(
+        smallet::accounts::CreateTransaction {
+            smallet: *smallet,
+            transaction,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateTransactionWithTimelock {
+            _bump: bump,
+            instructions,
+            eta,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::create_transaction_v2].
+pub fn create_transaction_v2(
+    smallet: &Pubkey,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    nonce: u64,
+    instructions: Vec<TXInstruction>,
+    eta: i64,
+    options: TransactionOptions,
+) -> Instruction {
+    let (transaction, _) = find_transaction_v2_address(smallet, proposer, nonce);
+    build(
+        smallet::accounts::CreateTransactionV2 {
+            smallet: *smallet,
+            transaction,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateTransactionV2 {
+            nonce,
+            instructions,
+            eta,
+            options,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::create_committed_transaction], committing to the
+/// hash of `instructions` salted with `salt` without publishing them.
+pub fn create_committed_transaction(
+    smallet: &Pubkey,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    nonce: u64,
+    salt: &[u8; 32],
+    instructions: &[TXInstruction],
+    eta: i64,
+    options: TransactionOptions,
+) -> Instruction {
+    let (transaction, _) = find_transaction_v2_address(smallet, proposer, nonce);
+    build(
+        smallet::accounts::CreateCommittedTransaction {
+            smallet: *smallet,
+            transaction,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateCommittedTransaction {
+            nonce,
+            instructions_hash: Transaction::hash_instructions(salt, instructions)
+                .expect("instructions serialize"),
+            eta,
+            options,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::propose_program_upgrade].
+pub fn propose_program_upgrade(
+    smallet: &Pubkey,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    nonce: u64,
+    program: &Pubkey,
+    buffer: &Pubkey,
+    spill: &Pubkey,
+    buffer_hash: [u8; 32],
+    eta: i64,
+) -> Instruction {
+    let (transaction, _) = find_transaction_v2_address(smallet, proposer, nonce);
+    build(
+        smallet::accounts::ProposeProgramUpgrade {
+            smallet: *smallet,
+            transaction,
+            program: *program,
+            buffer: *buffer,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::ProposeProgramUpgrade {
+            nonce,
+            spill: *spill,
+            buffer_hash,
+            eta,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::propose_transfer].
+pub fn propose_transfer(
+    smallet: &Pubkey,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    nonce: u64,
+    transfer: VaultTransfer,
+    eta: i64,
+) -> Instruction {
+    let (transaction, _) = find_transaction_v2_address(smallet, proposer, nonce);
+    build(
+        smallet::accounts::ProposeTransfer {
+            smallet: *smallet,
+            transaction,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::ProposeTransfer {
+            nonce,
+            transfer,
+            eta,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::assign_transaction_index].
+pub fn assign_transaction_index(smallet: &Pubkey, transaction: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::AssignTransactionIndex {
+            smallet: *smallet,
+            transaction: *transaction,
+        },
+        smallet::instruction::AssignTransactionIndex {},
+    )
+}
+
+/// Builds [smallet::smallet::approve].
+pub fn approve(smallet: &Pubkey, transaction: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::Approve {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+        },
+        smallet::instruction::Approve {},
+    )
+}
+
+/// Builds [smallet::smallet::unapprove].
+pub fn unapprove(smallet: &Pubkey, transaction: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::Approve {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+        },
+        smallet::instruction::Unapprove {},
+    )
+}
+
+/// Builds [smallet::smallet::veto].
+pub fn veto(smallet: &Pubkey, transaction: &Pubkey, owner: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::Veto {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::Veto {},
+    )
+}
+
+/// Builds [smallet::smallet::execute_transaction] for the decoded [Transaction] `tx`.
+pub fn execute_transaction(
+    smallet: &Pubkey,
+    transaction: &Pubkey,
+    tx: &Transaction,
+    owner: &Pubkey,
+) -> Instruction {
+    let mut ix = build(
+        smallet::accounts::ExecuteTransaction {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+        },
+        smallet::instruction::ExecuteTransaction {},
+    );
+    ix.accounts.extend(invoked_accounts(
+        &tx.instructions,
+        &pda_signer_addresses(smallet, tx),
+    ));
+    ix.accounts.extend(condition_accounts(tx));
+    ix
+}
+
+/// Builds [smallet::smallet::execute_transaction_derived] for the decoded
+/// [Transaction] `tx`, which must declare the derived address at `index`,
+/// or no signers if it was proposed via [create_transaction].
+pub fn execute_transaction_derived(
+    smallet: &Pubkey,
+    transaction: &Pubkey,
+    tx: &Transaction,
+    owner: &Pubkey,
+    index: u64,
+) -> Instruction {
+    let (wallet, bump) = find_wallet_derived_address(smallet, index);
+    // Transactions declaring no signers are signed by the derived address alone.
+    let pdas = if tx.pda_signers.is_empty() {
+        vec![wallet]
+    } else {
+        pda_signer_addresses(smallet, tx)
+    };
+    let mut ix = build(
+        smallet::accounts::ExecuteTransaction {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+        },
+        smallet::instruction::ExecuteTransactionDerived { index, bump },
+    );
+    ix.accounts
+        .extend(invoked_accounts(&tx.instructions, &pdas));
+    ix.accounts.extend(condition_accounts(tx));
+    ix
+}
+
+/// Builds [smallet::smallet::execute_committed_transaction] for the decoded
+/// [Transaction] `tx`, revealing the `instructions` and the `salt` it
+/// committed to.
+pub fn execute_committed_transaction(
+    smallet: &Pubkey,
+    transaction: &Pubkey,
+    tx: &Transaction,
+    owner: &Pubkey,
+    salt: [u8; 32],
+    instructions: Vec<TXInstruction>,
+) -> Instruction {
+    let accounts = invoked_accounts(&instructions, &pda_signer_addresses(smallet, tx));
+    let mut ix = build(
+        smallet::accounts::ExecuteTransaction {
+            smallet: *smallet,
+            transaction: *transaction,
+            owner: *owner,
+        },
+        smallet::instruction::ExecuteCommittedTransaction { salt, instructions },
+    );
+    ix.accounts.extend(accounts);
+    ix.accounts.extend(condition_accounts(tx));
+    ix
+}
+
+/// Builds [smallet::smallet::owner_invoke_instruction], invoking `ix` as the
+/// owner invoker at `index`.
+pub fn owner_invoke_instruction(
+    smallet: &Pubkey,
+    owner: &Pubkey,
+    index: u64,
+    ix: TXInstruction,
+) -> Instruction {
+    let (invoker, bump) = find_owner_invoker_address(smallet, index);
+    let (policy, _) = find_invoker_policy_address(smallet, index);
+    let remaining = invoked_accounts(std::slice::from_ref(&ix), &[invoker]);
+    let mut result = build(
+        smallet::accounts::OwnerInvokeInstruction {
+            smallet: *smallet,
+            owner: *owner,
+            policy,
+        },
+        smallet::instruction::OwnerInvokeInstruction { index, bump, ix },
+    );
+    result.accounts.extend(remaining);
+    result
+}
+
+/// Builds [smallet::smallet::owner_invoke_instruction_v2], invoking `ix` as the
+/// owner invoker at `index`.
+pub fn owner_invoke_instruction_v2(
+    smallet: &Pubkey,
+    owner: &Pubkey,
+    index: u64,
+    ix: TXInstruction,
+) -> Instruction {
+    let (invoker, bump) = find_owner_invoker_address(smallet, index);
+    let (policy, _) = find_invoker_policy_address(smallet, index);
+    let remaining = invoked_accounts(std::slice::from_ref(&ix), &[invoker]);
+    let mut result = build(
+        smallet::accounts::OwnerInvokeInstruction {
+            smallet: *smallet,
+            owner: *owner,
+            policy,
+        },
+        smallet::instruction::OwnerInvokeInstructionV2 {
+            index,
+            bump,
+            invoker,
+            data: ix.data,
+        },
+    );
+    result.accounts.extend(remaining);
+    result
+}
+
+/// Builds [smallet::smallet::migrate_smallet].
+pub fn migrate_smallet(smallet: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::MigrateSmallet {
+            smallet: *smallet,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::MigrateSmallet {},
+    )
+}
+
+/// Builds [smallet::smallet::migrate_transaction].
+pub fn migrate_transaction(transaction: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        smallet::accounts::MigrateTransaction {
+            transaction: *transaction,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::MigrateTransaction {},
+    )
+}
+
+/// Builds [smallet::smallet::create_invoker_policy], to be proposed.
+pub fn create_invoker_policy(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    index: u64,
+    owners: Vec<Pubkey>,
+    allowed_instructions: Vec<AllowedInstruction>,
+    max_calls_per_period: u64,
+    period: i64,
+) -> Instruction {
+    let (policy, _) = find_invoker_policy_address(smallet, index);
+    build(
+        smallet::accounts::CreateInvokerPolicy {
+            smallet: *smallet,
+            policy,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateInvokerPolicy {
+            index,
+            owners,
+            allowed_instructions,
+            max_calls_per_period,
+            period,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::set_invoker_policy], to be proposed.
+pub fn set_invoker_policy(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    index: u64,
+    owners: Vec<Pubkey>,
+    allowed_instructions: Vec<AllowedInstruction>,
+    max_calls_per_period: u64,
+    period: i64,
+) -> Instruction {
+    let (policy, _) = find_invoker_policy_address(smallet, index);
+    build(
+        smallet::accounts::SetInvokerPolicy {
+            smallet: *smallet,
+            policy,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::SetInvokerPolicy {
+            owners,
+            allowed_instructions,
+            max_calls_per_period,
+            period,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::set_timelock_tiers], to be proposed.
+pub fn set_timelock_tiers(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    tiers: Vec<TimelockTier>,
+) -> Instruction {
+    build(
+        smallet::accounts::SetTimelockTiers {
+            smallet: *smallet,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::SetTimelockTiers { tiers },
+    )
+}
+
+/// Builds [smallet::smallet::set_optimistic_mode], to be proposed.
+pub fn set_optimistic_mode(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    config: Option<OptimisticConfig>,
+) -> Instruction {
+    build(
+        smallet::accounts::SetOptimisticMode {
+            smallet: *smallet,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::SetOptimisticMode { config },
+    )
+}
+
+/// Builds [smallet::smallet::create_address_book], to be proposed.
+pub fn create_address_book(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    threshold: u64,
+    minimum_delay: i64,
+    entry_delay: i64,
+) -> Instruction {
+    let (address_book, _) = find_address_book_address(smallet);
+    build(
+        smallet::accounts::CreateAddressBook {
+            smallet: *smallet,
+            address_book,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateAddressBook {
+            threshold,
+            minimum_delay,
+            entry_delay,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::set_address_book_policy], to be proposed.
+pub fn set_address_book_policy(
+    smallet: &Pubkey,
+    threshold: u64,
+    minimum_delay: i64,
+    entry_delay: i64,
+) -> Instruction {
+    let (address_book, _) = find_address_book_address(smallet);
+    build(
+        smallet::accounts::SetAddressBookPolicy {
+            smallet: *smallet,
+            address_book,
+        },
+        smallet::instruction::SetAddressBookPolicy {
+            threshold,
+            minimum_delay,
+            entry_delay,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::add_address_book_entry], to be proposed.
+pub fn add_address_book_entry(
+    smallet: &Pubkey,
+    payer: &Pubkey,
+    recipient: Pubkey,
+    label: String,
+) -> Instruction {
+    let (address_book, _) = find_address_book_address(smallet);
+    build(
+        smallet::accounts::AddAddressBookEntry {
+            smallet: *smallet,
+            address_book,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::AddAddressBookEntry { recipient, label },
+    )
+}
+
+/// Builds [smallet::smallet::remove_address_book_entry], to be proposed.
+pub fn remove_address_book_entry(smallet: &Pubkey, recipient: Pubkey) -> Instruction {
+    let (address_book, _) = find_address_book_address(smallet);
+    build(
+        smallet::accounts::RemoveAddressBookEntry {
+            smallet: *smallet,
+            address_book,
+        },
+        smallet::instruction::RemoveAddressBookEntry { recipient },
+    )
+}
+
+/// Builds [smallet::smallet::create_subaccount_info].
+/// `registry_index` must be the current [smallet::Smallet::num_subaccounts].
+pub fn create_subaccount_info(
+    smallet: &Pubkey,
+    registry_index: u64,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    subaccount_type: SubaccountType,
+    index: u64,
+    label: Option<String>,
+) -> Instruction {
+    let (subaccount, _) = subaccount_type.find_address(smallet, index);
+    let (subaccount_info, bump) = find_subaccount_info_address(&subaccount);
+    let (registry_entry, _) = find_subaccount_registry_address(smallet, registry_index);
+    build(
+        smallet::accounts::CreateSubaccountInfo {
+            subaccount_info,
+            smallet: *smallet,
+            registry_entry,
+            owner: *owner,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::CreateSubaccountInfo {
+            _bump: bump,
+            subaccount,
+            smallet: *smallet,
+            index,
+            subaccount_type,
+            label,
+        },
+    )
+}
+
+/// Builds [smallet::smallet::update_subaccount_info], to be proposed.
+pub fn update_subaccount_info(
+    smallet: &Pubkey,
+    subaccount: &Pubkey,
+    label: Option<String>,
+) -> Instruction {
+    let (subaccount_info, _) = find_subaccount_info_address(subaccount);
+    build(
+        smallet::accounts::UpdateSubaccountInfo {
+            smallet: *smallet,
+            subaccount_info,
+        },
+        smallet::instruction::UpdateSubaccountInfo { label },
+    )
+}
+
+/// Builds [smallet::smallet::close_subaccount_info], to be proposed.
+pub fn close_subaccount_info(
+    smallet: &Pubkey,
+    subaccount: &Pubkey,
+    registry_index: u64,
+    receiver: &Pubkey,
+) -> Instruction {
+    let (subaccount_info, _) = find_subaccount_info_address(subaccount);
+    let (registry_entry, _) = find_subaccount_registry_address(smallet, registry_index);
+    build(
+        smallet::accounts::CloseSubaccountInfo {
+            smallet: *smallet,
+            subaccount_info,
+            registry_entry,
+            receiver: *receiver,
+        },
+        smallet::instruction::CloseSubaccountInfo {},
+    )
+}
+
+/// Builds [smallet::smallet::migrate_subaccount_info].
+/// `registry_index` must be the current [smallet::Smallet::num_subaccounts].
+pub fn migrate_subaccount_info(
+    smallet: &Pubkey,
+    subaccount: &Pubkey,
+    registry_index: u64,
+    payer: &Pubkey,
+) -> Instruction {
+    let (subaccount_info, _) = find_subaccount_info_address(subaccount);
+    let (registry_entry, _) = find_subaccount_registry_address(smallet, registry_index);
+    build(
+        smallet::accounts::MigrateSubaccountInfo {
+            subaccount_info,
+            smallet: *smallet,
+            registry_entry,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        smallet::instruction::MigrateSubaccountInfo {},
+    )
+}
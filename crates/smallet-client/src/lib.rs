@@ -0,0 +1,27 @@
+//! Rust client for the smallet program.
+//!
+//! This crate provides:
+//!
+//! - [pda]: functions deriving every address used by the program.
+//! - [instructions]: builders for every instruction of [smallet::smallet].
+//! - [decode]: human-readable rendering of the instructions of a transaction.
+//! - [accounts]: decoders for the program accounts, accepting both the legacy
+//!   and the current account layouts.
+//! - [events]: decoding of the events emitted by the program from logs.
+//! - [history]: reconstruction of the timeline of each smallet from its events.
+//! - [status]: the execution status of a transaction.
+//! - [rpc] (with the `rpc` feature): a minimal JSON-RPC client to fetch
+//!   accounts and send transactions.
+#![allow(clippy::too_many_arguments, clippy::result_large_err)]
+
+pub mod accounts;
+pub mod decode;
+pub mod events;
+pub mod history;
+pub mod instructions;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod status;
+
+pub use smallet;
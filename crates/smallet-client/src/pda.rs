@@ -0,0 +1,87 @@
+//! Program derived addresses of the smallet program.
+
+use anchor_lang::prelude::Pubkey;
+use smallet::SubaccountType;
+
+/// Finds the address of the [smallet::Smallet] created with `base`.
+pub fn find_smallet(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"CosmicSmallet", base.as_ref()], &smallet::ID)
+}
+
+/// Finds the address of the [smallet::Transaction] at `index`, as created by
+/// [smallet::smallet::create_transaction].
+pub fn find_transaction_address(smallet: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"CosmicTransaction",
+            smallet.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &smallet::ID,
+    )
+}
+
+/// Finds the address of a [smallet::Transaction] created by `proposer` with
+/// `nonce`, as created by [smallet::smallet::create_transaction_v2].
+pub fn find_transaction_v2_address(
+    smallet: &Pubkey,
+    proposer: &Pubkey,
+    nonce: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"CosmicTransactionV2",
+            smallet.as_ref(),
+            proposer.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &smallet::ID,
+    )
+}
+
+/// Finds a derived address of a smallet.
+pub fn find_wallet_derived_address(smallet: &Pubkey, index: u64) -> (Pubkey, u8) {
+    SubaccountType::Derived.find_address(smallet, index)
+}
+
+/// Finds an owner invoker address of a smallet.
+pub fn find_owner_invoker_address(smallet: &Pubkey, index: u64) -> (Pubkey, u8) {
+    SubaccountType::OwnerInvoker.find_address(smallet, index)
+}
+
+/// Finds the [smallet::SubaccountInfo] address of a subaccount.
+pub fn find_subaccount_info_address(subaccount: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"CosmicSubaccountInfo", subaccount.as_ref()],
+        &smallet::ID,
+    )
+}
+
+/// Finds the [smallet::SubaccountRegistryEntry] at `registry_index`.
+pub fn find_subaccount_registry_address(smallet: &Pubkey, registry_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"CosmicSubaccountRegistry",
+            smallet.as_ref(),
+            &registry_index.to_le_bytes(),
+        ],
+        &smallet::ID,
+    )
+}
+
+/// Finds the [smallet::AddressBook] of a smallet.
+pub fn find_address_book_address(smallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"CosmicAddressBook", smallet.as_ref()], &smallet::ID)
+}
+
+/// Finds the [smallet::InvokerPolicy] of the owner invoker at `index`.
+pub fn find_invoker_policy_address(smallet: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"CosmicInvokerPolicy",
+            smallet.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &smallet::ID,
+    )
+}
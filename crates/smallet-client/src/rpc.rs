@@ -0,0 +1,416 @@
+//! Minimal blocking JSON-RPC client for a Solana validator.
+//!
+//! Only the methods needed to read smallet accounts and to send transactions
+//! are implemented. Any RPC URL works, including `http://127.0.0.1:8899` for
+//! a local `solana-test-validator`.
+
+use std::path::Path;
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::Duration;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::message::Message;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::Discriminator;
+use ed25519_dalek::Signer;
+use serde_json::{json, Value};
+
+/// Errors returned by [RpcClient].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("transport error")]
+    Transport(#[from] Box<ureq::Error>),
+    #[error("io error")]
+    Io(#[from] std::io::Error),
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("invalid RPC response: {0}")]
+    InvalidResponse(String),
+    #[error("transaction {0} failed: {1}")]
+    TransactionFailed(String, String),
+    #[error("transaction {0} was not confirmed in time")]
+    ConfirmationTimeout(String),
+    #[error("invalid keypair: {0}")]
+    InvalidKeypair(String),
+}
+
+/// Result of an RPC call.
+pub type RpcResult<T> = std::result::Result<T, RpcError>;
+
+/// An ed25519 keypair, as stored by `solana-keygen`.
+pub struct Keypair(ed25519_dalek::Keypair);
+
+impl Keypair {
+    /// Reads a keypair file containing a JSON array of 64 bytes.
+    pub fn read_from_file(path: impl AsRef<Path>) -> RpcResult<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let bytes: Vec<u8> = serde_json::from_str(&contents)
+            .map_err(|e| RpcError::InvalidKeypair(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Creates a keypair from its 64 byte secret and public key.
+    pub fn from_bytes(bytes: &[u8]) -> RpcResult<Self> {
+        ed25519_dalek::Keypair::from_bytes(bytes)
+            .map(Keypair)
+            .map_err(|e| RpcError::InvalidKeypair(e.to_string()))
+    }
+
+    /// The public key of the keypair.
+    pub fn pubkey(&self) -> Pubkey {
+        Pubkey::new_from_array(self.0.public.to_bytes())
+    }
+
+    /// Signs `message`.
+    pub fn sign(&self, message: &[u8]) -> [u8; 64] {
+        self.0.sign(message).to_bytes()
+    }
+}
+
+/// An account as returned by the RPC.
+#[derive(Clone, Debug)]
+pub struct RpcAccount {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// A transaction signature as returned by `getSignaturesForAddress`.
+#[derive(Clone, Debug)]
+pub struct RpcSignature {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// True if the transaction failed.
+    pub failed: bool,
+}
+
+/// A `getProgramAccounts` filter.
+#[derive(Clone, Debug)]
+pub enum RpcFilter {
+    /// Account data at `offset` starts with `bytes`.
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    /// Account data is exactly this long.
+    DataSize(u64),
+}
+
+impl RpcFilter {
+    /// Matches accounts of the Anchor account type `T`.
+    pub fn discriminator<T: Discriminator>() -> Self {
+        RpcFilter::Memcmp {
+            offset: 0,
+            bytes: T::discriminator().to_vec(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            RpcFilter::Memcmp { offset, bytes } => json!({
+                "memcmp": { "offset": offset, "bytes": bs58::encode(bytes).into_string() }
+            }),
+            RpcFilter::DataSize(size) => json!({ "dataSize": size }),
+        }
+    }
+}
+
+/// Blocking JSON-RPC client.
+pub struct RpcClient {
+    url: String,
+    commitment: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    /// Creates a client for `url` using the `confirmed` commitment.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::new_with_commitment(url, "confirmed")
+    }
+
+    /// Creates a client for `url` using `commitment`.
+    pub fn new_with_commitment(url: impl Into<String>, commitment: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            commitment: commitment.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// The RPC URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Performs a raw JSON-RPC call, returning its `result`.
+    pub fn call(&self, method: &str, params: Value) -> RpcResult<Value> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let mut response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(request)
+            .map_err(Box::new)?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Fetches an account, returning [None] if it does not exist.
+    pub fn get_account(&self, address: &Pubkey) -> RpcResult<Option<RpcAccount>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+        match &result["value"] {
+            Value::Null => Ok(None),
+            value => parse_account(value).map(Some),
+        }
+    }
+
+    /// Fetches up to 100 accounts at once, in the order of `addresses`.
+    pub fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> RpcResult<Vec<Option<RpcAccount>>> {
+        let addresses: Vec<String> = addresses.iter().map(Pubkey::to_string).collect();
+        let result = self.call(
+            "getMultipleAccounts",
+            json!([addresses, { "encoding": "base64", "commitment": self.commitment }]),
+        )?;
+        let values = result["value"]
+            .as_array()
+            .ok_or_else(|| RpcError::InvalidResponse("expected an array".to_string()))?;
+        values
+            .iter()
+            .map(|value| match value {
+                Value::Null => Ok(None),
+                value => parse_account(value).map(Some),
+            })
+            .collect()
+    }
+
+    /// Fetches all accounts owned by `program_id` matching every filter.
+    pub fn get_program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[RpcFilter],
+    ) -> RpcResult<Vec<(Pubkey, RpcAccount)>> {
+        let filters: Vec<Value> = filters.iter().map(RpcFilter::to_json).collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), {
+                "encoding": "base64",
+                "commitment": self.commitment,
+                "filters": filters,
+            }]),
+        )?;
+        let entries = result
+            .as_array()
+            .ok_or_else(|| RpcError::InvalidResponse("expected an array".to_string()))?;
+        entries
+            .iter()
+            .map(|entry| {
+                let pubkey = parse_pubkey(&entry["pubkey"])?;
+                Ok((pubkey, parse_account(&entry["account"])?))
+            })
+            .collect()
+    }
+
+    /// Fetches signatures of transactions involving `address`, newest first.
+    /// `before` and `until` are exclusive bounds; at most `limit` (up to 1000)
+    /// signatures are returned.
+    pub fn get_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+        limit: usize,
+    ) -> RpcResult<Vec<RpcSignature>> {
+        let mut config = json!({ "limit": limit, "commitment": self.commitment });
+        if let Some(before) = before {
+            config["before"] = json!(before);
+        }
+        if let Some(until) = until {
+            config["until"] = json!(until);
+        }
+        let result = self.call(
+            "getSignaturesForAddress",
+            json!([address.to_string(), config]),
+        )?;
+        let entries = result
+            .as_array()
+            .ok_or_else(|| RpcError::InvalidResponse("expected an array".to_string()))?;
+        entries
+            .iter()
+            .map(|entry| {
+                Ok(RpcSignature {
+                    signature: entry["signature"]
+                        .as_str()
+                        .ok_or_else(|| RpcError::InvalidResponse("missing signature".to_string()))?
+                        .to_string(),
+                    slot: entry["slot"].as_u64().unwrap_or_default(),
+                    block_time: entry["blockTime"].as_i64(),
+                    failed: !entry["err"].is_null(),
+                })
+            })
+            .collect()
+    }
+
+    /// Fetches the log messages of a transaction, or [None] if it is unknown.
+    pub fn get_transaction_logs(&self, signature: &str) -> RpcResult<Option<Vec<String>>> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": self.commitment,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        let logs = result["meta"]["logMessages"]
+            .as_array()
+            .ok_or_else(|| RpcError::InvalidResponse("missing log messages".to_string()))?;
+        Ok(Some(
+            logs.iter()
+                .filter_map(|line| line.as_str().map(str::to_string))
+                .collect(),
+        ))
+    }
+
+    /// Fetches the current [Clock] of the cluster.
+    pub fn get_clock(&self) -> RpcResult<Clock> {
+        let account = self
+            .get_account(&sysvar::clock::ID)?
+            .ok_or_else(|| RpcError::InvalidResponse("missing clock sysvar".to_string()))?;
+        bincode::deserialize(&account.data)
+            .map_err(|e| RpcError::InvalidResponse(e.to_string()))
+    }
+
+    /// Fetches the latest blockhash.
+    pub fn get_latest_blockhash(&self) -> RpcResult<Hash> {
+        let result = self.call(
+            "getLatestBlockhash",
+            json!([{ "commitment": self.commitment }]),
+        )?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| RpcError::InvalidResponse("missing blockhash".to_string()))?;
+        Hash::from_str(blockhash).map_err(|e| RpcError::InvalidResponse(e.to_string()))
+    }
+
+    /// Signs `instructions` with `signers` and sends them in a single transaction.
+    /// The first signer pays the fees. Returns the transaction signature.
+    pub fn send_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> RpcResult<String> {
+        let payer = signers
+            .first()
+            .ok_or_else(|| RpcError::InvalidKeypair("no fee payer".to_string()))?
+            .pubkey();
+        let mut message = Message::new(instructions, Some(&payer));
+        message.recent_blockhash = self.get_latest_blockhash()?;
+        let message_data = message.serialize();
+
+        let num_signers = message.header.num_required_signatures as usize;
+        let mut wire = vec![];
+        encode_length(&mut wire, num_signers);
+        for key in &message.account_keys[..num_signers] {
+            let signer = signers
+                .iter()
+                .find(|signer| signer.pubkey() == *key)
+                .ok_or_else(|| RpcError::InvalidKeypair(format!("missing signer {}", key)))?;
+            wire.extend_from_slice(&signer.sign(&message_data));
+        }
+        wire.extend_from_slice(&message_data);
+
+        let result = self.call(
+            "sendTransaction",
+            json!([base64::encode(&wire), {
+                "encoding": "base64",
+                "preflightCommitment": self.commitment,
+            }]),
+        )?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| RpcError::InvalidResponse("missing signature".to_string()))
+    }
+
+    /// Sends a transaction and waits for it to reach the client's commitment.
+    pub fn send_and_confirm_transaction(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> RpcResult<String> {
+        let signature = self.send_transaction(instructions, signers)?;
+        for _ in 0..60 {
+            let result = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(RpcError::TransactionFailed(
+                        signature,
+                        status["err"].to_string(),
+                    ));
+                }
+                match status["confirmationStatus"].as_str() {
+                    Some("finalized") => return Ok(signature),
+                    Some("confirmed") if self.commitment != "finalized" => return Ok(signature),
+                    _ => {}
+                }
+            }
+            sleep(Duration::from_millis(500));
+        }
+        Err(RpcError::ConfirmationTimeout(signature))
+    }
+}
+
+fn parse_pubkey(value: &Value) -> RpcResult<Pubkey> {
+    value
+        .as_str()
+        .and_then(|s| Pubkey::from_str(s).ok())
+        .ok_or_else(|| RpcError::InvalidResponse(format!("invalid pubkey {}", value)))
+}
+
+fn parse_account(value: &Value) -> RpcResult<RpcAccount> {
+    let data = value["data"][0]
+        .as_str()
+        .and_then(|data| base64::decode(data).ok())
+        .ok_or_else(|| RpcError::InvalidResponse("invalid account data".to_string()))?;
+    Ok(RpcAccount {
+        lamports: value["lamports"].as_u64().unwrap_or_default(),
+        owner: parse_pubkey(&value["owner"])?,
+        data,
+    })
+}
+
+// Encodes a compact-u16 length prefix.
+fn encode_length(out: &mut Vec<u8>, mut len: usize) {
+    loop {
+        let mut byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
@@ -0,0 +1,116 @@
+//! Execution status of a [Transaction], mirroring the checks performed by
+//! [smallet::smallet::execute_transaction].
+
+use std::fmt;
+
+use smallet::{AddressBook, Smallet, Transaction, NO_ETA};
+
+/// Whether a [Transaction] can be executed, and if not, why.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction was executed at the given time.
+    Executed { at: i64 },
+    /// The owner set of the smallet changed after the transaction was proposed.
+    /// The transaction can never be executed.
+    OwnerSetChanged,
+    /// The grace period after the ETA has elapsed.
+    /// The transaction can never be executed.
+    Expired { at: i64 },
+    /// Enough owners vetoed the transaction before its ETA.
+    /// The transaction can never be executed.
+    Vetoed { at: i64 },
+    /// The timelock tier of the transaction no longer exists on the smallet.
+    /// The transaction cannot be executed unless the tier is added back.
+    InvalidTier { tier: u8 },
+    /// The transaction pays address book entries but the smallet has no
+    /// address book. The transaction cannot be executed unless one is created.
+    MissingAddressBook,
+    /// Not enough owners approved the transaction, not counting expired
+    /// approvals.
+    NeedsApprovals { approvals: u64, threshold: u64 },
+    /// The transaction is approved but its ETA has not passed yet.
+    Timelocked { eta: i64 },
+    /// The transaction may be executed now.
+    Ready,
+}
+
+impl TransactionStatus {
+    /// Computes the status of `tx` at the unix timestamp `now`. Payments of
+    /// address book entries need the `address_book` of the smallet; whether
+    /// their recipients are still trusted is only checked at execution.
+    pub fn of(
+        smallet: &Smallet,
+        address_book: Option<&AddressBook>,
+        tx: &Transaction,
+        now: i64,
+    ) -> Self {
+        if tx.executed_at != -1 {
+            return TransactionStatus::Executed { at: tx.executed_at };
+        }
+        if tx.owner_set_seqno != smallet.owner_set_seqno {
+            return TransactionStatus::OwnerSetChanged;
+        }
+        if let Some(at) = tx.vetoed_at {
+            return TransactionStatus::Vetoed { at };
+        }
+        if tx.eta != NO_ETA {
+            let expires_at = tx.eta.saturating_add(smallet.grace_period);
+            if now > expires_at {
+                return TransactionStatus::Expired { at: expires_at };
+            }
+        }
+        let threshold = match (tx.address_book, address_book) {
+            (true, Some(address_book)) => smallet.address_book_threshold(address_book),
+            (true, None) => return TransactionStatus::MissingAddressBook,
+            (false, _) => match smallet.threshold_for(tx.tier) {
+                Ok(threshold) => threshold,
+                Err(_) => {
+                    return TransactionStatus::InvalidTier {
+                        tier: tx.tier.unwrap_or_default(),
+                    }
+                }
+            },
+        };
+        let approvals = tx.num_fresh_signers(smallet.approval_ttl, now) as u64;
+        if approvals < threshold && !smallet.is_optimistic(tx) {
+            return TransactionStatus::NeedsApprovals {
+                approvals,
+                threshold,
+            };
+        }
+        if now < tx.eta {
+            return TransactionStatus::Timelocked { eta: tx.eta };
+        }
+        TransactionStatus::Ready
+    }
+
+    /// True if the transaction is neither executed nor permanently unexecutable.
+    pub fn is_pending(&self) -> bool {
+        !matches!(
+            self,
+            TransactionStatus::Executed { .. }
+                | TransactionStatus::OwnerSetChanged
+                | TransactionStatus::Expired { .. }
+                | TransactionStatus::Vetoed { .. }
+        )
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionStatus::Executed { at } => write!(f, "executed at {}", at),
+            TransactionStatus::OwnerSetChanged => write!(f, "stale (owner set changed)"),
+            TransactionStatus::Expired { at } => write!(f, "stale (expired at {})", at),
+            TransactionStatus::Vetoed { at } => write!(f, "vetoed at {}", at),
+            TransactionStatus::InvalidTier { tier } => write!(f, "invalid tier #{}", tier),
+            TransactionStatus::MissingAddressBook => write!(f, "missing address book"),
+            TransactionStatus::NeedsApprovals {
+                approvals,
+                threshold,
+            } => write!(f, "needs approvals ({}/{})", approvals, threshold),
+            TransactionStatus::Timelocked { eta } => write!(f, "timelocked until {}", eta),
+            TransactionStatus::Ready => write!(f, "ready"),
+        }
+    }
+}
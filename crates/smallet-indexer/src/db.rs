@@ -0,0 +1,840 @@
+//! SQLite schema and writes.
+
+use anchor_lang::prelude::*;
+use anchor_lang::AnchorSerialize;
+use rusqlite::{params, Connection, OptionalExtension};
+use smallet_client::decode::{
+    decode, describe, describe_post_condition, describe_pre_condition, Labels,
+};
+use smallet_client::events::SmalletEvent;
+use smallet_client::smallet::{
+    AddressBook, Smallet, SubaccountInfo, Transaction, TransferAsset, NO_ETA, NO_INDEX,
+};
+
+/// Version of the schema, stored in `PRAGMA user_version`. Bump it whenever a
+/// view changes, so that the views of existing databases are recreated.
+pub const SCHEMA_VERSION: i64 = 2;
+
+/// Tables and indexes. New tables may be added; existing tables are never
+/// altered.
+const TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS smallets (
+    address TEXT PRIMARY KEY,
+    base TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    threshold INTEGER NOT NULL,
+    minimum_delay INTEGER NOT NULL,
+    grace_period INTEGER NOT NULL,
+    owner_set_seqno INTEGER NOT NULL,
+    num_transactions INTEGER NOT NULL,
+    num_subaccounts INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+
+-- The current owners of each smallet. `position` is the index of the owner in
+-- `Smallet::owners`, which `transaction_approvals.owner_index` refers to.
+CREATE TABLE IF NOT EXISTS smallet_owners (
+    smallet TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    owner TEXT NOT NULL,
+    PRIMARY KEY (smallet, position)
+);
+CREATE INDEX IF NOT EXISTS smallet_owners_owner ON smallet_owners (owner);
+
+-- The timelock tiers of each smallet. `position` is the index of the tier in
+-- `Smallet::tiers`, which `transaction_tiers.tier` refers to.
+CREATE TABLE IF NOT EXISTS smallet_tiers (
+    smallet TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    minimum_delay INTEGER NOT NULL,
+    threshold INTEGER NOT NULL,
+    -- Base64 of the borsh serialized `Vec<AllowedInstruction>`.
+    allowed_instructions TEXT NOT NULL,
+    PRIMARY KEY (smallet, position)
+);
+
+-- The approval TTL of each smallet whose approvals expire.
+CREATE TABLE IF NOT EXISTS approval_ttls (
+    smallet TEXT PRIMARY KEY,
+    approval_ttl INTEGER NOT NULL
+);
+
+-- The FIFO cursor of each smallet that executes its transactions in order.
+CREATE TABLE IF NOT EXISTS fifo_cursors (
+    smallet TEXT PRIMARY KEY,
+    cursor INTEGER NOT NULL
+);
+
+-- The optimistic mode of each smallet that enabled it, and its proposers.
+CREATE TABLE IF NOT EXISTS optimistic_modes (
+    smallet TEXT PRIMARY KEY,
+    objection_threshold INTEGER NOT NULL,
+    challenge_period INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS optimistic_proposers (
+    smallet TEXT NOT NULL,
+    proposer TEXT NOT NULL,
+    PRIMARY KEY (smallet, proposer)
+);
+
+CREATE TABLE IF NOT EXISTS transactions (
+    address TEXT PRIMARY KEY,
+    smallet TEXT NOT NULL,
+    -- NULL until an index is assigned.
+    tx_index INTEGER,
+    proposer TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    -- NULL if the transaction has no timelock.
+    eta INTEGER,
+    owner_set_seqno INTEGER NOT NULL,
+    num_approvals INTEGER NOT NULL,
+    -- NULL until executed.
+    executed_at INTEGER,
+    executor TEXT,
+    is_program_upgrade INTEGER NOT NULL,
+    -- Base64 of the borsh serialized `Vec<TXInstruction>`.
+    instructions TEXT NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transactions_smallet ON transactions (smallet);
+
+-- The instructions of each transaction, decoded for display. `name` is NULL if
+-- the instruction is not of a known program.
+CREATE TABLE IF NOT EXISTS transaction_instructions (
+    transaction_address TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    program_id TEXT NOT NULL,
+    name TEXT,
+    summary TEXT NOT NULL,
+    PRIMARY KEY (transaction_address, position)
+);
+
+-- The pre-conditions of each transaction, described for display.
+CREATE TABLE IF NOT EXISTS transaction_pre_conditions (
+    transaction_address TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    account TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    PRIMARY KEY (transaction_address, position)
+);
+
+-- The outflow limits and minimum balances of each transaction, described for
+-- display.
+CREATE TABLE IF NOT EXISTS transaction_post_conditions (
+    transaction_address TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    account TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    PRIMARY KEY (transaction_address, position)
+);
+
+-- The transfer of each transaction proposed via `propose_transfer`. `mint`
+-- is NULL for SOL.
+CREATE TABLE IF NOT EXISTS transaction_transfers (
+    transaction_address TEXT PRIMARY KEY,
+    mint TEXT,
+    amount INTEGER NOT NULL,
+    source_index INTEGER NOT NULL,
+    recipient TEXT NOT NULL
+);
+
+-- The timelock tier declared by each transaction, if any.
+CREATE TABLE IF NOT EXISTS transaction_tiers (
+    transaction_address TEXT PRIMARY KEY,
+    tier INTEGER NOT NULL
+);
+
+-- Indexes of the transactions that must be executed before each transaction.
+CREATE TABLE IF NOT EXISTS transaction_prerequisites (
+    transaction_address TEXT NOT NULL,
+    prerequisite_index INTEGER NOT NULL,
+    PRIMARY KEY (transaction_address, prerequisite_index)
+);
+
+-- Transactions proposed optimistically.
+CREATE TABLE IF NOT EXISTS optimistic_transactions (
+    transaction_address TEXT PRIMARY KEY
+);
+
+-- Transactions paying address book entries, to which the threshold of the
+-- address book of their smallet applies.
+CREATE TABLE IF NOT EXISTS address_book_transactions (
+    transaction_address TEXT PRIMARY KEY
+);
+
+-- Owners that approved each transaction, by their position in the owner set
+-- the transaction was proposed under.
+CREATE TABLE IF NOT EXISTS transaction_approvals (
+    transaction_address TEXT NOT NULL,
+    owner_index INTEGER NOT NULL,
+    PRIMARY KEY (transaction_address, owner_index)
+);
+
+-- When each approval in `transaction_approvals` was given, if known.
+-- Approvals older than the `approval_ttl` of the smallet do not count.
+CREATE TABLE IF NOT EXISTS transaction_approval_times (
+    transaction_address TEXT NOT NULL,
+    owner_index INTEGER NOT NULL,
+    approved_at INTEGER NOT NULL,
+    PRIMARY KEY (transaction_address, owner_index)
+);
+
+-- Owners that vetoed each transaction, like `transaction_approvals`.
+CREATE TABLE IF NOT EXISTS transaction_vetoes (
+    transaction_address TEXT NOT NULL,
+    owner_index INTEGER NOT NULL,
+    PRIMARY KEY (transaction_address, owner_index)
+);
+
+-- Transactions blocked by reaching the veto threshold of their smallet.
+CREATE TABLE IF NOT EXISTS vetoed_transactions (
+    transaction_address TEXT PRIMARY KEY,
+    vetoed_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS subaccounts (
+    subaccount_info TEXT PRIMARY KEY,
+    subaccount TEXT NOT NULL,
+    smallet TEXT NOT NULL,
+    subaccount_type TEXT NOT NULL,
+    subaccount_index INTEGER NOT NULL,
+    version INTEGER NOT NULL,
+    registry_index INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    label TEXT,
+    updated_slot INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS subaccounts_smallet ON subaccounts (smallet);
+
+-- The address book of each smallet that created one, and its entries.
+-- Entries are trusted from `trusted_at` on.
+CREATE TABLE IF NOT EXISTS address_books (
+    address TEXT PRIMARY KEY,
+    smallet TEXT NOT NULL UNIQUE,
+    threshold INTEGER NOT NULL,
+    minimum_delay INTEGER NOT NULL,
+    entry_delay INTEGER NOT NULL,
+    updated_slot INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS address_book_entries (
+    address_book TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    label TEXT NOT NULL,
+    trusted_at INTEGER NOT NULL,
+    PRIMARY KEY (address_book, recipient)
+);
+
+CREATE TABLE IF NOT EXISTS events (
+    signature TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    slot INTEGER NOT NULL,
+    block_time INTEGER,
+    smallet TEXT NOT NULL,
+    name TEXT NOT NULL,
+    -- The transaction account the event refers to, if any.
+    transaction_address TEXT,
+    timestamp INTEGER NOT NULL,
+    -- Base64 of the event as logged: discriminator and borsh serialized data.
+    data TEXT NOT NULL,
+    PRIMARY KEY (signature, position)
+);
+CREATE INDEX IF NOT EXISTS events_smallet ON events (smallet, slot);
+CREATE INDEX IF NOT EXISTS events_transaction ON events (transaction_address);
+
+-- Newest processed signature of the program.
+CREATE TABLE IF NOT EXISTS cursor (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    signature TEXT NOT NULL,
+    slot INTEGER NOT NULL
+);
+"#;
+
+/// Views, dropped and recreated when [SCHEMA_VERSION] changes.
+const VIEWS: &str = r#"
+DROP VIEW IF EXISTS pending_transactions_by_owner;
+DROP VIEW IF EXISTS pending_transactions;
+
+-- Transactions that may still be executed: not executed, not vetoed and
+-- proposed under the current owner set. Expired transactions are included; filter on
+-- `expires_at` to exclude them.
+CREATE VIEW pending_transactions AS
+SELECT
+    t.address,
+    t.smallet,
+    t.tx_index,
+    t.proposer,
+    t.eta,
+    CASE WHEN t.eta IS NULL THEN NULL ELSE t.eta + s.grace_period END AS expires_at,
+    t.num_approvals,
+    -- Optimistic transactions of a still authorized proposer need no approvals,
+    -- and payments of address book entries need those of the address book.
+    -- Untiered transactions are held to the strictest tier.
+    CASE
+        WHEN op.proposer IS NOT NULL THEN 0
+        WHEN abt.transaction_address IS NOT NULL THEN ab.threshold
+        ELSE COALESCE(st.threshold, MAX(s.threshold, COALESCE(
+            (SELECT MAX(x.threshold) FROM smallet_tiers x WHERE x.smallet = t.smallet), 0
+        )))
+    END AS threshold
+FROM transactions t
+JOIN smallets s ON s.address = t.smallet AND s.owner_set_seqno = t.owner_set_seqno
+LEFT JOIN transaction_tiers tt ON tt.transaction_address = t.address
+LEFT JOIN smallet_tiers st ON st.smallet = t.smallet AND st.position = tt.tier
+LEFT JOIN optimistic_transactions ot ON ot.transaction_address = t.address
+LEFT JOIN optimistic_proposers op
+    ON ot.transaction_address IS NOT NULL AND op.smallet = t.smallet AND op.proposer = t.proposer
+LEFT JOIN address_book_transactions abt ON abt.transaction_address = t.address
+LEFT JOIN address_books ab ON ab.smallet = t.smallet
+WHERE t.executed_at IS NULL
+    AND NOT EXISTS (
+        SELECT 1 FROM vetoed_transactions v WHERE v.transaction_address = t.address
+    );
+
+-- Pending transactions for each owner of their smallet, e.g.
+-- `SELECT * FROM pending_transactions_by_owner WHERE owner = ?`.
+CREATE VIEW pending_transactions_by_owner AS
+SELECT
+    o.owner,
+    p.*,
+    EXISTS (
+        SELECT 1 FROM transaction_approvals a
+        WHERE a.transaction_address = p.address AND a.owner_index = o.position
+    ) AS approved_by_owner
+FROM pending_transactions p
+JOIN smallet_owners o ON o.smallet = p.smallet;
+"#;
+
+/// Opens the database at `path`, creating or migrating the schema if needed.
+pub fn open(path: &str) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+    migrate(&conn)?;
+    Ok(conn)
+}
+
+/// Creates the missing tables, and recreates the views if the database was
+/// created by an older version of the indexer.
+pub fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    let db_tx = conn.unchecked_transaction()?;
+    db_tx.execute_batch(TABLES)?;
+    let version: i64 = db_tx.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version < SCHEMA_VERSION {
+        db_tx.execute_batch(VIEWS)?;
+        db_tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    }
+    db_tx.commit()
+}
+
+/// Reads the newest processed signature.
+pub fn cursor(conn: &Connection) -> rusqlite::Result<Option<String>> {
+    conn.query_row("SELECT signature FROM cursor WHERE id = 0", [], |row| {
+        row.get(0)
+    })
+    .optional()
+}
+
+/// Records the newest processed signature.
+pub fn set_cursor(conn: &Connection, signature: &str, slot: u64) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO cursor (id, signature, slot) VALUES (0, ?1, ?2)
+         ON CONFLICT (id) DO UPDATE SET signature = ?1, slot = ?2",
+        params![signature, slot],
+    )?;
+    Ok(())
+}
+
+/// Inserts or replaces a smallet, its owners, its tiers, its approval TTL and
+/// its optimistic mode.
+pub fn upsert_smallet(
+    conn: &Connection,
+    address: &Pubkey,
+    smallet: &Smallet,
+    slot: u64,
+) -> rusqlite::Result<()> {
+    let address = address.to_string();
+    conn.execute(
+        "INSERT OR REPLACE INTO smallets (address, base, version, threshold, minimum_delay,
+            grace_period, owner_set_seqno, num_transactions, num_subaccounts, updated_slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            address,
+            smallet.base.to_string(),
+            smallet.version,
+            smallet.threshold,
+            smallet.minimum_delay,
+            smallet.grace_period,
+            smallet.owner_set_seqno,
+            smallet.num_transactions,
+            smallet.num_subaccounts,
+            slot,
+        ],
+    )?;
+    conn.execute(
+        "DELETE FROM smallet_owners WHERE smallet = ?1",
+        params![address],
+    )?;
+    for (position, owner) in smallet.owners.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO smallet_owners (smallet, position, owner) VALUES (?1, ?2, ?3)",
+            params![address, position, owner.to_string()],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM smallet_tiers WHERE smallet = ?1",
+        params![address],
+    )?;
+    for (position, tier) in smallet.tiers.iter().enumerate() {
+        let allowed_instructions = tier
+            .allowed_instructions
+            .try_to_vec()
+            .expect("allowed instructions serialize");
+        conn.execute(
+            "INSERT INTO smallet_tiers (smallet, position, name, minimum_delay, threshold,
+                allowed_instructions)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                address,
+                position,
+                tier.name,
+                tier.minimum_delay,
+                tier.threshold,
+                base64::encode(allowed_instructions),
+            ],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM approval_ttls WHERE smallet = ?1",
+        params![address],
+    )?;
+    if smallet.approval_ttl != 0 {
+        conn.execute(
+            "INSERT INTO approval_ttls (smallet, approval_ttl) VALUES (?1, ?2)",
+            params![address, smallet.approval_ttl],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM fifo_cursors WHERE smallet = ?1",
+        params![address],
+    )?;
+    if let Some(cursor) = smallet.fifo_cursor {
+        conn.execute(
+            "INSERT INTO fifo_cursors (smallet, cursor) VALUES (?1, ?2)",
+            params![address, cursor],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM optimistic_modes WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM optimistic_proposers WHERE smallet = ?1",
+        params![address],
+    )?;
+    if let Some(config) = &smallet.optimistic {
+        conn.execute(
+            "INSERT INTO optimistic_modes (smallet, objection_threshold, challenge_period)
+             VALUES (?1, ?2, ?3)",
+            params![address, config.objection_threshold, config.challenge_period],
+        )?;
+        for proposer in &config.proposers {
+            conn.execute(
+                "INSERT OR IGNORE INTO optimistic_proposers (smallet, proposer) VALUES (?1, ?2)",
+                params![address, proposer.to_string()],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Inserts or replaces a transaction, its tier, whether it is optimistic, its
+/// approvals, its vetoes and its decoded instructions.
+pub fn upsert_transaction(
+    conn: &Connection,
+    address: &Pubkey,
+    tx: &Transaction,
+    slot: u64,
+) -> rusqlite::Result<()> {
+    let address = address.to_string();
+    let instructions = tx
+        .instructions
+        .try_to_vec()
+        .expect("instructions serialize");
+    conn.execute(
+        "INSERT OR REPLACE INTO transactions (address, smallet, tx_index, proposer, version, eta,
+            owner_set_seqno, num_approvals, executed_at, executor, is_program_upgrade,
+            instructions, updated_slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            address,
+            tx.smallet.to_string(),
+            (tx.index != NO_INDEX).then_some(tx.index),
+            tx.proposer.to_string(),
+            tx.version,
+            (tx.eta != NO_ETA).then_some(tx.eta),
+            tx.owner_set_seqno,
+            tx.num_signers(),
+            (tx.executed_at != -1).then_some(tx.executed_at),
+            (tx.executed_at != -1).then(|| tx.executor.to_string()),
+            tx.program_upgrade.is_some(),
+            base64::encode(instructions),
+            slot,
+        ],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_tiers WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    if let Some(tier) = tx.tier {
+        conn.execute(
+            "INSERT INTO transaction_tiers (transaction_address, tier) VALUES (?1, ?2)",
+            params![address, tier],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM optimistic_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    if tx.optimistic {
+        conn.execute(
+            "INSERT INTO optimistic_transactions (transaction_address) VALUES (?1)",
+            params![address],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM address_book_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    if tx.address_book {
+        conn.execute(
+            "INSERT INTO address_book_transactions (transaction_address) VALUES (?1)",
+            params![address],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_prerequisites WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    for prerequisite in tx.prerequisites.iter() {
+        conn.execute(
+            "INSERT OR IGNORE INTO transaction_prerequisites (transaction_address,
+                prerequisite_index)
+             VALUES (?1, ?2)",
+            params![address, prerequisite],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_approvals WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_approval_times WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    for (owner_index, _) in tx.signers.iter().enumerate().filter(|(_, signed)| **signed) {
+        conn.execute(
+            "INSERT INTO transaction_approvals (transaction_address, owner_index) VALUES (?1, ?2)",
+            params![address, owner_index],
+        )?;
+        match tx.approved_at.get(owner_index) {
+            Some(&approved_at) if approved_at != 0 => {
+                conn.execute(
+                    "INSERT INTO transaction_approval_times (transaction_address, owner_index,
+                        approved_at)
+                     VALUES (?1, ?2, ?3)",
+                    params![address, owner_index, approved_at],
+                )?;
+            }
+            _ => {}
+        }
+    }
+    conn.execute(
+        "DELETE FROM transaction_vetoes WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    for (owner_index, _) in tx.vetoes.iter().enumerate().filter(|(_, vetoed)| **vetoed) {
+        conn.execute(
+            "INSERT INTO transaction_vetoes (transaction_address, owner_index) VALUES (?1, ?2)",
+            params![address, owner_index],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM vetoed_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    if let Some(vetoed_at) = tx.vetoed_at {
+        conn.execute(
+            "INSERT INTO vetoed_transactions (transaction_address, vetoed_at) VALUES (?1, ?2)",
+            params![address, vetoed_at],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_instructions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    let labels = Labels::for_smallet_address(&tx.smallet);
+    for (position, instruction) in tx.instructions.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO transaction_instructions (transaction_address, position, program_id,
+                name, summary)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                address,
+                position,
+                instruction.program_id.to_string(),
+                decode(instruction, &labels).map(|decoded| decoded.name),
+                describe(instruction, &labels),
+            ],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_pre_conditions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    for (position, pre_condition) in tx.pre_conditions.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO transaction_pre_conditions (transaction_address, position, account,
+                summary)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                address,
+                position,
+                pre_condition.account.to_string(),
+                describe_pre_condition(pre_condition, &labels),
+            ],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_transfers WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    if let Some(transfer) = &tx.transfer {
+        let mint = match transfer.asset {
+            TransferAsset::Sol => None,
+            TransferAsset::Token { mint } => Some(mint.to_string()),
+        };
+        conn.execute(
+            "INSERT INTO transaction_transfers (transaction_address, mint, amount, source_index,
+                recipient)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                address,
+                mint,
+                transfer.amount,
+                transfer.source_index,
+                transfer.recipient.to_string(),
+            ],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM transaction_post_conditions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    for (position, post_condition) in tx.post_conditions.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO transaction_post_conditions (transaction_address, position, account,
+                summary)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                address,
+                position,
+                post_condition.account.to_string(),
+                describe_post_condition(post_condition, &labels),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Inserts or replaces a sub-account info.
+pub fn upsert_subaccount_info(
+    conn: &Connection,
+    address: &Pubkey,
+    info: &SubaccountInfo,
+    slot: u64,
+) -> rusqlite::Result<()> {
+    let (subaccount, _) = info.subaccount_type.find_address(&info.smallet, info.index);
+    conn.execute(
+        "INSERT OR REPLACE INTO subaccounts (subaccount_info, subaccount, smallet, subaccount_type,
+            subaccount_index, version, registry_index, created_at, label, updated_slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            address.to_string(),
+            subaccount.to_string(),
+            info.smallet.to_string(),
+            format!("{:?}", info.subaccount_type),
+            info.index,
+            info.version,
+            info.registry_index,
+            info.created_at,
+            info.label,
+            slot,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Inserts or replaces an address book and its entries.
+pub fn upsert_address_book(
+    conn: &Connection,
+    address: &Pubkey,
+    address_book: &AddressBook,
+    slot: u64,
+) -> rusqlite::Result<()> {
+    let address = address.to_string();
+    conn.execute(
+        "INSERT OR REPLACE INTO address_books (address, smallet, threshold, minimum_delay,
+            entry_delay, updated_slot)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            address,
+            address_book.smallet.to_string(),
+            address_book.threshold,
+            address_book.minimum_delay,
+            address_book.entry_delay,
+            slot,
+        ],
+    )?;
+    conn.execute(
+        "DELETE FROM address_book_entries WHERE address_book = ?1",
+        params![address],
+    )?;
+    for entry in address_book.entries.iter() {
+        conn.execute(
+            "INSERT OR REPLACE INTO address_book_entries (address_book, recipient, label,
+                trusted_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                address,
+                entry.recipient.to_string(),
+                entry.label,
+                entry.trusted_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Removes a closed account from every table keyed by its address.
+pub fn delete_account(conn: &Connection, address: &Pubkey) -> rusqlite::Result<()> {
+    let address = address.to_string();
+    conn.execute("DELETE FROM smallets WHERE address = ?1", params![address])?;
+    conn.execute(
+        "DELETE FROM smallet_owners WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM smallet_tiers WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM approval_ttls WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM fifo_cursors WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM optimistic_modes WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM optimistic_proposers WHERE smallet = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transactions WHERE address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_tiers WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM optimistic_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM address_book_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_prerequisites WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_approvals WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_approval_times WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_vetoes WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM vetoed_transactions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_instructions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_pre_conditions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_post_conditions WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM transaction_transfers WHERE transaction_address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM subaccounts WHERE subaccount_info = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM address_books WHERE address = ?1",
+        params![address],
+    )?;
+    conn.execute(
+        "DELETE FROM address_book_entries WHERE address_book = ?1",
+        params![address],
+    )?;
+    Ok(())
+}
+
+/// Records an event. Recording the same event twice is a no-op.
+pub fn insert_event(
+    conn: &Connection,
+    signature: &str,
+    position: usize,
+    slot: u64,
+    block_time: Option<i64>,
+    transaction: Option<&Pubkey>,
+    event: &SmalletEvent,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO events (signature, position, slot, block_time, smallet, name,
+            transaction_address, timestamp, data)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            signature,
+            position,
+            slot,
+            block_time,
+            event.smallet().to_string(),
+            event.name(),
+            transaction.map(Pubkey::to_string),
+            event.timestamp(),
+            base64::encode(event.data()),
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Follows the smallet program and writes its accounts and events to SQLite.
+
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use rusqlite::Connection;
+use smallet_client::accounts::{
+    decode_address_book, decode_smallet, decode_subaccount_info, decode_transaction,
+};
+use smallet_client::events::{parse_logs, SmalletEvent};
+use smallet_client::pda::find_subaccount_info_address;
+use smallet_client::rpc::{RpcAccount, RpcClient, RpcFilter, RpcSignature};
+use smallet_client::smallet::{AddressBook, Smallet, SubaccountInfo, Transaction};
+
+use crate::db;
+
+/// Maximum number of signatures returned by `getSignaturesForAddress`.
+const SIGNATURE_PAGE: usize = 1000;
+/// Maximum number of accounts returned by `getMultipleAccounts`.
+const ACCOUNT_PAGE: usize = 100;
+
+/// The transaction account an event refers to, if any.
+fn event_transaction(event: &SmalletEvent) -> Option<Pubkey> {
+    match event {
+        SmalletEvent::TransactionCreate(e) => Some(e.transaction),
+        SmalletEvent::TransactionApprove(e) => Some(e.transaction),
+        SmalletEvent::TransactionUnapprove(e) => Some(e.transaction),
+        SmalletEvent::TransactionVeto(e) => Some(e.transaction),
+        SmalletEvent::TransactionExecute(e) => Some(e.transaction),
+        SmalletEvent::TransactionIndexAssign(e) => Some(e.transaction),
+        SmalletEvent::TransactionMigrate(e) => Some(e.transaction),
+        SmalletEvent::ProgramUpgradePropose(e) => Some(e.transaction),
+        SmalletEvent::TransferPropose(e) => Some(e.transaction),
+        SmalletEvent::TransferExecute(e) => Some(e.transaction),
+        SmalletEvent::TransactionCommit(e) => Some(e.transaction),
+        SmalletEvent::TransactionReveal(e) => Some(e.transaction),
+        _ => None,
+    }
+}
+
+/// Accounts whose state may have changed when an event was emitted.
+fn touched_accounts(event: &SmalletEvent) -> Vec<Pubkey> {
+    let mut accounts = vec![event.smallet()];
+    accounts.extend(event_transaction(event));
+    match event {
+        SmalletEvent::SubaccountCreate(e) => {
+            accounts.push(find_subaccount_info_address(&e.subaccount).0)
+        }
+        SmalletEvent::SubaccountClose(e) => {
+            accounts.push(find_subaccount_info_address(&e.subaccount).0)
+        }
+        SmalletEvent::SubaccountMigrate(e) => {
+            accounts.push(find_subaccount_info_address(&e.subaccount).0)
+        }
+        SmalletEvent::SubaccountUpdate(e) => accounts.push(e.subaccount_info),
+        SmalletEvent::AddressBookPolicySet(e) => accounts.push(e.address_book),
+        SmalletEvent::AddressBookEntryAdd(e) => accounts.push(e.address_book),
+        SmalletEvent::AddressBookEntryRemove(e) => accounts.push(e.address_book),
+        _ => {}
+    }
+    accounts
+}
+
+/// Writes an account to the database, or removes it if it was closed.
+pub fn store_account(
+    conn: &Connection,
+    address: &Pubkey,
+    account: Option<&RpcAccount>,
+    slot: u64,
+) -> anyhow::Result<()> {
+    let account = match account {
+        Some(account) if account.owner == smallet_client::smallet::ID => account,
+        _ => return Ok(db::delete_account(conn, address)?),
+    };
+    let data = &account.data;
+    if data.starts_with(&Smallet::discriminator()) {
+        db::upsert_smallet(conn, address, &decode_smallet(data)?, slot)?;
+    } else if data.starts_with(&Transaction::discriminator()) {
+        db::upsert_transaction(conn, address, &decode_transaction(data)?, slot)?;
+    } else if data.starts_with(&SubaccountInfo::discriminator()) {
+        db::upsert_subaccount_info(conn, address, &decode_subaccount_info(data)?, slot)?;
+    } else if data.starts_with(&AddressBook::discriminator()) {
+        db::upsert_address_book(conn, address, &decode_address_book(data)?, slot)?;
+    }
+    Ok(())
+}
+
+pub struct Indexer {
+    client: RpcClient,
+    conn: Connection,
+}
+
+impl Indexer {
+    pub fn new(client: RpcClient, conn: Connection) -> Self {
+        Self { client, conn }
+    }
+
+    /// Loads every smallet, transaction, sub-account info and address book of
+    /// the program.
+    pub fn scan_accounts(&self) -> anyhow::Result<usize> {
+        let slot = self.client.get_clock()?.slot;
+        let db_tx = self.conn.unchecked_transaction()?;
+        let mut count = 0;
+        for filter in [
+            RpcFilter::discriminator::<Smallet>(),
+            RpcFilter::discriminator::<Transaction>(),
+            RpcFilter::discriminator::<SubaccountInfo>(),
+            RpcFilter::discriminator::<AddressBook>(),
+        ] {
+            let accounts = self
+                .client
+                .get_program_accounts(&smallet_client::smallet::ID, &[filter])?;
+            for (address, account) in &accounts {
+                store_account(&db_tx, address, Some(account), slot)?;
+            }
+            count += accounts.len();
+        }
+        db_tx.commit()?;
+        Ok(count)
+    }
+
+    /// Refreshes the given accounts.
+    fn refresh_accounts(
+        &self,
+        conn: &Connection,
+        addresses: &BTreeSet<Pubkey>,
+        slot: u64,
+    ) -> anyhow::Result<()> {
+        let addresses: Vec<Pubkey> = addresses.iter().copied().collect();
+        for chunk in addresses.chunks(ACCOUNT_PAGE) {
+            let accounts = self.client.get_multiple_accounts(chunk)?;
+            for (address, account) in chunk.iter().zip(accounts.iter()) {
+                store_account(conn, address, account.as_ref(), slot)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetches the signatures of the program newer than the cursor, oldest first.
+    fn new_signatures(&self, until: Option<&str>) -> anyhow::Result<Vec<RpcSignature>> {
+        let mut signatures: Vec<RpcSignature> = vec![];
+        loop {
+            let before = signatures.last().map(|s| s.signature.as_str());
+            let page = self.client.get_signatures_for_address(
+                &smallet_client::smallet::ID,
+                before,
+                until,
+                SIGNATURE_PAGE,
+            )?;
+            let done = page.len() < SIGNATURE_PAGE;
+            signatures.extend(page);
+            if done {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Processes the transactions of the program since the last call, storing
+    /// their events and refreshing the accounts they touched.
+    ///
+    /// On the first call, the whole history of the program is processed if
+    /// `backfill` is set; otherwise only the cursor is initialized.
+    /// Returns the number of events stored.
+    pub fn sync_events(&self, backfill: bool) -> anyhow::Result<usize> {
+        let cursor = db::cursor(&self.conn)?;
+        if cursor.is_none() && !backfill {
+            let newest = self.client.get_signatures_for_address(
+                &smallet_client::smallet::ID,
+                None,
+                None,
+                1,
+            )?;
+            if let Some(newest) = newest.first() {
+                db::set_cursor(&self.conn, &newest.signature, newest.slot)?;
+            }
+            return Ok(0);
+        }
+
+        let signatures = self.new_signatures(cursor.as_deref())?;
+        let newest = match signatures.last() {
+            Some(newest) => newest,
+            None => return Ok(0),
+        };
+
+        let db_tx = self.conn.unchecked_transaction()?;
+        let mut touched = BTreeSet::new();
+        let mut count = 0;
+        for signature in signatures.iter().filter(|s| !s.failed) {
+            let logs = match self.client.get_transaction_logs(&signature.signature)? {
+                Some(logs) => logs,
+                None => continue,
+            };
+            for (position, event) in parse_logs(&logs).iter().enumerate() {
+                db::insert_event(
+                    &db_tx,
+                    &signature.signature,
+                    position,
+                    signature.slot,
+                    signature.block_time,
+                    event_transaction(event).as_ref(),
+                    event,
+                )?;
+                touched.extend(touched_accounts(event));
+                count += 1;
+            }
+        }
+        self.refresh_accounts(&db_tx, &touched, newest.slot)?;
+        db::set_cursor(&db_tx, &newest.signature, newest.slot)?;
+        db_tx.commit()?;
+        Ok(count)
+    }
+}
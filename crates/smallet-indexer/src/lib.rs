@@ -0,0 +1,5 @@
+//! SQLite schema and ingestion of the `smallet-indexer` binary, exposed for
+//! tests and for tools sharing its database.
+
+pub mod db;
+pub mod indexer;
@@ -0,0 +1,76 @@
+//! `smallet-indexer`: follows a validator and keeps a SQLite database of
+//! smallet accounts and events.
+//!
+//! On start, every smallet, transaction and sub-account info is loaded. The
+//! indexer then polls the transactions of the program, storing their events
+//! and refreshing the accounts they touched.
+//!
+//! Example query, the pending transactions an owner may act on:
+//!
+//! ```sql
+//! SELECT * FROM pending_transactions_by_owner WHERE owner = '<pubkey>';
+//! ```
+
+use std::thread::sleep;
+use std::time::Duration;
+
+use clap::Parser;
+use smallet_client::rpc::RpcClient;
+use smallet_indexer::db;
+use smallet_indexer::indexer::Indexer;
+
+#[derive(Parser)]
+#[clap(name = "smallet-indexer", version, about = "Index smallets into SQLite")]
+struct Cli {
+    /// RPC URL of the validator.
+    #[clap(long, short, env = "SMALLET_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Commitment level. Must be `confirmed` or `finalized`.
+    #[clap(long, default_value = "confirmed")]
+    commitment: String,
+    /// Path of the SQLite database.
+    #[clap(long, default_value = "smallet-indexer.db")]
+    db: String,
+    /// Seconds between polls.
+    #[clap(long, default_value = "5")]
+    interval: u64,
+    /// Processes the whole history of the program when the database is new.
+    #[clap(long)]
+    backfill: bool,
+    /// Exits after a single sync.
+    #[clap(long)]
+    once: bool,
+}
+
+fn run(cli: Cli) -> anyhow::Result<()> {
+    let client = RpcClient::new_with_commitment(&cli.url, cli.commitment);
+    let indexer = Indexer::new(client, db::open(&cli.db)?);
+
+    // Initialize the cursor before loading accounts, so that no transaction
+    // landing in between is missed.
+    let events = indexer.sync_events(cli.backfill)?;
+    let accounts = indexer.scan_accounts()?;
+    println!(
+        "Loaded {} accounts and {} events from {}",
+        accounts, events, cli.url
+    );
+    loop {
+        match indexer.sync_events(cli.backfill) {
+            Ok(0) => {}
+            Ok(events) => println!("Indexed {} events", events),
+            Err(err) if !cli.once => eprintln!("Sync failed: {:#}", err),
+            Err(err) => return Err(err),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(cli.interval));
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Cli::parse()) {
+        eprintln!("Error: {:#}", err);
+        std::process::exit(1);
+    }
+}
@@ -6,6 +6,7 @@ use crate::*;
 
 // Emitted when smallet is first created
 #[event]
+#[derive(Clone, Debug)]
 pub struct WalletCreateEvent {
     // The smallet
     #[index]
@@ -21,6 +22,7 @@ pub struct WalletCreateEvent {
 }
 // Emitted when owners of a smallet are changed.
 #[event]
+#[derive(Clone, Debug)]
 pub struct WalletSetOwnersEvent {
     #[index]
     // The smallet
@@ -32,6 +34,7 @@ pub struct WalletSetOwnersEvent {
 }
 // Emitted when the threshold of a smallet is changed
 #[event]
+#[derive(Clone, Debug)]
 pub struct WalletChangeThresholdEvent {
     #[index]
     pub smallet: Pubkey,
@@ -39,8 +42,40 @@ pub struct WalletChangeThresholdEvent {
     pub threshold: u64,
     pub timestamp: i64,
 }
+// Emitted when the veto threshold of a smallet is changed
+#[event]
+#[derive(Clone, Debug)]
+pub struct WalletChangeVetoThresholdEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The new veto threshold
+    pub veto_threshold: u64,
+    pub timestamp: i64,
+}
+// Emitted when the approval TTL of a smallet is changed
+#[event]
+#[derive(Clone, Debug)]
+pub struct WalletChangeApprovalTtlEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The new approval TTL, in seconds
+    pub approval_ttl: i64,
+    pub timestamp: i64,
+}
+// Emitted when strict FIFO execution of a smallet is enabled or disabled, or
+// its cursor advances
+#[event]
+#[derive(Clone, Debug)]
+pub struct FifoCursorSetEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The new cursor, or None if FIFO mode was disabled
+    pub cursor: Option<u64>,
+    pub timestamp: i64,
+}
 // Emitted when a transaction is proposed
 #[event]
+#[derive(Clone, Debug)]
 pub struct TransactionCreateEvent {
     #[index]
     pub smallet: Pubkey,
@@ -57,6 +92,7 @@ pub struct TransactionCreateEvent {
 }
 // Emitted when a transaction is approved
 #[event]
+#[derive(Clone, Debug)]
 pub struct TransactionApproveEvent {
     #[index]
     pub smallet: Pubkey,
@@ -67,6 +103,7 @@ pub struct TransactionApproveEvent {
 }
 
 #[event]
+#[derive(Clone, Debug)]
 pub struct TransactionUnapproveEvent {
     #[index]
     pub smallet: Pubkey,
@@ -76,7 +113,22 @@ pub struct TransactionUnapproveEvent {
     pub timestamp: i64,
 }
 
+// Emitted when an owner vetoes a transaction during its timelock
 #[event]
+#[derive(Clone, Debug)]
+pub struct TransactionVetoEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    pub owner: Pubkey,
+    // Whether this veto reached the veto threshold and blocked the transaction
+    pub blocked: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[derive(Clone, Debug)]
 pub struct TransactionExecuteEvent {
     #[index]
     pub smallet: Pubkey,
@@ -85,3 +137,270 @@ pub struct TransactionExecuteEvent {
     pub executor: Pubkey,
     pub timestamp: i64,
 }
+
+// Emitted when a transaction created via `create_transaction_v2` is assigned an index
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransactionIndexAssignEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    // The index assigned to the transaction
+    pub index: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a smallet is migrated to the current account layout
+#[event]
+#[derive(Clone, Debug)]
+pub struct SmalletMigrateEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The layout version after migration
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+// Emitted when a transaction is migrated to the current account layout
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransactionMigrateEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    // The layout version after migration
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+// Emitted when a program upgrade is proposed
+#[event]
+#[derive(Clone, Debug)]
+pub struct ProgramUpgradeProposeEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    // The program to upgrade
+    pub program: Pubkey,
+    // The buffer containing the new program data
+    pub buffer: Pubkey,
+    // Expected SHA-256 of the program data in the buffer
+    pub buffer_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+// Emitted when a transfer out of a vault is proposed
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransferProposeEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    pub proposer: Pubkey,
+    pub transfer: VaultTransfer,
+    pub timestamp: i64,
+}
+// Emitted when a transfer out of a vault is executed
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransferExecuteEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    pub asset: TransferAsset,
+    pub amount: u64,
+    // The vault, or its associated token account
+    pub source: Pubkey,
+    // The recipient, or its associated token account
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+// Emitted when a transaction committing to the hash of its instructions is proposed
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransactionCommitEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    // SHA-256 of the salt and the borsh serialized instructions
+    pub instructions_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+// Emitted when the instructions of a committed transaction are revealed at execution
+#[event]
+#[derive(Clone, Debug)]
+pub struct TransactionRevealEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub transaction: Pubkey,
+    // The salt of the committed hash
+    pub salt: [u8; 32],
+    // The executed instructions
+    pub instructions: Vec<TXInstruction>,
+    pub timestamp: i64,
+}
+
+// Emitted when the timelock tiers of a smallet are replaced
+#[event]
+#[derive(Clone, Debug)]
+pub struct TimelockTiersSetEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The new tiers of the smallet
+    pub tiers: Vec<TimelockTier>,
+    pub timestamp: i64,
+}
+
+// Emitted when the optimistic mode of a smallet is enabled, replaced or disabled
+#[event]
+#[derive(Clone, Debug)]
+pub struct OptimisticModeSetEvent {
+    #[index]
+    pub smallet: Pubkey,
+    // The new configuration, or None if optimistic mode was disabled
+    pub config: Option<OptimisticConfig>,
+    pub timestamp: i64,
+}
+
+// Emitted when the policy of an owner invoker is created or replaced
+#[event]
+#[derive(Clone, Debug)]
+pub struct InvokerPolicySetEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub policy: Pubkey,
+    // Index of the owner invoker
+    pub index: u64,
+    // Owners allowed to use the owner invoker
+    pub owners: Vec<Pubkey>,
+    // Instructions allowed to be invoked
+    pub allowed_instructions: Vec<AllowedInstruction>,
+    // Maximum number of calls per period, or 0 if unlimited
+    pub max_calls_per_period: u64,
+    // Length of a period, in seconds
+    pub period: i64,
+    pub timestamp: i64,
+}
+
+// Emitted when an owner invokes an instruction as an owner invoker
+#[event]
+#[derive(Clone, Debug)]
+pub struct OwnerInvokeEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    // The owner invoker that signed the instruction
+    pub invoker: Pubkey,
+    // Index of the owner invoker
+    pub index: u64,
+    // The owner who invoked the instruction
+    pub owner: Pubkey,
+    // The program that was invoked
+    pub program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+// Emitted when a sub-account is registered via `create_subaccount_info`
+#[event]
+#[derive(Clone, Debug)]
+pub struct SubaccountCreateEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub subaccount: Pubkey,
+    // Type of the sub-account
+    pub subaccount_type: SubaccountType,
+    // Index of the sub-account
+    pub index: u64,
+    // Index of the sub-account in the registry of the smallet
+    pub registry_index: u64,
+    // Label of the sub-account
+    pub label: Option<String>,
+    pub timestamp: i64,
+}
+
+// Emitted when the label of a sub-account is changed
+#[event]
+#[derive(Clone, Debug)]
+pub struct SubaccountUpdateEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub subaccount_info: Pubkey,
+    // The new label
+    pub label: Option<String>,
+    pub timestamp: i64,
+}
+
+// Emitted when a sub-account info is closed
+#[event]
+#[derive(Clone, Debug)]
+pub struct SubaccountCloseEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub subaccount: Pubkey,
+    // Index of the closed entry in the registry of the smallet
+    pub registry_index: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when a legacy sub-account info is migrated and registered
+#[event]
+#[derive(Clone, Debug)]
+pub struct SubaccountMigrateEvent {
+    #[index]
+    pub smallet: Pubkey,
+    #[index]
+    pub subaccount: Pubkey,
+    // Index of the sub-account in the registry of the smallet
+    pub registry_index: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when the threshold or delays of an address book are set
+#[event]
+#[derive(Clone, Debug)]
+pub struct AddressBookPolicySetEvent {
+    #[index]
+    pub smallet: Pubkey,
+    pub address_book: Pubkey,
+    pub threshold: u64,
+    pub minimum_delay: i64,
+    pub entry_delay: i64,
+    pub timestamp: i64,
+}
+
+// Emitted when a recipient is added to an address book
+#[event]
+#[derive(Clone, Debug)]
+pub struct AddressBookEntryAddEvent {
+    #[index]
+    pub smallet: Pubkey,
+    pub address_book: Pubkey,
+    pub recipient: Pubkey,
+    pub label: String,
+    // When the recipient becomes trusted
+    pub trusted_at: i64,
+    pub timestamp: i64,
+}
+
+// Emitted when a recipient is removed from an address book
+#[event]
+#[derive(Clone, Debug)]
+pub struct AddressBookEntryRemoveEvent {
+    #[index]
+    pub smallet: Pubkey,
+    pub address_book: Pubkey,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,71 @@
+//! Instruction handler for [smallet::add_address_book_entry].
+
+use crate::*;
+
+// Instruction handler for [smallet::add_address_book_entry].
+pub fn handler(ctx: Context<AddAddressBookEntry>, recipient: Pubkey, label: String) -> Result<()> {
+    invariant!(label.len() <= MAX_LABEL_LEN, LabelTooLong);
+    invariant!(
+        !ctx.accounts
+            .address_book
+            .entries
+            .iter()
+            .any(|entry| entry.recipient == recipient),
+        AddressBookEntryExists
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let entry = AddressBookEntry {
+        recipient,
+        label: label.clone(),
+        trusted_at: unwrap_int!(now.checked_add(ctx.accounts.address_book.entry_delay)),
+    };
+
+    let info = ctx.accounts.address_book.to_account_info();
+    realloc_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        unwrap_int!(info.data_len().checked_add(entry.space())),
+    )?;
+
+    let trusted_at = entry.trusted_at;
+    ctx.accounts.address_book.entries.push(entry);
+
+    emit!(AddressBookEntryAddEvent {
+        smallet: ctx.accounts.smallet.key(),
+        address_book: ctx.accounts.address_book.key(),
+        recipient,
+        label,
+        trusted_at,
+        timestamp: now
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for AddAddressBookEntry<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::add_address_book_entry].
+#[derive(Accounts)]
+pub struct AddAddressBookEntry<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [AddressBook] to add the entry to.
+    #[account(mut, has_one = smallet)]
+    pub address_book: Account<'info, AddressBook>,
+    // Payer of additional rent as the [AddressBook] grows.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,41 @@
+//! Instruction handler for [smallet::advance_fifo_cursor].
+
+use crate::*;
+
+// Instruction handler for [smallet::advance_fifo_cursor].
+pub fn handler(ctx: Context<AdvanceFifoCursor>) -> Result<()> {
+    let current_ts = Clock::get()?.unix_timestamp;
+    let smallet = &ctx.accounts.smallet;
+    let mut cursor = unwrap_opt!(smallet.fifo_cursor);
+    // Stop at the first transaction that is missing or may still be executed.
+    while let Some(tx) = find_indexed_transaction(ctx.remaining_accounts, &smallet.key(), cursor) {
+        if !smallet.is_settled(&tx, current_ts) {
+            break;
+        }
+        cursor = unwrap_int!(cursor.checked_add(1));
+    }
+    ctx.accounts.smallet.fifo_cursor = Some(cursor);
+
+    emit!(FifoCursorSetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        cursor: Some(cursor),
+        timestamp: current_ts
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for AdvanceFifoCursor<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(self.smallet.fifo_cursor.is_some(), "FIFO mode is disabled");
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::advance_fifo_cursor].
+#[derive(Accounts)]
+pub struct AdvanceFifoCursor<'info> {
+    // The [Smallet].
+    #[account(mut)]
+    pub smallet: Account<'info, Smallet>,
+}
@@ -8,13 +8,16 @@ pub fn handler(ctx: Context<Approve>) -> Result<()> {
         .accounts
         .smallet
         .try_owner_index(ctx.accounts.owner.key())?;
-    ctx.accounts.transaction.signers[owner_index] = true;
+    let current_ts = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .transaction
+        .set_approval(owner_index, Some(current_ts));
 
     emit!(TransactionApproveEvent {
         smallet: ctx.accounts.smallet.key(),
         transaction: ctx.accounts.transaction.key(),
         owner: ctx.accounts.owner.key(),
-        timestamp: Clock::get()?.unix_timestamp
+        timestamp: current_ts
     });
     Ok(())
 }
@@ -24,6 +27,11 @@ impl<'info> Validate<'info> for Approve<'info> {
     fn validate(&self) -> Result<()> {
         // The TX in question should belong to the smallet
         assert_keys_eq!(self.smallet, self.transaction.smallet);
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.transaction.version == TRANSACTION_VERSION,
+            AccountNotMigrated
+        );
         // If the owner set has changed, should not allow approvals/unapprovals to change
         // This can potentially cause someone to be able to approve/unapprove someone else's TXs.
         invariant!(
@@ -0,0 +1,49 @@
+//! Instruction handler for [smallet::assign_transaction_index].
+
+use crate::*;
+
+// Instruction handler for [smallet::assign_transaction_index].
+pub fn handler(ctx: Context<AssignTransactionIndex>) -> Result<()> {
+    let smallet = &mut ctx.accounts.smallet;
+    let index = smallet.num_transactions;
+    smallet.num_transactions = unwrap_int!(smallet.num_transactions.checked_add(1));
+
+    let tx = &mut ctx.accounts.transaction;
+    tx.index = index;
+
+    emit!(TransactionIndexAssignEvent {
+        smallet: ctx.accounts.smallet.key(),
+        transaction: ctx.accounts.transaction.key(),
+        index,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for AssignTransactionIndex<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.smallet, self.transaction.smallet);
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.transaction.version == TRANSACTION_VERSION,
+            AccountNotMigrated
+        );
+        // Only [smallet::create_transaction_v2] leaves the index unassigned.
+        invariant!(
+            self.transaction.index == NO_INDEX,
+            TransactionIndexAlreadyAssigned
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::assign_transaction_index].
+#[derive(Accounts)]
+pub struct AssignTransactionIndex<'info> {
+    // The [Smallet].
+    #[account(mut)]
+    pub smallet: Account<'info, Smallet>,
+    // The [Transaction] to assign an index to.
+    #[account(mut, has_one = smallet)]
+    pub transaction: Account<'info, Transaction>,
+}
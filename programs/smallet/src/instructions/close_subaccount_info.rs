@@ -0,0 +1,49 @@
+//! Instruction handler for [smallet::close_subaccount_info].
+
+use crate::*;
+
+// Instruction handler for [smallet::close_subaccount_info].
+pub fn handler(ctx: Context<CloseSubaccountInfo>) -> Result<()> {
+    emit!(SubaccountCloseEvent {
+        smallet: ctx.accounts.smallet.key(),
+        subaccount: ctx.accounts.registry_entry.subaccount,
+        registry_index: ctx.accounts.registry_entry.registry_index,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CloseSubaccountInfo<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        invariant!(
+            self.subaccount_info.version == SUBACCOUNT_INFO_VERSION,
+            AccountNotMigrated
+        );
+        invariant!(
+            self.registry_entry.registry_index == self.subaccount_info.registry_index,
+            "registry_index"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::close_subaccount_info].
+#[derive(Accounts)]
+pub struct CloseSubaccountInfo<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [SubaccountInfo] to close.
+    #[account(mut, has_one = smallet, close = receiver)]
+    pub subaccount_info: Account<'info, SubaccountInfo>,
+    // The [SubaccountRegistryEntry] of the sub-account.
+    #[account(mut, has_one = smallet, close = receiver)]
+    pub registry_entry: Account<'info, SubaccountRegistryEntry>,
+    /// CHECK: Receives the rent of the closed accounts.
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
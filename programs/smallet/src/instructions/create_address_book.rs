@@ -0,0 +1,67 @@
+//! Instruction handler for [smallet::create_address_book].
+
+use crate::*;
+
+// Instruction handler for [smallet::create_address_book].
+pub fn handler(
+    ctx: Context<CreateAddressBook>,
+    threshold: u64,
+    minimum_delay: i64,
+    entry_delay: i64,
+) -> Result<()> {
+    AddressBook::validate_policy(&ctx.accounts.smallet, threshold, minimum_delay, entry_delay)?;
+
+    let address_book = &mut ctx.accounts.address_book;
+    address_book.smallet = ctx.accounts.smallet.key();
+    address_book.bump = *unwrap_int!(ctx.bumps.get("address_book"));
+    address_book.threshold = threshold;
+    address_book.minimum_delay = minimum_delay;
+    address_book.entry_delay = entry_delay;
+    address_book.entries = vec![];
+
+    emit!(AddressBookPolicySetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        address_book: ctx.accounts.address_book.key(),
+        threshold,
+        minimum_delay,
+        entry_delay,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateAddressBook<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::create_address_book].
+#[derive(Accounts)]
+pub struct CreateAddressBook<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [AddressBook] to create.
+    #[account(
+        init,
+        seeds = [
+            b"CosmicAddressBook".as_ref(),
+            smallet.key().to_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = AddressBook::space(&[]),
+    )]
+    pub address_book: Account<'info, AddressBook>,
+    // Payer to create the [AddressBook].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
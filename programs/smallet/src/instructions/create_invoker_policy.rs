@@ -0,0 +1,81 @@
+//! Instruction handler for [smallet::create_invoker_policy].
+
+use crate::*;
+
+// Instruction handler for [smallet::create_invoker_policy].
+pub fn handler(
+    ctx: Context<CreateInvokerPolicy>,
+    index: u64,
+    owners: Vec<Pubkey>,
+    allowed_instructions: Vec<AllowedInstruction>,
+    max_calls_per_period: u64,
+    period: i64,
+) -> Result<()> {
+    InvokerPolicy::validate_rate_limit(max_calls_per_period, period)?;
+
+    let policy = &mut ctx.accounts.policy;
+    policy.smallet = ctx.accounts.smallet.key();
+    policy.index = index;
+    policy.bump = *unwrap_int!(ctx.bumps.get("policy"));
+
+    policy.owners = owners.clone();
+    policy.allowed_instructions = allowed_instructions.clone();
+    policy.max_calls_per_period = max_calls_per_period;
+    policy.period = period;
+    policy.period_start = Clock::get()?.unix_timestamp;
+    policy.calls_in_period = 0;
+
+    emit!(InvokerPolicySetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        policy: ctx.accounts.policy.key(),
+        index,
+        owners,
+        allowed_instructions,
+        max_calls_per_period,
+        period,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for CreateInvokerPolicy<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::create_invoker_policy].
+#[derive(Accounts)]
+#[instruction(
+    index: u64,
+    owners: Vec<Pubkey>,
+    allowed_instructions: Vec<AllowedInstruction>
+)]
+pub struct CreateInvokerPolicy<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [InvokerPolicy] to create.
+    #[account(
+        init,
+        seeds = [
+            b"CosmicInvokerPolicy".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            index.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = InvokerPolicy::space(&owners, &allowed_instructions),
+    )]
+    pub policy: Account<'info, InvokerPolicy>,
+    // Payer to create the [InvokerPolicy].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,63 @@
+//! Instruction handler for [smallet::create_transaction_v2].
+
+use crate::*;
+
+// Instruction handler for [smallet::create_transaction_v2].
+pub fn handler(
+    ctx: Context<CreateTransactionV2>,
+    _nonce: u64,
+    instructions: Vec<TXInstruction>,
+    eta: i64,
+    options: TransactionOptions,
+) -> Result<()> {
+    do_create_transaction(
+        &ctx.accounts.smallet,
+        &mut ctx.accounts.transaction,
+        &ctx.accounts.proposer,
+        NO_INDEX,
+        *unwrap_int!(ctx.bumps.get("transaction")),
+        instructions,
+        eta,
+        options,
+        find_address_book(ctx.remaining_accounts, &ctx.accounts.smallet.key()).as_ref(),
+    )
+}
+
+impl<'info> Validate<'info> for CreateTransactionV2<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        // owner_index check happens later
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::create_transaction_v2].
+#[derive(Accounts)]
+#[instruction(nonce: u64, instructions: Vec<TXInstruction>, eta: i64, options: TransactionOptions)]
+pub struct CreateTransactionV2<'info> {
+    // The [Smallet]. Not written to, so that proposals do not contend.
+    pub smallet: Account<'info, Smallet>,
+    // The [Transaction], seeded by the proposer and their chosen nonce.
+    #[account(
+        init,
+        seeds = [
+            b"CosmicTransactionV2".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            proposer.key().to_bytes().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = Transaction::space(instructions)
+            + options.space()
+            + Transaction::approvals_space(&smallet),
+    )]
+    pub transaction: Account<'info, Transaction>,
+    // One of the owners. Checked in the handler via [Smallet::try_owner_index].
+    pub proposer: Signer<'info>,
+    // Payer to create the [Transaction].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+//! Instruction handler for [smallet::migrate_smallet].
+
+use crate::*;
+
+// Instruction handler for [smallet::migrate_smallet].
+pub fn handler(ctx: Context<MigrateSmallet>) -> Result<()> {
+    let info = ctx.accounts.smallet.to_account_info();
+    let (mut smallet, legacy_len) = {
+        let data = info.try_borrow_data()?;
+        let smallet = Smallet::try_deserialize_versioned(&data)?;
+        invariant!(smallet.version == LEGACY_VERSION, AccountAlreadyMigrated);
+        let mut rest: &[u8] = &data[8..];
+        SmalletV0::deserialize(&mut rest)?;
+        (smallet, data.len() - rest.len())
+    };
+
+    smallet.version = SMALLET_VERSION;
+    let current_len = 8 + smallet.try_to_vec()?.len();
+    // Keep the spare space of the legacy account, i.e. room for `max_owners`.
+    let new_len = unwrap_int!(info
+        .data_len()
+        .checked_add(current_len.saturating_sub(legacy_len)));
+    realloc_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        new_len,
+    )?;
+    smallet.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(SmalletMigrateEvent {
+        smallet: info.key(),
+        version: SMALLET_VERSION,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for MigrateSmallet<'info> {
+    fn validate(&self) -> Result<()> {
+        // layout checks happen in the handler
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::migrate_smallet].
+#[derive(Accounts)]
+pub struct MigrateSmallet<'info> {
+    /// CHECK: The [Smallet] to migrate. It cannot be loaded as an [Account]
+    /// until migrated, so its layout is checked in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub smallet: UncheckedAccount<'info>,
+    // Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
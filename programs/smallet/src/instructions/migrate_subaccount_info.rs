@@ -0,0 +1,82 @@
+//! Instruction handler for [smallet::migrate_subaccount_info].
+
+use crate::*;
+
+// Instruction handler for [smallet::migrate_subaccount_info].
+pub fn handler(ctx: Context<MigrateSubaccountInfo>) -> Result<()> {
+    let info = ctx.accounts.subaccount_info.to_account_info();
+    let mut subaccount_info = SubaccountInfo::try_deserialize_versioned(&info.try_borrow_data()?)?;
+    invariant!(
+        subaccount_info.version == LEGACY_VERSION,
+        AccountAlreadyMigrated
+    );
+    assert_keys_eq!(subaccount_info.smallet, ctx.accounts.smallet, "smallet");
+    let (subaccount, _) = subaccount_info
+        .subaccount_type
+        .find_address(&subaccount_info.smallet, subaccount_info.index);
+
+    let smallet = &mut ctx.accounts.smallet;
+    let registry_index = smallet.num_subaccounts;
+    smallet.num_subaccounts = unwrap_int!(smallet.num_subaccounts.checked_add(1));
+
+    let entry = &mut ctx.accounts.registry_entry;
+    entry.smallet = smallet.key();
+    entry.registry_index = registry_index;
+    entry.subaccount = subaccount;
+
+    subaccount_info.version = SUBACCOUNT_INFO_VERSION;
+    subaccount_info.registry_index = registry_index;
+    realloc_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + SubaccountInfo::LEN,
+    )?;
+    subaccount_info.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(SubaccountMigrateEvent {
+        smallet: ctx.accounts.smallet.key(),
+        subaccount,
+        registry_index,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for MigrateSubaccountInfo<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        // layout checks happen in the handler
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::migrate_subaccount_info].
+#[derive(Accounts)]
+pub struct MigrateSubaccountInfo<'info> {
+    /// CHECK: The [SubaccountInfo] to migrate. It cannot be loaded as an
+    /// [Account] until migrated, so its layout is checked in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub subaccount_info: UncheckedAccount<'info>,
+    // The [Smallet] to register the sub-account on.
+    #[account(mut)]
+    pub smallet: Account<'info, Smallet>,
+    // The [SubaccountRegistryEntry] to create.
+    #[account(
+        init,
+        seeds = [
+            b"CosmicSubaccountRegistry".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            smallet.num_subaccounts.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + SubaccountRegistryEntry::LEN
+    )]
+    pub registry_entry: Account<'info, SubaccountRegistryEntry>,
+    // Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,60 @@
+//! Instruction handler for [smallet::migrate_transaction].
+
+use crate::*;
+
+// Instruction handler for [smallet::migrate_transaction].
+pub fn handler(ctx: Context<MigrateTransaction>) -> Result<()> {
+    let info = ctx.accounts.transaction.to_account_info();
+    let (mut tx, legacy_len) = {
+        let data = info.try_borrow_data()?;
+        let tx = Transaction::try_deserialize_versioned(&data)?;
+        invariant!(tx.version == LEGACY_VERSION, AccountAlreadyMigrated);
+        let mut rest: &[u8] = &data[8..];
+        TransactionV0::deserialize(&mut rest)?;
+        (tx, data.len() - rest.len())
+    };
+
+    tx.version = TRANSACTION_VERSION;
+    // Leave room for approval timestamps. Legacy approvals have none.
+    tx.approved_at = vec![0; tx.signers.len()];
+    let current_len = 8 + tx.try_to_vec()?.len();
+    let new_len = unwrap_int!(info
+        .data_len()
+        .checked_add(current_len.saturating_sub(legacy_len)));
+    realloc_account(
+        &info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        new_len,
+    )?;
+    tx.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(TransactionMigrateEvent {
+        smallet: tx.smallet,
+        transaction: info.key(),
+        version: TRANSACTION_VERSION,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for MigrateTransaction<'info> {
+    fn validate(&self) -> Result<()> {
+        // layout checks happen in the handler
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::migrate_transaction].
+#[derive(Accounts)]
+pub struct MigrateTransaction<'info> {
+    /// CHECK: The [Transaction] to migrate. It cannot be loaded as an [Account]
+    /// until migrated, so its layout is checked in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub transaction: UncheckedAccount<'info>,
+    // Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
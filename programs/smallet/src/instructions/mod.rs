@@ -1,5 +1,47 @@
+// Every module exports its own `handler`, which is called by its path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod add_address_book_entry;
+pub mod advance_fifo_cursor;
 pub mod approve;
+pub mod assign_transaction_index;
+pub mod close_subaccount_info;
+pub mod create_address_book;
+pub mod create_committed_transaction;
+pub mod create_invoker_policy;
+pub mod create_transaction_v2;
+pub mod migrate_smallet;
+pub mod migrate_subaccount_info;
+pub mod migrate_transaction;
+pub mod propose_program_upgrade;
+pub mod propose_transfer;
+pub mod remove_address_book_entry;
+pub mod set_address_book_policy;
+pub mod set_invoker_policy;
+pub mod set_optimistic_mode;
+pub mod set_timelock_tiers;
 pub mod unapprove;
+pub mod update_subaccount_info;
+pub mod veto;
 
+pub use add_address_book_entry::*;
+pub use advance_fifo_cursor::*;
 pub use approve::*;
-pub use unapprove::*;
+pub use assign_transaction_index::*;
+pub use close_subaccount_info::*;
+pub use create_address_book::*;
+pub use create_committed_transaction::*;
+pub use create_invoker_policy::*;
+pub use create_transaction_v2::*;
+pub use migrate_smallet::*;
+pub use migrate_subaccount_info::*;
+pub use migrate_transaction::*;
+pub use propose_program_upgrade::*;
+pub use propose_transfer::*;
+pub use remove_address_book_entry::*;
+pub use set_address_book_policy::*;
+pub use set_invoker_policy::*;
+pub use set_optimistic_mode::*;
+pub use set_timelock_tiers::*;
+pub use update_subaccount_info::*;
+pub use veto::*;
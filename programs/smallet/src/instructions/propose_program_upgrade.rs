@@ -0,0 +1,97 @@
+//! Instruction handler for [smallet::propose_program_upgrade].
+
+use crate::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+// Instruction handler for [smallet::propose_program_upgrade].
+pub fn handler(
+    ctx: Context<ProposeProgramUpgrade>,
+    _nonce: u64,
+    spill: Pubkey,
+    buffer_hash: [u8; 32],
+    eta: i64,
+) -> Result<()> {
+    let upgrade = ProgramUpgrade {
+        program: ctx.accounts.program.key(),
+        buffer: ctx.accounts.buffer.key(),
+        buffer_hash,
+    };
+    let ix = upgrade.to_instruction(&ctx.accounts.smallet.key(), &spill);
+
+    do_create_transaction(
+        &ctx.accounts.smallet,
+        &mut ctx.accounts.transaction,
+        &ctx.accounts.proposer,
+        NO_INDEX,
+        *unwrap_int!(ctx.bumps.get("transaction")),
+        vec![ix],
+        eta,
+        TransactionOptions::default(),
+        None,
+    )?;
+    ctx.accounts.transaction.program_upgrade = Some(upgrade);
+
+    emit!(ProgramUpgradeProposeEvent {
+        smallet: ctx.accounts.smallet.key(),
+        transaction: ctx.accounts.transaction.key(),
+        program: upgrade.program,
+        buffer: upgrade.buffer,
+        buffer_hash,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for ProposeProgramUpgrade<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(self.program.executable, "program.executable");
+        assert_keys_eq!(
+            *self.program.owner,
+            bpf_loader_upgradeable::ID,
+            "program.owner"
+        );
+        assert_keys_eq!(
+            *self.buffer.owner,
+            bpf_loader_upgradeable::ID,
+            InvalidProgramBuffer
+        );
+        // owner_index check happens later
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::propose_program_upgrade].
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct ProposeProgramUpgrade<'info> {
+    // The [Smallet], which must be the upgrade authority of the program.
+    pub smallet: Account<'info, Smallet>,
+    // The [Transaction], seeded like in [smallet::create_transaction_v2].
+    #[account(
+        init,
+        seeds = [
+            b"CosmicTransactionV2".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            proposer.key().to_bytes().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = Transaction::space(vec![
+            ProgramUpgrade::default().to_instruction(&Pubkey::default(), &Pubkey::default())
+        ]) + Transaction::approvals_space(&smallet),
+    )]
+    pub transaction: Account<'info, Transaction>,
+    /// CHECK: The program to upgrade. Checked in the validator.
+    pub program: UncheckedAccount<'info>,
+    /// CHECK: The buffer holding the new program data. Checked in the validator.
+    pub buffer: UncheckedAccount<'info>,
+    // One of the owners. Checked in the handler via [Smallet::try_owner_index].
+    pub proposer: Signer<'info>,
+    // Payer to create the [Transaction].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
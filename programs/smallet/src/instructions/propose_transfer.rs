@@ -0,0 +1,89 @@
+//! Instruction handler for [smallet::propose_transfer].
+
+use crate::*;
+
+// Instruction handler for [smallet::propose_transfer].
+pub fn handler(
+    ctx: Context<ProposeTransfer>,
+    _nonce: u64,
+    transfer: VaultTransfer,
+    eta: i64,
+) -> Result<()> {
+    invariant!(transfer.amount > 0, InvalidTransferAmount);
+    let smallet_key = ctx.accounts.smallet.key();
+    let ix = transfer.to_instruction(&smallet_key);
+
+    // Transfers to trusted entries of the address book, if passed, are
+    // payments of the book.
+    let address_book = find_address_book(ctx.remaining_accounts, &smallet_key);
+    let now = Clock::get()?.unix_timestamp;
+    let trusted = matches!(
+        &address_book,
+        Some(book) if book.is_trusted(&transfer.recipient, now)
+    );
+
+    do_create_transaction(
+        &ctx.accounts.smallet,
+        &mut ctx.accounts.transaction,
+        &ctx.accounts.proposer,
+        NO_INDEX,
+        *unwrap_int!(ctx.bumps.get("transaction")),
+        vec![ix],
+        eta,
+        TransactionOptions {
+            pda_signers: vec![transfer.signer(&smallet_key)],
+            address_book: trusted,
+            ..Default::default()
+        },
+        address_book.as_ref(),
+    )?;
+    ctx.accounts.transaction.transfer = Some(transfer);
+
+    emit!(TransferProposeEvent {
+        smallet: smallet_key,
+        transaction: ctx.accounts.transaction.key(),
+        proposer: ctx.accounts.proposer.key(),
+        transfer,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for ProposeTransfer<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        // owner_index check happens later
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::propose_transfer].
+#[derive(Accounts)]
+#[instruction(nonce: u64, transfer: VaultTransfer)]
+pub struct ProposeTransfer<'info> {
+    // The [Smallet].
+    pub smallet: Account<'info, Smallet>,
+    // The [Transaction], seeded like in [smallet::create_transaction_v2].
+    #[account(
+        init,
+        seeds = [
+            b"CosmicTransactionV2".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            proposer.key().to_bytes().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = Transaction::space(vec![transfer.to_instruction(&smallet.key())])
+            + PdaSigner::LEN
+            + Transaction::approvals_space(&smallet),
+    )]
+    pub transaction: Account<'info, Transaction>,
+    // One of the owners. Checked in the handler via [Smallet::try_owner_index].
+    pub proposer: Signer<'info>,
+    // Payer to create the [Transaction].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,43 @@
+//! Instruction handler for [smallet::remove_address_book_entry].
+
+use crate::*;
+
+// Instruction handler for [smallet::remove_address_book_entry].
+pub fn handler(ctx: Context<RemoveAddressBookEntry>, recipient: Pubkey) -> Result<()> {
+    let entries = &mut ctx.accounts.address_book.entries;
+    let position = unwrap_opt!(
+        entries.iter().position(|entry| entry.recipient == recipient),
+        AddressBookEntryNotFound
+    );
+    entries.remove(position);
+
+    emit!(AddressBookEntryRemoveEvent {
+        smallet: ctx.accounts.smallet.key(),
+        address_book: ctx.accounts.address_book.key(),
+        recipient,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for RemoveAddressBookEntry<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::remove_address_book_entry].
+#[derive(Accounts)]
+pub struct RemoveAddressBookEntry<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [AddressBook] to remove the entry from.
+    #[account(mut, has_one = smallet)]
+    pub address_book: Account<'info, AddressBook>,
+}
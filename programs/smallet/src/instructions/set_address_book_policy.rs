@@ -0,0 +1,50 @@
+//! Instruction handler for [smallet::set_address_book_policy].
+
+use crate::*;
+
+// Instruction handler for [smallet::set_address_book_policy].
+pub fn handler(
+    ctx: Context<SetAddressBookPolicy>,
+    threshold: u64,
+    minimum_delay: i64,
+    entry_delay: i64,
+) -> Result<()> {
+    AddressBook::validate_policy(&ctx.accounts.smallet, threshold, minimum_delay, entry_delay)?;
+
+    let address_book = &mut ctx.accounts.address_book;
+    address_book.threshold = threshold;
+    address_book.minimum_delay = minimum_delay;
+    address_book.entry_delay = entry_delay;
+
+    emit!(AddressBookPolicySetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        address_book: ctx.accounts.address_book.key(),
+        threshold,
+        minimum_delay,
+        entry_delay,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetAddressBookPolicy<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::set_address_book_policy].
+#[derive(Accounts)]
+pub struct SetAddressBookPolicy<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [AddressBook] to update.
+    #[account(mut, has_one = smallet)]
+    pub address_book: Account<'info, AddressBook>,
+}
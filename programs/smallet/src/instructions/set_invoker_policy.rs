@@ -0,0 +1,72 @@
+//! Instruction handler for [smallet::set_invoker_policy].
+
+use crate::*;
+
+// Instruction handler for [smallet::set_invoker_policy].
+pub fn handler(
+    ctx: Context<SetInvokerPolicy>,
+    owners: Vec<Pubkey>,
+    allowed_instructions: Vec<AllowedInstruction>,
+    max_calls_per_period: u64,
+    period: i64,
+) -> Result<()> {
+    InvokerPolicy::validate_rate_limit(max_calls_per_period, period)?;
+
+    let new_len = InvokerPolicy::space(&owners, &allowed_instructions);
+    let info = ctx.accounts.policy.to_account_info();
+    if new_len > info.data_len() {
+        realloc_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            new_len,
+        )?;
+    }
+
+    let policy = &mut ctx.accounts.policy;
+    policy.owners = owners.clone();
+    policy.allowed_instructions = allowed_instructions.clone();
+    policy.max_calls_per_period = max_calls_per_period;
+    policy.period = period;
+    policy.period_start = Clock::get()?.unix_timestamp;
+    policy.calls_in_period = 0;
+
+    emit!(InvokerPolicySetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        policy: ctx.accounts.policy.key(),
+        index: ctx.accounts.policy.index,
+        owners,
+        allowed_instructions,
+        max_calls_per_period,
+        period,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetInvokerPolicy<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::set_invoker_policy].
+#[derive(Accounts)]
+pub struct SetInvokerPolicy<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [InvokerPolicy] to update.
+    #[account(mut, has_one = smallet)]
+    pub policy: Account<'info, InvokerPolicy>,
+    // Payer of additional rent if the policy grows.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,62 @@
+//! Instruction handler for [smallet::set_optimistic_mode].
+
+use crate::*;
+
+// Instruction handler for [smallet::set_optimistic_mode].
+pub fn handler(ctx: Context<SetOptimisticMode>, config: Option<OptimisticConfig>) -> Result<()> {
+    if let Some(config) = &config {
+        invariant!(config.objection_threshold > 0, InvalidThreshold);
+        invariant!(
+            config.objection_threshold <= ctx.accounts.smallet.owners.len() as u64,
+            InvalidThreshold
+        );
+        invariant!(config.challenge_period >= 0, "delay must be positive");
+        invariant!(config.challenge_period < MAX_DELAY_SECONDS, DelayTooHigh);
+    }
+
+    // Only grow by the difference, so the room left for owners is preserved.
+    let space = |config: &Option<OptimisticConfig>| config.as_ref().map_or(0, |c| c.space());
+    let info = ctx.accounts.smallet.to_account_info();
+    let growth = space(&config).saturating_sub(space(&ctx.accounts.smallet.optimistic));
+    if growth > 0 {
+        realloc_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            unwrap_int!(info.data_len().checked_add(growth)),
+        )?;
+    }
+
+    ctx.accounts.smallet.optimistic = config.clone();
+
+    emit!(OptimisticModeSetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        config,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetOptimisticMode<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::set_optimistic_mode].
+#[derive(Accounts)]
+pub struct SetOptimisticMode<'info> {
+    // The [Smallet].
+    #[account(mut, signer)]
+    pub smallet: Account<'info, Smallet>,
+    // Payer of additional rent if the [Smallet] grows.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
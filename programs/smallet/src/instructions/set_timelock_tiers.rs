@@ -0,0 +1,61 @@
+//! Instruction handler for [smallet::set_timelock_tiers].
+
+use crate::*;
+
+// Instruction handler for [smallet::set_timelock_tiers].
+pub fn handler(ctx: Context<SetTimelockTiers>, tiers: Vec<TimelockTier>) -> Result<()> {
+    invariant!(tiers.len() <= usize::from(u8::MAX), "too many tiers");
+    let num_owners = ctx.accounts.smallet.owners.len() as u64;
+    for tier in tiers.iter() {
+        invariant!(tier.name.len() <= MAX_LABEL_LEN, LabelTooLong);
+        invariant!(tier.minimum_delay >= 0, "delay must be positive");
+        invariant!(tier.minimum_delay < MAX_DELAY_SECONDS, DelayTooHigh);
+        invariant!(tier.threshold <= num_owners, InvalidThreshold);
+    }
+
+    // Only grow by the difference, so the room left for owners is preserved.
+    let space = |tiers: &[TimelockTier]| tiers.iter().map(|tier| tier.space()).sum::<usize>();
+    let info = ctx.accounts.smallet.to_account_info();
+    let growth = space(&tiers).saturating_sub(space(&ctx.accounts.smallet.tiers));
+    if growth > 0 {
+        realloc_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            unwrap_int!(info.data_len().checked_add(growth)),
+        )?;
+    }
+
+    ctx.accounts.smallet.tiers = tiers.clone();
+
+    emit!(TimelockTiersSetEvent {
+        smallet: ctx.accounts.smallet.key(),
+        tiers,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for SetTimelockTiers<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::set_timelock_tiers].
+#[derive(Accounts)]
+pub struct SetTimelockTiers<'info> {
+    // The [Smallet].
+    #[account(mut, signer)]
+    pub smallet: Account<'info, Smallet>,
+    // Payer of additional rent if the [Smallet] grows.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -7,7 +7,7 @@ pub fn handler(ctx: Context<Approve>) -> Result<()> {
         .accounts
         .smallet
         .try_owner_index(ctx.accounts.owner.key())?;
-    ctx.accounts.transaction.signers[owner_index] = false;
+    ctx.accounts.transaction.set_approval(owner_index, None);
 
     emit!(TransactionUnapproveEvent {
         smallet: ctx.accounts.smallet.key(),
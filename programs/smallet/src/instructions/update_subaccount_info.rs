@@ -0,0 +1,45 @@
+//! Instruction handler for [smallet::update_subaccount_info].
+
+use crate::*;
+
+// Instruction handler for [smallet::update_subaccount_info].
+pub fn handler(ctx: Context<UpdateSubaccountInfo>, label: Option<String>) -> Result<()> {
+    if let Some(label) = &label {
+        invariant!(label.len() <= MAX_LABEL_LEN, LabelTooLong);
+    }
+    let info = &mut ctx.accounts.subaccount_info;
+    info.label = label.clone();
+
+    emit!(SubaccountUpdateEvent {
+        smallet: ctx.accounts.smallet.key(),
+        subaccount_info: ctx.accounts.subaccount_info.key(),
+        label,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for UpdateSubaccountInfo<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.smallet.to_account_info().is_signer,
+            "smallet.is_signer"
+        );
+        invariant!(
+            self.subaccount_info.version == SUBACCOUNT_INFO_VERSION,
+            AccountNotMigrated
+        );
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::update_subaccount_info].
+#[derive(Accounts)]
+pub struct UpdateSubaccountInfo<'info> {
+    // The [Smallet].
+    #[account(signer)]
+    pub smallet: Account<'info, Smallet>,
+    // The [SubaccountInfo] to update.
+    #[account(mut, has_one = smallet)]
+    pub subaccount_info: Account<'info, SubaccountInfo>,
+}
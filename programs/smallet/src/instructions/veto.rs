@@ -0,0 +1,88 @@
+//! Instruction handler for [smallet::veto].
+
+use crate::*;
+
+// Instruction handler for [smallet::veto].
+pub fn handler(ctx: Context<Veto>) -> Result<()> {
+    let smallet = &ctx.accounts.smallet;
+    let owner_index = smallet.try_owner_index(ctx.accounts.owner.key())?;
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    let tx = &mut ctx.accounts.transaction;
+    if tx.vetoes.is_empty() {
+        tx.vetoes.resize(smallet.owners.len(), false);
+    }
+    invariant!(!tx.vetoes[owner_index], "already vetoed");
+    tx.vetoes[owner_index] = true;
+
+    let num_vetoes = tx.vetoes.iter().filter(|&vetoed| *vetoed).count() as u64;
+    let blocked = num_vetoes >= smallet.veto_threshold_for(tx);
+    if blocked {
+        tx.vetoed_at = Some(current_ts);
+    }
+
+    // The vetoes are only allocated on the first veto.
+    let info = tx.to_account_info();
+    let len = unwrap_int!(8_usize.checked_add(tx.try_to_vec()?.len()));
+    if len > info.data_len() {
+        realloc_account(
+            &info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            len,
+        )?;
+    }
+
+    emit!(TransactionVetoEvent {
+        smallet: ctx.accounts.smallet.key(),
+        transaction: ctx.accounts.transaction.key(),
+        owner: ctx.accounts.owner.key(),
+        blocked,
+        timestamp: current_ts
+    });
+    Ok(())
+}
+
+impl<'info> Validate<'info> for Veto<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.smallet, self.transaction.smallet);
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.transaction.version == TRANSACTION_VERSION,
+            AccountNotMigrated
+        );
+        invariant!(
+            self.smallet.veto_threshold_for(&self.transaction) > 0,
+            VetoDisabled
+        );
+        invariant!(
+            self.smallet.owner_set_seqno == self.transaction.owner_set_seqno,
+            OwnerSetChanged
+        );
+        invariant!(self.transaction.executed_at == -1, AlreadyExecuted);
+        invariant!(self.transaction.vetoed_at.is_none(), TransactionVetoed);
+
+        // Vetoes are only possible during the timelock window.
+        let eta = self.transaction.eta;
+        invariant!(eta != NO_ETA, VetoWindowClosed);
+        invariant!(Clock::get()?.unix_timestamp < eta, VetoWindowClosed);
+        Ok(())
+    }
+}
+
+// Accounts for [smallet::veto].
+#[derive(Accounts)]
+pub struct Veto<'info> {
+    // The [Smallet].
+    pub smallet: Account<'info, Smallet>,
+    // The [Transaction].
+    #[account(mut, has_one = smallet)]
+    pub transaction: Account<'info, Transaction>,
+    // One of the smallet owners. Checked in the handler.
+    pub owner: Signer<'info>,
+    // Payer of additional rent if the [Transaction] grows.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    // The [System] program.
+    pub system_program: Program<'info, System>,
+}
@@ -8,7 +8,7 @@
 //! 1. Owners - the set of addresses that sign transactions for the smallet.
 //! 2. Threshold - the number of signers required to execute a transaction.
 //! 3. Minimum Delay - the minimum amount of time that must pass before a [Transaction]
-//!                    can be executed. If 0, this is ignored.
+//!    can be executed. If 0, this is ignored.
 //!
 //! Once the [Smallet] account is created, one can create a [Transaction]
 //! account, specifying the parameters for a normal Solana instruction.
@@ -18,6 +18,7 @@
 //! signed.
 
 #![allow(rustdoc::all)]
+#![allow(clippy::result_large_err)]
 #![allow(rustdoc::missing_doc_code_examples)]
 #![allow(clippy::unwrap_used)]
 
@@ -46,6 +47,21 @@ pub const DEFAULT_GRACE_PERIOD: i64 = 14 * SECONDS_PER_DAY;
 // Constant declaring that there is no ETA of the transaction
 pub const NO_ETA: i64 = -1;
 
+// Constant declaring that a transaction has not been assigned an index yet
+pub const NO_INDEX: u64 = u64::MAX;
+
+// The SPL Token program
+pub mod token_program {
+    use super::*;
+    declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+}
+
+// The SPL Associated Token Account program
+pub mod associated_token_program {
+    use super::*;
+    declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+}
+
 declare_id!("7vZw152zk65W5F59S6Svt29JjDhzZocWvF7PYvjNCAKB");
 
 #[program]
@@ -67,6 +83,7 @@ pub mod smallet {
         invariant!(minimum_delay < MAX_DELAY_SECONDS, DelayTooHigh);
 
         invariant!((max_owners as usize) >= owners.len(), "max_owners");
+        invariant!(threshold <= owners.len() as u64, InvalidThreshold);
 
         let smallet = &mut ctx.accounts.smallet;
         smallet.base = ctx.accounts.base.key();
@@ -80,6 +97,7 @@ pub mod smallet {
         smallet.num_transactions = 0;
 
         smallet.owners = owners.clone();
+        smallet.version = SMALLET_VERSION;
 
         emit!(WalletCreateEvent {
             smallet: ctx.accounts.smallet.key(),
@@ -93,12 +111,46 @@ pub mod smallet {
 
 	// Sets the owners field on the smallet. The only way this can be invoked 
 	// is via a recursive call from execute_transaction -> set_owners.
+	// If the address of the [AddressBook] is passed as a remaining account, its
+	// threshold is lowered too. Transactions proposed without it still execute:
+	// the book threshold is then clamped to the owner count when paying.
     #[access_control(ctx.accounts.validate())]
     pub fn set_owners(ctx: Context<Auth>, owners: Vec<Pubkey>) -> Result<()> {
+        let (book_address, _) = Pubkey::find_program_address(
+            &[b"CosmicAddressBook".as_ref(), ctx.accounts.smallet.key().as_ref()],
+            &crate::ID,
+        );
+        let book_info = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account| account.key() == book_address);
+        if let Some(book_info) = book_info.filter(|info| *info.owner == crate::ID) {
+            let mut book = AddressBook::try_deserialize(&mut &book_info.try_borrow_data()?[..])?;
+            if (owners.len() as u64) < book.threshold {
+                book.threshold = owners.len() as u64;
+                book.try_serialize(&mut &mut book_info.try_borrow_mut_data()?[..])?;
+                emit!(AddressBookPolicySetEvent {
+                    smallet: ctx.accounts.smallet.key(),
+                    address_book: book_address,
+                    threshold: book.threshold,
+                    minimum_delay: book.minimum_delay,
+                    entry_delay: book.entry_delay,
+                    timestamp: Clock::get()?.unix_timestamp
+                });
+            }
+        }
+
         let smallet = &mut ctx.accounts.smallet;
         if (owners.len() as u64) < smallet.threshold {
             smallet.threshold = owners.len() as u64;
         }
+        for tier in smallet.tiers.iter_mut() {
+            tier.threshold = tier.threshold.min(owners.len() as u64);
+        }
+        smallet.veto_threshold = smallet.veto_threshold.min(owners.len() as u64);
+        if let Some(config) = smallet.optimistic.as_mut() {
+            config.objection_threshold = config.objection_threshold.min(owners.len() as u64);
+        }
 
         smallet.owners = owners.clone();
         smallet.owner_set_seqno = unwrap_int!(smallet.owner_set_seqno.checked_add(1));
@@ -131,6 +183,64 @@ pub mod smallet {
         Ok(())
     }
 
+	// Changes the number of owner vetoes that block a timelocked transaction.
+	// 0 disables vetoes. The only way this can be invoked is via a recursive
+	// call from execute_transaction -> change_veto_threshold.
+    #[access_control(ctx.accounts.validate())]
+    pub fn change_veto_threshold(ctx: Context<Auth>, veto_threshold: u64) -> Result<()> {
+        invariant!(
+            veto_threshold <= ctx.accounts.smallet.owners.len() as u64,
+            InvalidThreshold
+        );
+        let smallet = &mut ctx.accounts.smallet;
+        smallet.veto_threshold = veto_threshold;
+
+        emit!(WalletChangeVetoThresholdEvent {
+            smallet: ctx.accounts.smallet.key(),
+            veto_threshold,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+
+	// Changes the time after which approvals expire. 0 disables expiry.
+	// The only way this can be invoked is via a recursive call from
+	// execute_transaction -> change_approval_ttl.
+    #[access_control(ctx.accounts.validate())]
+    pub fn change_approval_ttl(ctx: Context<Auth>, approval_ttl: i64) -> Result<()> {
+        invariant!(approval_ttl >= 0, "approval TTL must be positive");
+        let smallet = &mut ctx.accounts.smallet;
+        smallet.approval_ttl = approval_ttl;
+
+        emit!(WalletChangeApprovalTtlEvent {
+            smallet: ctx.accounts.smallet.key(),
+            approval_ttl,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+
+	// Enables or disables strict FIFO execution. When enabled, transactions
+	// from the current [Smallet::num_transactions] on are executed in order.
+	// The only way this can be invoked is via a recursive call from
+	// execute_transaction -> set_fifo_mode.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_fifo_mode(ctx: Context<Auth>, enabled: bool) -> Result<()> {
+        let smallet = &mut ctx.accounts.smallet;
+        smallet.fifo_cursor = if enabled {
+            Some(smallet.fifo_cursor.unwrap_or(smallet.num_transactions))
+        } else {
+            None
+        };
+
+        emit!(FifoCursorSetEvent {
+            smallet: ctx.accounts.smallet.key(),
+            cursor: ctx.accounts.smallet.fifo_cursor,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+
 	// Creates a new [Transaction] account, automatically signed by the creator, 
 	// which must be one of the owners of the smallet.
     pub fn create_transaction(
@@ -149,61 +259,110 @@ pub mod smallet {
         instructions: Vec<TXInstruction>,
         eta: i64,
     ) -> Result<()> {
-        let smallet = &ctx.accounts.smallet;
-        let owner_index = smallet.try_owner_index(ctx.accounts.proposer.key())?;
+        let smallet = &mut ctx.accounts.smallet;
+        // Legacy proposals cannot state a tier.
+        invariant!(smallet.tiers.is_empty(), TierRequired);
+        let index = smallet.num_transactions;
+        smallet.num_transactions = unwrap_int!(smallet.num_transactions.checked_add(1));
 
-        let clock = Clock::get()?;
-        let current_ts = clock.unix_timestamp;
-        if smallet.minimum_delay != 0 {
-            invariant!(
-                eta >= unwrap_int!(current_ts.checked_add(smallet.minimum_delay as i64)),
-                InvalidETA
-            );
-        }
-        if eta != NO_ETA {
-            invariant!(eta >= 0, "ETA must be positive");
-            let delay = unwrap_int!(eta.checked_sub(current_ts));
-            invariant!(delay >= 0, "ETA must be in the future");
-            invariant!(delay <= MAX_DELAY_SECONDS, DelayTooHigh);
-        }
+        do_create_transaction(
+            &ctx.accounts.smallet,
+            &mut ctx.accounts.transaction,
+            &ctx.accounts.proposer,
+            index,
+            *unwrap_int!(ctx.bumps.get("transaction")),
+            instructions,
+            eta,
+            TransactionOptions::default(),
+            None,
+        )
+    }
 
-		// generate the signers boolean list
-        let owners = &smallet.owners;
-        let mut signers = Vec::new();
-        signers.resize(owners.len(), false);
-        signers[owner_index] = true;
+	// Creates a new [Transaction] account seeded by the proposer and a nonce
+	// chosen by the proposer instead of [Smallet::num_transactions].
+	// The [Smallet] is not written to, so concurrent proposals do not collide.
+	// The index of the [Transaction] is assigned later via
+	// [smallet::assign_transaction_index].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_transaction_v2(
+        ctx: Context<CreateTransactionV2>,
+        nonce: u64,
+        instructions: Vec<TXInstruction>,
+        eta: i64,
+        options: TransactionOptions,
+    ) -> Result<()> {
+        instructions::create_transaction_v2::handler(ctx, nonce, instructions, eta, options)
+    }
 
-        let index = smallet.num_transactions;
-        let smallet = &mut ctx.accounts.smallet;
-        smallet.num_transactions = unwrap_int!(smallet.num_transactions.checked_add(1));
+	// Creates a new [Transaction] like [smallet::create_transaction_v2], but
+	// committing only to the hash of its instructions, computed via
+	// [Transaction::hash_instructions]. Owners verify the hash off chain before
+	// approving; the instructions are revealed at execution via
+	// [smallet::execute_committed_transaction].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_committed_transaction(
+        ctx: Context<CreateCommittedTransaction>,
+        nonce: u64,
+        instructions_hash: [u8; 32],
+        eta: i64,
+        options: TransactionOptions,
+    ) -> Result<()> {
+        instructions::create_committed_transaction::handler(
+            ctx,
+            nonce,
+            instructions_hash,
+            eta,
+            options,
+        )
+    }
 
-		// init the TX
-        let tx = &mut ctx.accounts.transaction;
-        tx.smallet = smallet.key();
-        tx.index = index;
-        tx.bump = *unwrap_int!(ctx.bumps.get("transaction"));
+	// Proposes upgrading a program whose upgrade authority is the [Smallet]
+	// to the contents of a buffer. The hash of the buffer is recorded on the
+	// [Transaction] and checked again at execution.
+    #[access_control(ctx.accounts.validate())]
+    pub fn propose_program_upgrade(
+        ctx: Context<ProposeProgramUpgrade>,
+        nonce: u64,
+        spill: Pubkey,
+        buffer_hash: [u8; 32],
+        eta: i64,
+    ) -> Result<()> {
+        instructions::propose_program_upgrade::handler(ctx, nonce, spill, buffer_hash, eta)
+    }
 
-        tx.proposer = ctx.accounts.proposer.key();
-        tx.instructions = instructions.clone();
-        tx.signers = signers;
-        tx.owner_set_seqno = smallet.owner_set_seqno;
-        tx.eta = eta;
+	// Creates a new [Transaction] seeded like in [smallet::create_transaction_v2]
+	// that moves SOL or SPL tokens out of a [SubaccountType::Derived] vault.
+	// The instruction and its signer are built by the program, and the
+	// transfer is recorded on the [Transaction] for reviewers and indexers.
+	// If the [AddressBook] is passed as a remaining account and trusts the
+	// recipient, the transfer is a payment of the book.
+    #[access_control(ctx.accounts.validate())]
+    pub fn propose_transfer(
+        ctx: Context<ProposeTransfer>,
+        nonce: u64,
+        transfer: VaultTransfer,
+        eta: i64,
+    ) -> Result<()> {
+        instructions::propose_transfer::handler(ctx, nonce, transfer, eta)
+    }
 
-        tx.executor = Pubkey::default();
-        tx.executed_at = -1;
+	// Assigns the next [Smallet::num_transactions] index to a [Transaction]
+	// created via [smallet::create_transaction_v2]. Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn assign_transaction_index(ctx: Context<AssignTransactionIndex>) -> Result<()> {
+        instructions::assign_transaction_index::handler(ctx)
+    }
 
-        emit!(TransactionCreateEvent {
-            smallet: ctx.accounts.smallet.key(),
-            transaction: ctx.accounts.transaction.key(),
-            proposer: ctx.accounts.proposer.key(),
-            instructions,
-            eta,
-            timestamp: Clock::get()?.unix_timestamp
-        });
-        Ok(())
+	// Moves the [Smallet::fifo_cursor] past the settled transactions passed as
+	// remaining accounts, so that they need not be passed to later executions.
+	// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn advance_fifo_cursor(ctx: Context<AdvanceFifoCursor>) -> Result<()> {
+        instructions::advance_fifo_cursor::handler(ctx)
     }
 
 	// Approves a transaction on behalf of an owner of the [Smallet]
+	// Approving again refreshes the approval if it expired.
     #[access_control(ctx.accounts.validate())]
     pub fn approve(ctx: Context<Approve>) -> Result<()> {
         instructions::approve::handler(ctx)
@@ -215,42 +374,102 @@ pub mod smallet {
         instructions::unapprove::handler(ctx)
     }
 
+	// Vetoes a timelocked transaction before its ETA on behalf of an owner of
+	// the [Smallet]. Once [Smallet::veto_threshold] owners have vetoed it, the
+	// transaction can never be executed.
+	// Vetoes of an optimistic transaction are objections, counted towards
+	// [OptimisticConfig::objection_threshold] instead.
+    #[access_control(ctx.accounts.validate())]
+    pub fn veto(ctx: Context<Veto>) -> Result<()> {
+        instructions::veto::handler(ctx)
+    }
+
 	// Executes the given transaction if threshold owners have signed it.
+	// The instructions are signed by every PDA declared on the [Transaction].
     #[access_control(ctx.accounts.validate())]
     pub fn execute_transaction(ctx: Context<ExecuteTransaction>) -> Result<()> {
-        let smallet = &ctx.accounts.smallet;
-        let wallet_seeds: &[&[&[u8]]] = &[&[
-            b"CosmicSmallet" as &[u8],
-            &smallet.base.to_bytes(),
-            &[smallet.bump],
-        ]];
-        do_execute_transaction(ctx, wallet_seeds)
+        invariant!(
+            ctx.accounts.transaction.instructions_hash.is_none(),
+            InstructionsNotRevealed
+        );
+        let instructions = ctx.accounts.transaction.instructions.clone();
+        do_execute_transaction(ctx, &instructions, None)
     }
 
 	// Executes the given transaction signed by the given derived address,
     // if threshold owners have signed it.
     // This allows a Smallet to receive SOL.
+    // The derived address must have been declared as a signer of the
+    // [Transaction] when it was created, so that owners know which
+    // address they are approving instructions for. Transactions proposed
+    // via [smallet::create_transaction] declare no signers, and may still be
+    // signed by any derived address, as before signers could be declared.
     #[access_control(ctx.accounts.validate())]
     pub fn execute_transaction_derived(
         ctx: Context<ExecuteTransaction>,
         index: u64,
         bump: u8,
     ) -> Result<()> {
-        let smallet = &ctx.accounts.smallet;
-        // Execute the transaction signed by the smallet.
-        let wallet_seeds: &[&[&[u8]]] = &[&[
-            b"CosmicSmalletDerived" as &[u8],
-            &smallet.key().to_bytes(),
-            &index.to_le_bytes(),
-            &[bump],
-        ]];
-        do_execute_transaction(ctx, wallet_seeds)
+        let signer = PdaSigner::Derived { index, bump };
+        if ctx.accounts.transaction.pda_signers.contains(&signer) {
+            return execute_transaction(ctx);
+        }
+        let transaction = &ctx.accounts.transaction;
+        let legacy_address = Pubkey::create_program_address(
+            &[
+                b"CosmicTransaction".as_ref(),
+                ctx.accounts.smallet.key().as_ref(),
+                transaction.index.to_le_bytes().as_ref(),
+                &[transaction.bump],
+            ],
+            &crate::ID,
+        );
+        invariant!(
+            transaction.pda_signers.is_empty() && legacy_address.ok() == Some(transaction.key()),
+            DerivedSignerNotDeclared
+        );
+        invariant!(
+            transaction.instructions_hash.is_none(),
+            InstructionsNotRevealed
+        );
+        signer.validate_bump(&ctx.accounts.smallet.key(), &ctx.accounts.smallet)?;
+        let instructions = transaction.instructions.clone();
+        do_execute_transaction(ctx, &instructions, Some(signer))
+    }
+
+	// Executes a [Transaction] created via [smallet::create_committed_transaction]
+	// if threshold owners have signed it. The given instructions, salted with
+	// `salt`, must hash to the committed [Transaction::instructions_hash].
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_committed_transaction(
+        ctx: Context<ExecuteTransaction>,
+        salt: [u8; 32],
+        instructions: Vec<TXInstruction>,
+    ) -> Result<()> {
+        let instructions_hash = unwrap_opt!(
+            ctx.accounts.transaction.instructions_hash,
+            TransactionNotCommitted
+        );
+        invariant!(
+            Transaction::hash_instructions(&salt, &instructions)? == instructions_hash,
+            InstructionsHashMismatch
+        );
+
+        emit!(TransactionRevealEvent {
+            smallet: ctx.accounts.smallet.key(),
+            transaction: ctx.accounts.transaction.key(),
+            salt,
+            instructions: instructions.clone(),
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        do_execute_transaction(ctx, &instructions, None)
     }
 
 	// Invokes an arbitrary instruction as a PDA derived from the owner,
     // i.e. as an "Owner Invoker".
     // This is useful for using the multisig as a whitelist or as a council,
     // e.g. a whitelist of approved owners.
+    // The call must be allowed by the [InvokerPolicy] of the owner invoker.
     #[access_control(ctx.accounts.validate())]
     pub fn owner_invoke_instruction(
         ctx: Context<OwnerInvokeInstruction>,
@@ -258,7 +477,17 @@ pub mod smallet {
         bump: u8,
         ix: TXInstruction,
     ) -> Result<()> {
+        ctx.accounts.policy.record_call(
+            ctx.accounts.owner.key(),
+            ix.program_id,
+            &ix.data,
+            Clock::get()?.unix_timestamp,
+        )?;
+
         let smallet = &ctx.accounts.smallet;
+        let signer = PdaSigner::OwnerInvoker { index, bump };
+        signer.validate_bump(&smallet.key(), smallet)?;
+        let invoker = unwrap_opt!(signer.address(&smallet.key(), smallet), InvalidInvoker);
         let invoker_seeds: &[&[&[u8]]] = &[&[
             b"CosmicSmalletOwnerInvoker" as &[u8],
             &smallet.key().to_bytes(),
@@ -272,6 +501,14 @@ pub mod smallet {
             invoker_seeds,
         )?;
 
+        emit!(OwnerInvokeEvent {
+            smallet: ctx.accounts.smallet.key(),
+            invoker,
+            index,
+            owner: ctx.accounts.owner.key(),
+            program_id: ix.program_id,
+            timestamp: Clock::get()?.unix_timestamp
+        });
         Ok(())
     }
 
@@ -280,6 +517,7 @@ pub mod smallet {
     //
     // This is useful for using the multisig as a whitelist or as a council,
     // e.g. a whitelist of approved owners.
+    // The call must be allowed by the [InvokerPolicy] of the owner invoker.
     //
     // # Arguments
     // - `index` - The index of the owner-invoker.
@@ -295,6 +533,12 @@ pub mod smallet {
         data: Vec<u8>,
     ) -> Result<()> {
         let smallet = &ctx.accounts.smallet;
+        // The invoker must be the owner invoker derived from `index` and `bump`.
+        let signer = PdaSigner::OwnerInvoker { index, bump };
+        signer.validate_bump(&smallet.key(), smallet)?;
+        let address = unwrap_opt!(signer.address(&smallet.key(), smallet), InvalidInvoker);
+        assert_keys_eq!(address, invoker, InvalidInvoker);
+
         // Execute the transaction signed by the smallet.
         let invoker_seeds: &[&[&[u8]]] = &[&[
             b"CosmicSmalletOwnerInvoker" as &[u8],
@@ -303,7 +547,14 @@ pub mod smallet {
             &[bump],
         ]];
 
-        let program_id = ctx.remaining_accounts[0].key();
+        let program_id = unwrap_opt!(ctx.remaining_accounts.first(), MissingInvokedProgram).key();
+        ctx.accounts.policy.record_call(
+            ctx.accounts.owner.key(),
+            program_id,
+            &data,
+            Clock::get()?.unix_timestamp,
+        )?;
+
         let accounts: Vec<AccountMeta> = ctx.remaining_accounts[1..]
             .iter()
             .map(|v| AccountMeta {
@@ -319,11 +570,152 @@ pub mod smallet {
         };
 
         solana_program::program::invoke_signed(ix, ctx.remaining_accounts, invoker_seeds)?;
+
+        emit!(OwnerInvokeEvent {
+            smallet: ctx.accounts.smallet.key(),
+            invoker,
+            index,
+            owner: ctx.accounts.owner.key(),
+            program_id,
+            timestamp: Clock::get()?.unix_timestamp
+        });
         Ok(())
     }
 
+	// Migrates a legacy [Smallet] account to the current layout, reallocating
+	// it if needed. Anyone may call this, since the migration is deterministic.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_smallet(ctx: Context<MigrateSmallet>) -> Result<()> {
+        instructions::migrate_smallet::handler(ctx)
+    }
+
+	// Migrates a legacy [Transaction] account to the current layout, reallocating
+	// it if needed. Anyone may call this, since the migration is deterministic.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_transaction(ctx: Context<MigrateTransaction>) -> Result<()> {
+        instructions::migrate_transaction::handler(ctx)
+    }
+
+	// Creates the [InvokerPolicy] of an owner invoker. The only way this can be
+	// invoked is via a recursive call from execute_transaction ->
+	// create_invoker_policy.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_invoker_policy(
+        ctx: Context<CreateInvokerPolicy>,
+        index: u64,
+        owners: Vec<Pubkey>,
+        allowed_instructions: Vec<AllowedInstruction>,
+        max_calls_per_period: u64,
+        period: i64,
+    ) -> Result<()> {
+        instructions::create_invoker_policy::handler(
+            ctx,
+            index,
+            owners,
+            allowed_instructions,
+            max_calls_per_period,
+            period,
+        )
+    }
+
+	// Replaces the rules of an [InvokerPolicy]. The only way this can be
+	// invoked is via a recursive call from execute_transaction ->
+	// set_invoker_policy.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_invoker_policy(
+        ctx: Context<SetInvokerPolicy>,
+        owners: Vec<Pubkey>,
+        allowed_instructions: Vec<AllowedInstruction>,
+        max_calls_per_period: u64,
+        period: i64,
+    ) -> Result<()> {
+        instructions::set_invoker_policy::handler(
+            ctx,
+            owners,
+            allowed_instructions,
+            max_calls_per_period,
+            period,
+        )
+    }
+
+	// Replaces the [TimelockTier]s of the [Smallet]. The only way this can be
+	// invoked is via a recursive call from execute_transaction ->
+	// set_timelock_tiers.
+	// Pending transactions are checked against the tier at their index when
+	// executed, so replacing the tiers also applies to them.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_timelock_tiers(
+        ctx: Context<SetTimelockTiers>,
+        tiers: Vec<TimelockTier>,
+    ) -> Result<()> {
+        instructions::set_timelock_tiers::handler(ctx, tiers)
+    }
+
+	// Enables, replaces or disables (with `None`) the [OptimisticConfig] of the
+	// [Smallet]. The only way this can be invoked is via a recursive call from
+	// execute_transaction -> set_optimistic_mode.
+	// Pending optimistic transactions need approvals again once their proposer
+	// is no longer authorized.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_optimistic_mode(
+        ctx: Context<SetOptimisticMode>,
+        config: Option<OptimisticConfig>,
+    ) -> Result<()> {
+        instructions::set_optimistic_mode::handler(ctx, config)
+    }
+
+	// Creates the [AddressBook] of the [Smallet], initially empty. The only way
+	// this can be invoked is via a recursive call from execute_transaction ->
+	// create_address_book.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_address_book(
+        ctx: Context<CreateAddressBook>,
+        threshold: u64,
+        minimum_delay: i64,
+        entry_delay: i64,
+    ) -> Result<()> {
+        instructions::create_address_book::handler(ctx, threshold, minimum_delay, entry_delay)
+    }
+
+	// Changes the threshold and delays of the [AddressBook]. The only way this
+	// can be invoked is via a recursive call from execute_transaction ->
+	// set_address_book_policy.
+	// Pending payments are checked against the threshold when executed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_address_book_policy(
+        ctx: Context<SetAddressBookPolicy>,
+        threshold: u64,
+        minimum_delay: i64,
+        entry_delay: i64,
+    ) -> Result<()> {
+        instructions::set_address_book_policy::handler(ctx, threshold, minimum_delay, entry_delay)
+    }
+
+	// Adds a recipient to the [AddressBook], trusted after
+	// [AddressBook::entry_delay]. The only way this can be invoked is via a
+	// recursive call from execute_transaction -> add_address_book_entry.
+    #[access_control(ctx.accounts.validate())]
+    pub fn add_address_book_entry(
+        ctx: Context<AddAddressBookEntry>,
+        recipient: Pubkey,
+        label: String,
+    ) -> Result<()> {
+        instructions::add_address_book_entry::handler(ctx, recipient, label)
+    }
+
+	// Removes a recipient from the [AddressBook]. Pending payments of the
+	// recipient can no longer be executed. The only way this can be invoked is
+	// via a recursive call from execute_transaction -> remove_address_book_entry.
+    #[access_control(ctx.accounts.validate())]
+    pub fn remove_address_book_entry(
+        ctx: Context<RemoveAddressBookEntry>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_address_book_entry::handler(ctx, recipient)
+    }
+
 	// Creates a struct containing a reverse mapping of a subaccount to a
-    // [Smallet].
+    // [Smallet], and registers the subaccount on the [Smallet].
     #[access_control(ctx.accounts.validate())]
     pub fn create_subaccount_info(
         ctx: Context<CreateSubaccountInfo>,
@@ -332,35 +724,71 @@ pub mod smallet {
         smallet: Pubkey,
         index: u64,
         subaccount_type: SubaccountType,
+        label: Option<String>,
     ) -> Result<()> {
-        let (address, _derived_bump) = match subaccount_type {
-            SubaccountType::Derived => Pubkey::find_program_address(
-                &[
-                    b"CosmicSmalletDerived" as &[u8],
-                    &smallet.to_bytes(),
-                    &index.to_le_bytes(),
-                ],
-                &crate::ID,
-            ),
-            SubaccountType::OwnerInvoker => Pubkey::find_program_address(
-                &[
-                    b"CosmicSmalletOwnerInvoker" as &[u8],
-                    &smallet.to_bytes(),
-                    &index.to_le_bytes(),
-                ],
-                &crate::ID,
-            ),
-        };
+        assert_keys_eq!(smallet, ctx.accounts.smallet, "smallet");
+        let (address, _derived_bump) = subaccount_type.find_address(&smallet, index);
 
         invariant!(address == subaccount, SubaccountOwnerMismatch);
+        if let Some(label) = &label {
+            invariant!(label.len() <= MAX_LABEL_LEN, LabelTooLong);
+        }
+
+        let smallet_account = &mut ctx.accounts.smallet;
+        let registry_index = smallet_account.num_subaccounts;
+        smallet_account.num_subaccounts =
+            unwrap_int!(smallet_account.num_subaccounts.checked_add(1));
+
+        let entry = &mut ctx.accounts.registry_entry;
+        entry.smallet = smallet;
+        entry.registry_index = registry_index;
+        entry.subaccount = subaccount;
 
+        let created_at = Clock::get()?.unix_timestamp;
         let info = &mut ctx.accounts.subaccount_info;
         info.smallet = smallet;
         info.subaccount_type = subaccount_type;
         info.index = index;
+        info.version = SUBACCOUNT_INFO_VERSION;
+        info.registry_index = registry_index;
+        info.created_at = created_at;
+        info.label = label.clone();
 
+        emit!(SubaccountCreateEvent {
+            smallet,
+            subaccount,
+            subaccount_type,
+            index,
+            registry_index,
+            label,
+            timestamp: created_at
+        });
         Ok(())
     }
+
+	// Sets the label of a [SubaccountInfo]. The only way this can be invoked
+	// is via a recursive call from execute_transaction -> update_subaccount_info.
+    #[access_control(ctx.accounts.validate())]
+    pub fn update_subaccount_info(
+        ctx: Context<UpdateSubaccountInfo>,
+        label: Option<String>,
+    ) -> Result<()> {
+        instructions::update_subaccount_info::handler(ctx, label)
+    }
+
+	// Closes a [SubaccountInfo] and its registry entry. The only way this can be
+	// invoked is via a recursive call from execute_transaction -> close_subaccount_info.
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_subaccount_info(ctx: Context<CloseSubaccountInfo>) -> Result<()> {
+        instructions::close_subaccount_info::handler(ctx)
+    }
+
+	// Migrates a legacy [SubaccountInfo] account to the current layout and
+	// registers it on its [Smallet]. Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn migrate_subaccount_info(ctx: Context<MigrateSubaccountInfo>) -> Result<()> {
+        instructions::migrate_subaccount_info::handler(ctx)
+    }
 }
 // Accounts for [smallet::create_smallet].
 #[derive(Accounts)]
@@ -410,7 +838,8 @@ pub struct CreateTransaction<'info> {
         ],
         bump,
         payer = payer,
-        space = Transaction::space(instructions),
+        space = Transaction::space(instructions)
+            + Transaction::approvals_space(&smallet),
     )]
     pub transaction: Account<'info, Transaction>,
     // One of the owners. Checked in the handler via [Smallet::try_owner_index].
@@ -436,11 +865,23 @@ pub struct ExecuteTransaction<'info> {
 
 // Accounts for [smallet::owner_invoke_instruction].
 #[derive(Accounts)]
+#[instruction(index: u64)]
 pub struct OwnerInvokeInstruction<'info> {
 	// The [Smallet]
     pub smallet: Account<'info, Smallet>,
     // An owner of the [Smallet].
     pub owner: Signer<'info>,
+    // The [InvokerPolicy] of the owner invoker.
+    #[account(
+        mut,
+        seeds = [
+            b"CosmicInvokerPolicy".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            index.to_le_bytes().as_ref()
+        ],
+        bump = policy.bump,
+    )]
+    pub policy: Account<'info, InvokerPolicy>,
 }
 
 // Accounts for [smallet::create_subaccount_info].
@@ -459,6 +900,24 @@ pub struct CreateSubaccountInfo<'info> {
         space = 8 + SubaccountInfo::LEN
     )]
     pub subaccount_info: Account<'info, SubaccountInfo>,
+    // The [Smallet] to register the sub-account on.
+    #[account(mut)]
+    pub smallet: Account<'info, Smallet>,
+    // The [SubaccountRegistryEntry] to create.
+    #[account(
+        init,
+        seeds = [
+            b"CosmicSubaccountRegistry".as_ref(),
+            smallet.key().to_bytes().as_ref(),
+            smallet.num_subaccounts.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + SubaccountRegistryEntry::LEN
+    )]
+    pub registry_entry: Account<'info, SubaccountRegistryEntry>,
+    // One of the owners of the [Smallet].
+    pub owner: Signer<'info>,
     // Payer to create the [SubaccountInfo].
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -466,9 +925,318 @@ pub struct CreateSubaccountInfo<'info> {
     pub system_program: Program<'info, System>,
 }
 
-fn do_execute_transaction(ctx: Context<ExecuteTransaction>, seeds: &[&[&[u8]]]) -> Result<()> {
-    for ix in ctx.accounts.transaction.instructions.iter() {
-        solana_program::program::invoke_signed(&(ix).into(), ctx.remaining_accounts, seeds)?;
+// Initializes a [Transaction], automatically approved by the proposer.
+// Optimistic proposers need not be owners, in which case nobody approves.
+#[allow(clippy::too_many_arguments)]
+fn do_create_transaction<'info>(
+    smallet: &Account<'info, Smallet>,
+    transaction: &mut Account<'info, Transaction>,
+    proposer: &Signer<'info>,
+    index: u64,
+    bump: u8,
+    instructions: Vec<TXInstruction>,
+    eta: i64,
+    options: TransactionOptions,
+    address_book: Option<&AddressBook>,
+) -> Result<()> {
+    let owner_index = if options.optimistic {
+        let config = unwrap_opt!(smallet.optimistic.as_ref(), OptimisticModeDisabled);
+        invariant!(
+            config.proposers.contains(&proposer.key()),
+            UnauthorizedProposer
+        );
+        config.check_instructions(&instructions)?;
+        smallet.owner_index_opt(proposer.key())
+    } else {
+        Some(smallet.try_owner_index(proposer.key())?)
+    };
+    for signer in options.pda_signers.iter() {
+        signer.validate_bump(&smallet.key(), smallet)?;
+    }
+    if let Some(tier) = options.tier {
+        smallet.try_tier(tier)?.check_instructions(&instructions)?;
+    }
+    for prerequisite in options.prerequisites.iter() {
+        invariant!(
+            *prerequisite < smallet.num_transactions && *prerequisite != index,
+            InvalidPrerequisite
+        );
+    }
+    for pre_condition in options.pre_conditions.iter() {
+        pre_condition.validate()?;
+    }
+
+    let clock = Clock::get()?;
+    let current_ts = clock.unix_timestamp;
+    let minimum_delay = if options.address_book {
+        invariant!(
+            options.tier.is_none() && !options.optimistic,
+            "address book payments have their own threshold"
+        );
+        unwrap_opt!(address_book, MissingAddressBook).minimum_delay
+    } else {
+        smallet.minimum_delay_for(options.tier)?
+    };
+    if minimum_delay != 0 {
+        invariant!(
+            eta >= unwrap_int!(current_ts.checked_add(minimum_delay)),
+            InvalidETA
+        );
+    }
+    if let (true, Some(config)) = (options.optimistic, &smallet.optimistic) {
+        // Owners need time to object.
+        invariant!(eta != NO_ETA, InvalidETA);
+        invariant!(
+            eta >= unwrap_int!(current_ts.checked_add(config.challenge_period)),
+            InvalidETA
+        );
+    }
+    if eta != NO_ETA {
+        invariant!(eta >= 0, "ETA must be positive");
+        let delay = unwrap_int!(eta.checked_sub(current_ts));
+        invariant!(delay >= 0, "ETA must be in the future");
+        invariant!(delay <= MAX_DELAY_SECONDS, DelayTooHigh);
+    }
+
+	// generate the signers boolean list
+    let owners = &smallet.owners;
+    let mut signers = Vec::new();
+    signers.resize(owners.len(), false);
+    let mut approved_at = vec![0; owners.len()];
+    if let Some(owner_index) = owner_index {
+        signers[owner_index] = true;
+        approved_at[owner_index] = current_ts;
+    }
+
+	// init the TX
+    let tx = transaction;
+    tx.smallet = smallet.key();
+    tx.index = index;
+    tx.bump = bump;
+
+    tx.proposer = proposer.key();
+    tx.instructions = instructions.clone();
+    tx.signers = signers;
+    tx.approved_at = approved_at;
+    tx.owner_set_seqno = smallet.owner_set_seqno;
+    tx.eta = eta;
+
+    tx.executor = Pubkey::default();
+    tx.executed_at = -1;
+    tx.version = TRANSACTION_VERSION;
+    tx.pda_signers = options.pda_signers;
+    tx.tier = options.tier;
+    tx.optimistic = options.optimistic;
+    tx.prerequisites = options.prerequisites;
+    tx.pre_conditions = options.pre_conditions;
+    tx.post_conditions = options.post_conditions;
+    tx.address_book = options.address_book;
+
+    emit!(TransactionCreateEvent {
+        smallet: smallet.key(),
+        transaction: tx.key(),
+        proposer: proposer.key(),
+        instructions,
+        eta,
+        timestamp: current_ts
+    });
+    Ok(())
+}
+
+// Finds the [Transaction] of `smallet` at `index` among `accounts`.
+fn find_indexed_transaction(
+    accounts: &[AccountInfo],
+    smallet: &Pubkey,
+    index: u64,
+) -> Option<Transaction> {
+    accounts
+        .iter()
+        .filter(|account| *account.owner == crate::ID)
+        .find_map(|account| {
+            let data = account.try_borrow_data().ok()?;
+            let tx = Transaction::try_deserialize_versioned(&data).ok()?;
+            (tx.smallet == *smallet && tx.index == index).then_some(tx)
+        })
+}
+
+// Finds the [AddressBook] of `smallet` among `accounts`.
+fn find_address_book(accounts: &[AccountInfo], smallet: &Pubkey) -> Option<AddressBook> {
+    accounts
+        .iter()
+        .filter(|account| *account.owner == crate::ID)
+        .find_map(|account| {
+            let data = account.try_borrow_data().ok()?;
+            let book = AddressBook::try_deserialize(&mut &data[..]).ok()?;
+            (book.smallet == *smallet).then_some(book)
+        })
+}
+
+// Resizes an account owned by this program, topping up its rent from the payer.
+fn realloc_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(new_len);
+    let missing = required.saturating_sub(info.lamports());
+    if missing > 0 {
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(&payer.key(), info.key, missing),
+            &[
+                payer.to_account_info(),
+                info.clone(),
+                system_program.to_account_info(),
+            ],
+        )?;
+    }
+    info.realloc(new_len, false)?;
+    Ok(())
+}
+
+// Invokes `instructions` signed by every PDA declared on the [Transaction],
+// or by `legacy_signer` alone if given, then marks the [Transaction] as executed.
+fn do_execute_transaction(
+    ctx: Context<ExecuteTransaction>,
+    instructions: &[TXInstruction],
+    legacy_signer: Option<PdaSigner>,
+) -> Result<()> {
+    // The tier or the optimistic mode may have been replaced since the
+    // proposal, and the instructions of a committed transaction are only
+    // known now.
+    if let Some(tier) = ctx.accounts.transaction.tier {
+        ctx.accounts
+            .smallet
+            .try_tier(tier)?
+            .check_instructions(instructions)?;
+    }
+    if let (true, Some(config)) = (
+        ctx.accounts.smallet.is_optimistic(&ctx.accounts.transaction),
+        &ctx.accounts.smallet.optimistic,
+    ) {
+        config.check_instructions(instructions)?;
+    }
+
+    // Earlier transactions are passed as remaining accounts.
+    let smallet = &ctx.accounts.smallet;
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    // Entries may have been removed since the proposal, so payments are
+    // checked against the [AddressBook] as it is now.
+    let book = find_address_book(ctx.remaining_accounts, &smallet.key());
+    if ctx.accounts.transaction.address_book {
+        let book = unwrap_opt!(book.as_ref(), MissingAddressBook);
+        let sig_count = ctx
+            .accounts
+            .transaction
+            .num_fresh_signers(smallet.approval_ttl, current_ts);
+        invariant!(
+            sig_count as u64 >= smallet.address_book_threshold(book),
+            NotEnoughSigners
+        );
+        for ix in instructions.iter() {
+            book.check_payment(ix, ctx.remaining_accounts, current_ts)?;
+        }
+    }
+    for prerequisite in ctx.accounts.transaction.prerequisites.iter() {
+        let earlier =
+            find_indexed_transaction(ctx.remaining_accounts, &smallet.key(), *prerequisite);
+        invariant!(
+            matches!(earlier, Some(earlier) if earlier.executed_at != -1),
+            PrerequisiteNotExecuted
+        );
+    }
+    if let Some(cursor) = smallet.fifo_cursor {
+        let index = ctx.accounts.transaction.index;
+        invariant!(index != NO_INDEX, TransactionNotIndexed);
+        for earlier_index in cursor..index {
+            let earlier = unwrap_opt!(
+                find_indexed_transaction(ctx.remaining_accounts, &smallet.key(), earlier_index),
+                EarlierTransactionPending
+            );
+            // Address books are never closed, so earlier payments of its
+            // entries can only be skipped if it is passed.
+            invariant!(
+                !earlier.address_book || book.is_some(),
+                MissingAddressBook
+            );
+            invariant!(
+                smallet.is_settled(&earlier, current_ts)
+                    || !smallet.is_approved(&earlier, book.as_ref(), current_ts),
+                EarlierTransactionPending
+            );
+        }
+    }
+
+    // The accounts must still be in the state the owners approved.
+    for pre_condition in ctx.accounts.transaction.pre_conditions.iter() {
+        let account = unwrap_opt!(
+            ctx.remaining_accounts
+                .iter()
+                .find(|account| account.key() == pre_condition.account),
+            MissingPreConditionAccount
+        );
+        pre_condition.check(account)?;
+    }
+    let balances_before = ctx
+        .accounts
+        .transaction
+        .post_conditions
+        .iter()
+        .map(|post_condition| {
+            let account = unwrap_opt!(
+                ctx.remaining_accounts
+                    .iter()
+                    .find(|account| account.key() == post_condition.account),
+                MissingPostConditionAccount
+            );
+            Ok((account, post_condition.balance(account)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Ensure the buffer of a program upgrade is the one that was approved.
+    if let Some(upgrade) = ctx.accounts.transaction.program_upgrade {
+        let buffer = unwrap_opt!(
+            ctx.remaining_accounts
+                .iter()
+                .find(|account| account.key() == upgrade.buffer),
+            MissingProgramBuffer
+        );
+        invariant!(
+            ProgramUpgrade::hash_buffer(buffer)? == upgrade.buffer_hash,
+            BufferHashMismatch
+        );
+    }
+
+    let seeds = match legacy_signer {
+        Some(signer) => vec![signer.seeds(&ctx.accounts.smallet.key(), &ctx.accounts.smallet)],
+        None => ctx
+            .accounts
+            .transaction
+            .signer_seeds(&ctx.accounts.smallet.key(), &ctx.accounts.smallet),
+    };
+    let seeds: Vec<Vec<&[u8]>> = seeds
+        .iter()
+        .map(|pda| pda.iter().map(Vec::as_slice).collect())
+        .collect();
+    let wallet_seeds: Vec<&[&[u8]]> = seeds.iter().map(Vec::as_slice).collect();
+    for ix in instructions.iter() {
+        solana_program::program::invoke_signed(
+            &(ix).into(),
+            ctx.remaining_accounts,
+            &wallet_seeds,
+        )?;
+    }
+
+    // Abort if the instructions moved more than the owners approved.
+    for (post_condition, (account, before)) in ctx
+        .accounts
+        .transaction
+        .post_conditions
+        .iter()
+        .zip(balances_before)
+    {
+        post_condition.check(account, before)?;
     }
 	
 	// Burn the transaction to ensure one time use.
@@ -476,6 +1244,19 @@ fn do_execute_transaction(ctx: Context<ExecuteTransaction>, seeds: &[&[&[u8]]])
     tx.executor = ctx.accounts.owner.key();
     tx.executed_at = Clock::get()?.unix_timestamp;
 
+    if let Some(transfer) = ctx.accounts.transaction.transfer {
+        let (source, destination) = transfer.endpoints(&ctx.accounts.smallet.key());
+        emit!(TransferExecuteEvent {
+            smallet: ctx.accounts.smallet.key(),
+            transaction: ctx.accounts.transaction.key(),
+            asset: transfer.asset,
+            amount: transfer.amount,
+            source,
+            destination,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+    }
+
     emit!(TransactionExecuteEvent {
         smallet: ctx.accounts.smallet.key(),
         transaction: ctx.accounts.transaction.key(),
@@ -518,4 +1299,86 @@ pub enum ErrorCode {
     BufferBundleNotFinalized,
     #[msg("Buffer bundle has already been executed.")]
     BufferBundleExecuted,
+    #[msg("Transaction has already been assigned an index.")]
+    TransactionIndexAlreadyAssigned,
+    #[msg("Account must be migrated to the current layout.")]
+    AccountNotMigrated,
+    #[msg("Account has already been migrated.")]
+    AccountAlreadyMigrated,
+    #[msg("Program buffer is not a valid upgradeable loader buffer.")]
+    InvalidProgramBuffer,
+    #[msg("Program buffer account was not provided.")]
+    MissingProgramBuffer,
+    #[msg("Program buffer has changed since the upgrade was proposed.")]
+    BufferHashMismatch,
+    #[msg("Bump seed of a PDA signer is not canonical.")]
+    InvalidSignerBump,
+    #[msg("Derived address was not declared as a signer of the transaction.")]
+    DerivedSignerNotDeclared,
+    #[msg("Owner is not allowed to use this owner invoker.")]
+    InvokerNotAllowed,
+    #[msg("Instruction is not allowed by the invoker policy.")]
+    InstructionNotAllowed,
+    #[msg("Owner invoker has reached its call limit for the period.")]
+    InvokerCallLimitExceeded,
+    #[msg("Invoker is not the owner invoker derived from the given index and bump.")]
+    InvalidInvoker,
+    #[msg("Label is too long.")]
+    LabelTooLong,
+    #[msg("Instructions of a committed transaction must be revealed to execute it.")]
+    InstructionsNotRevealed,
+    #[msg("Transaction did not commit to the hash of its instructions.")]
+    TransactionNotCommitted,
+    #[msg("Revealed instructions do not match the committed hash.")]
+    InstructionsHashMismatch,
+    #[msg("Timelock tier does not exist on this smallet.")]
+    InvalidTier,
+    #[msg("Instruction is not allowed in the timelock tier of the transaction.")]
+    InstructionNotInTier,
+    #[msg("Vetoes are disabled on this smallet.")]
+    VetoDisabled,
+    #[msg("Transaction can only be vetoed before its ETA.")]
+    VetoWindowClosed,
+    #[msg("Transaction was vetoed.")]
+    TransactionVetoed,
+    #[msg("Optimistic mode is disabled on this smallet.")]
+    OptimisticModeDisabled,
+    #[msg("Proposer is not authorized to propose optimistically.")]
+    UnauthorizedProposer,
+    #[msg("Prerequisite must be the index of an earlier transaction.")]
+    InvalidPrerequisite,
+    #[msg("Prerequisite transaction is missing or not executed.")]
+    PrerequisiteNotExecuted,
+    #[msg("Transaction must be assigned an index to be executed in order.")]
+    TransactionNotIndexed,
+    #[msg("An earlier transaction is missing or still pending.")]
+    EarlierTransactionPending,
+    #[msg("Pre-condition can never hold.")]
+    InvalidPreCondition,
+    #[msg("Account of a pre-condition was not passed.")]
+    MissingPreConditionAccount,
+    #[msg("Pre-condition does not hold.")]
+    PreConditionFailed,
+    #[msg("Account is not an SPL Token account.")]
+    NotATokenAccount,
+    #[msg("Account of a post-condition was not passed.")]
+    MissingPostConditionAccount,
+    #[msg("Post-condition does not hold after execution.")]
+    PostConditionFailed,
+    #[msg("Transfer amount must be positive.")]
+    InvalidTransferAmount,
+    #[msg("Address book of the smallet was not passed.")]
+    MissingAddressBook,
+    #[msg("Instruction does not pay a trusted address book entry.")]
+    RecipientNotTrusted,
+    #[msg("Recipient is already in the address book.")]
+    AddressBookEntryExists,
+    #[msg("Recipient is not in the address book.")]
+    AddressBookEntryNotFound,
+    #[msg("Program to invoke was not passed.")]
+    MissingInvokedProgram,
+    #[msg("Smallets with timelock tiers only accept proposals via create_transaction_v2.")]
+    TierRequired,
+    #[msg("Instruction may not be proposed optimistically.")]
+    InstructionNotOptimistic,
 }
@@ -3,8 +3,23 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
+use anchor_lang::solana_program::bpf_loader_upgradeable::UpgradeableLoaderState;
 use vipers::prelude::*;
 
+// Layout version of accounts created before layouts were versioned.
+pub const LEGACY_VERSION: u8 = 0;
+
+// Current layout version of [Smallet] accounts.
+pub const SMALLET_VERSION: u8 = 1;
+
+// Current layout version of [Transaction] accounts.
+pub const TRANSACTION_VERSION: u8 = 1;
+
+// Current layout version of [SubaccountInfo] accounts.
+pub const SUBACCOUNT_INFO_VERSION: u8 = 1;
+
+// Maximum length of a [SubaccountInfo] label, in bytes.
+pub const MAX_LABEL_LEN: usize = 32;
 
 #[account]
 #[derive(Default, Debug, PartialEq)]
@@ -31,6 +46,29 @@ pub struct Smallet {
     pub num_transactions: u64,
 	// Owners of the [Smallet].
     pub owners: Vec<Pubkey>,
+	// Layout version of the account. This occupies the first byte of the
+	// legacy `reserved` space, so legacy accounts read as [LEGACY_VERSION].
+    pub version: u8,
+	// Total number of sub-accounts registered on this [Smallet].
+    pub num_subaccounts: u64,
+	// Risk classes that a [Transaction] may declare, each with its own delay
+	// and threshold. Transactions without a tier use the highest delay and
+	// threshold among the tiers, [Smallet::minimum_delay] and
+	// [Smallet::threshold].
+    pub tiers: Vec<TimelockTier>,
+	// Number of owner vetoes that permanently block a timelocked [Transaction]
+	// before its ETA. 0 disables vetoes.
+    pub veto_threshold: u64,
+	// If set, proposals of authorized proposers execute after their ETA
+	// unless enough owners object, instead of needing approvals.
+    pub optimistic: Option<OptimisticConfig>,
+	// Time after which an approval no longer counts towards the threshold,
+	// in seconds. 0 if approvals never expire.
+    pub approval_ttl: i64,
+	// If set, [Transaction]s are executed in index order: a [Transaction] may
+	// only be executed if no earlier one is still pending and approved. Every
+	// [Transaction] below the cursor is settled, so it need not be checked.
+    pub fifo_cursor: Option<u64>,
 	// Extra space for program upgrades.
     pub reserved: [u64; 16],
 }
@@ -52,6 +90,131 @@ impl Smallet {
     pub fn try_owner_index(&self, key: Pubkey) -> Result<usize> {
         Ok(unwrap_opt!(self.owner_index_opt(key), InvalidOwner))
     }
+
+	// Gets the [TimelockTier] at `index`, or error
+    pub fn try_tier(&self, index: u8) -> Result<&TimelockTier> {
+        Ok(unwrap_opt!(self.tiers.get(usize::from(index)), InvalidTier))
+    }
+
+	// Number of approvals needed to execute a [Transaction] of the given tier.
+	// Untiered transactions are held to the strictest tier, so that omitting
+	// the tier never lowers the requirements.
+    pub fn threshold_for(&self, tier: Option<u8>) -> Result<u64> {
+        match tier {
+            Some(index) => Ok(self.try_tier(index)?.threshold),
+            None => Ok(self
+                .tiers
+                .iter()
+                .map(|tier| tier.threshold)
+                .fold(self.threshold, u64::max)),
+        }
+    }
+
+	// Minimum delay of a [Transaction] of the given tier. Like the threshold,
+	// the strictest tier applies to untiered transactions.
+    pub fn minimum_delay_for(&self, tier: Option<u8>) -> Result<i64> {
+        match tier {
+            Some(index) => Ok(self.try_tier(index)?.minimum_delay),
+            None => Ok(self
+                .tiers
+                .iter()
+                .map(|tier| tier.minimum_delay)
+                .fold(self.minimum_delay, i64::max)),
+        }
+    }
+
+	// Whether `tx` executes without approvals: it was proposed optimistically
+	// and the [Smallet] still authorizes its proposer to do so.
+    pub fn is_optimistic(&self, tx: &Transaction) -> bool {
+        tx.optimistic
+            && matches!(
+                &self.optimistic,
+                Some(config) if config.proposers.contains(&tx.proposer)
+            )
+    }
+
+	// Number of vetoes that block `tx`. Vetoes of an optimistic [Transaction]
+	// are objections, and [OptimisticConfig::objection_threshold] applies.
+    pub fn veto_threshold_for(&self, tx: &Transaction) -> u64 {
+        match &self.optimistic {
+            Some(config) if self.is_optimistic(tx) => config.objection_threshold,
+            _ => self.veto_threshold,
+        }
+    }
+
+	// Whether `tx` can never be executed or already was, at `now`.
+    pub fn is_settled(&self, tx: &Transaction, now: i64) -> bool {
+        tx.executed_at != -1
+            || tx.vetoed_at.is_some()
+            || tx.owner_set_seqno != self.owner_set_seqno
+            || (tx.eta != crate::NO_ETA && now > tx.eta.saturating_add(self.grace_period))
+    }
+
+	// Whether `tx` has enough fresh approvals to be executed at `now`, ignoring
+	// its ETA. Payments of [AddressBook] entries need the `book` of the smallet.
+    pub fn is_approved(&self, tx: &Transaction, book: Option<&AddressBook>, now: i64) -> bool {
+        if self.is_optimistic(tx) {
+            return true;
+        }
+        let threshold = match (tx.address_book, book) {
+            (true, Some(book)) => Ok(self.address_book_threshold(book)),
+            (true, None) => return false,
+            (false, _) => self.threshold_for(tx.tier),
+        };
+        match threshold {
+            Ok(threshold) => tx.num_fresh_signers(self.approval_ttl, now) as u64 >= threshold,
+            Err(_) => false,
+        }
+    }
+
+	// Number of approvals payments of [AddressBook] entries need. The `book`
+	// threshold is not lowered with the owner set if it was not passed to
+	// [crate::smallet::set_owners].
+    pub fn address_book_threshold(&self, book: &AddressBook) -> u64 {
+        book.threshold.min(self.owners.len() as u64)
+    }
+
+	// Deserializes a [Smallet] from raw account data in either the legacy or
+	// the current layout. Legacy accounts keep [LEGACY_VERSION] as their version.
+    pub fn try_deserialize_versioned(buf: &[u8]) -> Result<Smallet> {
+        check_discriminator::<Smallet>(buf)?;
+        let legacy = SmalletV0::deserialize(&mut &buf[8..])?;
+        if legacy.reserved[0].to_le_bytes()[0] == LEGACY_VERSION {
+            return Ok(legacy.into());
+        }
+        Smallet::try_deserialize(&mut &buf[..])
+    }
+}
+
+// Layout of a [Smallet] before [Smallet::version] was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct SmalletV0 {
+    pub base: Pubkey,
+    pub bump: u8,
+    pub threshold: u64,
+    pub minimum_delay: i64,
+    pub grace_period: i64,
+    pub owner_set_seqno: u32,
+    pub num_transactions: u64,
+    pub owners: Vec<Pubkey>,
+    pub reserved: [u64; 16],
+}
+
+impl From<SmalletV0> for Smallet {
+    fn from(legacy: SmalletV0) -> Smallet {
+        Smallet {
+            base: legacy.base,
+            bump: legacy.bump,
+            threshold: legacy.threshold,
+            minimum_delay: legacy.minimum_delay,
+            grace_period: legacy.grace_period,
+            owner_set_seqno: legacy.owner_set_seqno,
+            num_transactions: legacy.num_transactions,
+            owners: legacy.owners,
+            version: LEGACY_VERSION,
+            ..Default::default()
+        }
+    }
 }
 
 
@@ -65,6 +228,8 @@ pub struct Transaction {
 	// The auto-incremented integer index of the transaction.
     // All transactions on the [Smallet] can be looked up via this index,
     // allowing for easier browsing of a wallet's historical transactions.
+    // Transactions created via [crate::smallet::create_transaction_v2] hold
+    // [crate::NO_INDEX] until [crate::smallet::assign_transaction_index] is called.
     pub index: u64,
 	// Bump seed.
     pub bump: u8,
@@ -85,6 +250,45 @@ pub struct Transaction {
     pub executor: Pubkey,
     // When the transaction was executed. -1 if not executed.
     pub executed_at: i64,
+	// Layout version of the account. Legacy accounts end before this field.
+    pub version: u8,
+	// The program upgrade performed by this transaction, if it was proposed
+	// via [crate::smallet::propose_program_upgrade].
+    pub program_upgrade: Option<ProgramUpgrade>,
+	// PDAs signing the instructions. If empty, only the [Smallet] signs.
+    pub pda_signers: Vec<PdaSigner>,
+	// Hash of the instructions committed to via
+	// [crate::smallet::create_committed_transaction]. The instructions are
+	// only revealed at execution, so [Transaction::instructions] stays empty.
+    pub instructions_hash: Option<[u8; 32]>,
+	// Index of the [TimelockTier] declared by the proposer, if any.
+    pub tier: Option<u8>,
+	// `vetoes[index]` is true iff `[Smallet]::owners[index]` vetoed the transaction.
+	// Empty until the first veto.
+    pub vetoes: Vec<bool>,
+	// When the [Smallet::veto_threshold] was reached. A vetoed transaction
+	// can never be executed.
+    pub vetoed_at: Option<i64>,
+	// Whether the [Transaction] was proposed via [OptimisticConfig], i.e. it
+	// needs no approvals unless objected to.
+    pub optimistic: bool,
+	// `approved_at[index]` is when `[Smallet]::owners[index]` last approved the
+	// transaction, or 0 if they did not or the approval predates timestamps.
+    pub approved_at: Vec<i64>,
+	// Indexes of the [Transaction]s that must be executed before this one.
+    pub prerequisites: Vec<u64>,
+	// Assertions on the state of accounts, checked before the instructions
+	// are invoked.
+    pub pre_conditions: Vec<PreCondition>,
+	// Limits on what the instructions may move out of accounts, and balances
+	// accounts must keep, checked after they are invoked.
+    pub post_conditions: Vec<PostCondition>,
+	// The transfer performed by this transaction, if it was proposed via
+	// [crate::smallet::propose_transfer].
+    pub transfer: Option<VaultTransfer>,
+	// Whether the instructions only pay entries of the [AddressBook], whose
+	// threshold and delay apply instead of those of the [Smallet].
+    pub address_book: bool,
 }
 
 impl Transaction {
@@ -94,11 +298,119 @@ impl Transaction {
             + std::mem::size_of::<Transaction>()
             + 4 // Vec discriminator
             + (instructions.iter().map(|ix| ix.space()).sum::<usize>())
+    }
+	// Space of the approval timestamps of a [Transaction] on `smallet`, on top
+	// of [Transaction::space].
+    pub fn approvals_space(smallet: &Smallet) -> usize {
+        std::mem::size_of::<i64>() * smallet.owners.len()
     }
 	// Number of signers.
     pub fn num_signers(&self) -> usize {
         self.signers.iter().filter(|&did_sign| *did_sign).count()
     }
+
+	// Number of signers whose approval is younger than `approval_ttl` at
+	// `now`. Approvals without a timestamp are considered expired.
+    pub fn num_fresh_signers(&self, approval_ttl: i64, now: i64) -> usize {
+        if approval_ttl == 0 {
+            return self.num_signers();
+        }
+        self.signers
+            .iter()
+            .enumerate()
+            .filter(|&(index, did_sign)| {
+                let approved_at = self.approved_at.get(index).copied().unwrap_or(0);
+                *did_sign && approved_at != 0 && now <= approved_at.saturating_add(approval_ttl)
+            })
+            .count()
+    }
+
+	// Records the approval of the owner at `owner_index` at `now`, or its
+	// revocation if `now` is None.
+    pub fn set_approval(&mut self, owner_index: usize, now: Option<i64>) {
+        self.signers[owner_index] = now.is_some();
+        if self.approved_at.len() < self.signers.len() {
+            self.approved_at.resize(self.signers.len(), 0);
+        }
+        self.approved_at[owner_index] = now.unwrap_or(0);
+    }
+
+	// Computes the hash committed to by a [Transaction] whose instructions are
+	// hidden until execution: the SHA-256 of a secret salt followed by the
+	// borsh serialized instructions. The salt keeps the instructions from
+	// being guessed by hashing likely candidates.
+    pub fn hash_instructions(salt: &[u8; 32], instructions: &[TXInstruction]) -> Result<[u8; 32]> {
+        Ok(solana_program::hash::hashv(&[salt, &instructions.try_to_vec()?]).to_bytes())
+    }
+
+	// Seeds of every PDA signing the instructions of this transaction.
+    pub fn signer_seeds(&self, smallet_key: &Pubkey, smallet: &Smallet) -> Vec<Vec<Vec<u8>>> {
+        if self.pda_signers.is_empty() {
+            return vec![PdaSigner::Smallet.seeds(smallet_key, smallet)];
+        }
+        self.pda_signers
+            .iter()
+            .map(|signer| signer.seeds(smallet_key, smallet))
+            .collect()
+    }
+
+	// Deserializes a [Transaction] from raw account data in either the legacy
+	// or the current layout. Legacy accounts keep [LEGACY_VERSION] as their version.
+    pub fn try_deserialize_versioned(buf: &[u8]) -> Result<Transaction> {
+        check_discriminator::<Transaction>(buf)?;
+        let mut rest = &buf[8..];
+        let legacy = TransactionV0::deserialize(&mut rest)?;
+        // Legacy accounts are either fully used or zero-padded past `executed_at`.
+        if rest.first().copied().unwrap_or(LEGACY_VERSION) == LEGACY_VERSION {
+            return Ok(legacy.into());
+        }
+        Transaction::try_deserialize(&mut &buf[..])
+    }
+}
+
+// Layout of a [Transaction] before [Transaction::version] was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TransactionV0 {
+    pub smallet: Pubkey,
+    pub index: u64,
+    pub bump: u8,
+    pub proposer: Pubkey,
+    pub instructions: Vec<TXInstruction>,
+    pub signers: Vec<bool>,
+    pub owner_set_seqno: u32,
+    pub eta: i64,
+    pub executor: Pubkey,
+    pub executed_at: i64,
+}
+
+impl From<TransactionV0> for Transaction {
+    fn from(legacy: TransactionV0) -> Transaction {
+        Transaction {
+            smallet: legacy.smallet,
+            index: legacy.index,
+            bump: legacy.bump,
+            proposer: legacy.proposer,
+            instructions: legacy.instructions,
+            signers: legacy.signers,
+            owner_set_seqno: legacy.owner_set_seqno,
+            eta: legacy.eta,
+            executor: legacy.executor,
+            executed_at: legacy.executed_at,
+            version: LEGACY_VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+// Checks the Anchor discriminator of raw account data.
+fn check_discriminator<T: anchor_lang::Discriminator>(buf: &[u8]) -> Result<()> {
+    if buf.len() < 8 {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+    if buf[..8] != T::discriminator() {
+        return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    Ok(())
 }
 
 // Instruction.
@@ -116,8 +428,26 @@ impl TXInstruction {
 	// Space that a [TXInstruction] takes up.
     pub fn space(&self) -> usize {
         std::mem::size_of::<Pubkey>()
-            + (self.keys.len() as usize) * std::mem::size_of::<TXAccountMeta>()
-            + (self.data.len() as usize)
+            + self.keys.len() * std::mem::size_of::<TXAccountMeta>()
+            + self.data.len()
+    }
+}
+
+impl From<solana_program::instruction::Instruction> for TXInstruction {
+    fn from(ix: solana_program::instruction::Instruction) -> TXInstruction {
+        TXInstruction {
+            program_id: ix.program_id,
+            keys: ix
+                .accounts
+                .into_iter()
+                .map(|meta| TXAccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data,
+        }
     }
 }
 
@@ -157,27 +487,366 @@ impl From<TXAccountMeta> for solana_program::instruction::AccountMeta {
         }
     }
 }
+// Optional parameters of a [Transaction] set at creation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TransactionOptions {
+	// PDAs signing the instructions. If empty, only the [Smallet] signs.
+    pub pda_signers: Vec<PdaSigner>,
+	// Index of the [TimelockTier] the transaction belongs to. If None, the
+	// [Smallet::minimum_delay] and [Smallet::threshold] apply.
+    pub tier: Option<u8>,
+	// Proposes the transaction optimistically. See [OptimisticConfig].
+    pub optimistic: bool,
+	// Indexes of the [Transaction]s that must be executed before this one.
+    pub prerequisites: Vec<u64>,
+	// Assertions on the state of accounts, checked before the instructions
+	// are invoked.
+    pub pre_conditions: Vec<PreCondition>,
+	// Limits on what the instructions may move out of accounts, and balances
+	// accounts must keep, checked after they are invoked.
+    pub post_conditions: Vec<PostCondition>,
+	// Proposes a payment of [AddressBook] entries. The [AddressBook] must be
+	// passed as a remaining account, and its threshold and delay apply.
+    pub address_book: bool,
+}
+
+impl TransactionOptions {
+	// Space that the variable-length options take up on a [Transaction].
+    pub fn space(&self) -> usize {
+        self.pda_signers.len() * PdaSigner::LEN
+            + self.prerequisites.len() * std::mem::size_of::<u64>()
+            + self.pre_conditions.len() * PreCondition::LEN
+            + self.post_conditions.len() * PostCondition::LEN
+    }
+}
+
+// Optimistic governance of a [Smallet], for low-risk recurring operations.
+// A [Transaction] proposed optimistically by one of the `proposers` is
+// treated as approved: it may be executed after its ETA, within the
+// [Smallet::grace_period], unless `objection_threshold` owners object via
+// [crate::smallet::veto] before the ETA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct OptimisticConfig {
+	// Keys that may propose optimistically. They need not be owners.
+    pub proposers: Vec<Pubkey>,
+	// Number of owner objections that block an optimistic [Transaction].
+    pub objection_threshold: u64,
+	// Minimum time between an optimistic proposal and its ETA, in seconds,
+	// during which owners may object.
+    pub challenge_period: i64,
+	// Instructions an optimistic [Transaction] may contain. Instructions of
+	// the smallet program, e.g. [crate::smallet::set_owners], are never
+	// allowed, so proposers cannot change how the [Smallet] is governed.
+    pub allowed_instructions: Vec<AllowedInstruction>,
+}
+
+impl OptimisticConfig {
+	// Space that an [OptimisticConfig] takes up.
+    pub fn space(&self) -> usize {
+        4 + std::mem::size_of::<Pubkey>() * self.proposers.len()
+            + 8
+            + 8
+            + 4
+            + self
+                .allowed_instructions
+                .iter()
+                .map(|allowed| allowed.space())
+                .sum::<usize>()
+    }
+
+	// Checks that every instruction may be proposed optimistically.
+    pub fn check_instructions(&self, instructions: &[TXInstruction]) -> Result<()> {
+        for ix in instructions {
+            invariant!(ix.program_id != crate::ID, InstructionNotOptimistic);
+            invariant!(
+                self.allowed_instructions
+                    .iter()
+                    .any(|allowed| allowed.matches(ix.program_id, &ix.data)),
+                InstructionNotOptimistic
+            );
+        }
+        Ok(())
+    }
+}
+
+// A risk class of [Transaction]s, e.g. "ops" with no delay for routine
+// payments, or "treasury" with a longer delay and a higher threshold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TimelockTier {
+	// Name of the tier.
+    pub name: String,
+    // Minimum delay between proposal and execution, in seconds.
+    pub minimum_delay: i64,
+    // Number of owner approvals needed to execute a [Transaction] of the tier.
+    pub threshold: u64,
+    // Instructions a [Transaction] of the tier may contain, so that proposals
+    // cannot under-classify themselves. If empty, any instruction is allowed.
+    pub allowed_instructions: Vec<AllowedInstruction>,
+}
+
+impl TimelockTier {
+	// Space that a [TimelockTier] takes up.
+    pub fn space(&self) -> usize {
+        4 + self.name.len()
+            + 8
+            + 8
+            + 4
+            + self
+                .allowed_instructions
+                .iter()
+                .map(|allowed| allowed.space())
+                .sum::<usize>()
+    }
+
+	// Checks that every instruction is allowed in the tier.
+    pub fn check_instructions(&self, instructions: &[TXInstruction]) -> Result<()> {
+        if self.allowed_instructions.is_empty() {
+            return Ok(());
+        }
+        for ix in instructions {
+            invariant!(
+                self.allowed_instructions
+                    .iter()
+                    .any(|allowed| allowed.matches(ix.program_id, &ix.data)),
+                InstructionNotInTier
+            );
+        }
+        Ok(())
+    }
+}
+
+// A program derived address that signs the instructions of a [Transaction].
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PdaSigner {
+	// The [Smallet] itself.
+    Smallet,
+    // A [SubaccountType::Derived] subaccount.
+    Derived { index: u64, bump: u8 },
+    // A [SubaccountType::OwnerInvoker] subaccount.
+    OwnerInvoker { index: u64, bump: u8 },
+}
+
+impl PdaSigner {
+	// Number of bytes that a [PdaSigner] uses.
+    pub const LEN: usize = 1 + 8 + 1;
+
+	// Signer seeds of the PDA, including the bump.
+    pub fn seeds(&self, smallet_key: &Pubkey, smallet: &Smallet) -> Vec<Vec<u8>> {
+        match *self {
+            PdaSigner::Smallet => vec![
+                b"CosmicSmallet".to_vec(),
+                smallet.base.to_bytes().to_vec(),
+                vec![smallet.bump],
+            ],
+            PdaSigner::Derived { index, bump } => vec![
+                b"CosmicSmalletDerived".to_vec(),
+                smallet_key.to_bytes().to_vec(),
+                index.to_le_bytes().to_vec(),
+                vec![bump],
+            ],
+            PdaSigner::OwnerInvoker { index, bump } => vec![
+                b"CosmicSmalletOwnerInvoker".to_vec(),
+                smallet_key.to_bytes().to_vec(),
+                index.to_le_bytes().to_vec(),
+                vec![bump],
+            ],
+        }
+    }
+
+	// Address of the PDA, or None if the seeds do not derive a valid PDA.
+    pub fn address(&self, smallet_key: &Pubkey, smallet: &Smallet) -> Option<Pubkey> {
+        let seeds = self.seeds(smallet_key, smallet);
+        let seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+        Pubkey::create_program_address(&seeds, &crate::ID).ok()
+    }
+
+	// Checks that the bump of a subaccount is canonical.
+    pub fn validate_bump(&self, smallet_key: &Pubkey, smallet: &Smallet) -> Result<()> {
+        if let PdaSigner::Derived { bump, .. } | PdaSigner::OwnerInvoker { bump, .. } = *self {
+            let seeds = self.seeds(smallet_key, smallet);
+            let seeds: Vec<&[u8]> = seeds[..seeds.len() - 1]
+                .iter()
+                .map(Vec::as_slice)
+                .collect();
+            let (_, canonical_bump) = Pubkey::find_program_address(&seeds, &crate::ID);
+            invariant!(bump == canonical_bump, InvalidSignerBump);
+        }
+        Ok(())
+    }
+}
+
+// A BPF upgradeable loader program upgrade, pinned to the contents of a buffer.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, PartialEq)]
+pub struct ProgramUpgrade {
+	// The program to upgrade.
+    pub program: Pubkey,
+    // The buffer containing the new program data.
+    pub buffer: Pubkey,
+    // SHA-256 of the program data in the buffer, excluding the buffer metadata.
+    pub buffer_hash: [u8; 32],
+}
+
+impl ProgramUpgrade {
+	// Builds the loader instruction performing the upgrade.
+    pub fn to_instruction(&self, authority: &Pubkey, spill: &Pubkey) -> TXInstruction {
+        solana_program::bpf_loader_upgradeable::upgrade(
+            &self.program,
+            &self.buffer,
+            authority,
+            spill,
+        )
+        .into()
+    }
+
+	// Computes the hash of the program data held in a buffer account.
+    pub fn hash_buffer(buffer: &AccountInfo) -> Result<[u8; 32]> {
+        let offset = unwrap_opt!(
+            UpgradeableLoaderState::buffer_data_offset().ok(),
+            "buffer_data_offset"
+        );
+        let data = buffer.try_borrow_data()?;
+        invariant!(data.len() >= offset, InvalidProgramBuffer);
+        Ok(solana_program::hash::hash(&data[offset..]).to_bytes())
+    }
+}
+
+// Asset moved by a [VaultTransfer].
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransferAsset {
+	// Native SOL, in lamports.
+    Sol,
+	// SPL tokens of `mint`, in base units, held in associated token accounts.
+    Token { mint: Pubkey },
+}
+
+// A transfer out of a [SubaccountType::Derived] vault of a [Smallet].
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VaultTransfer {
+	// The asset to move.
+    pub asset: TransferAsset,
+	// Amount of the asset, in lamports or base units.
+    pub amount: u64,
+	// Index of the [SubaccountType::Derived] sub-account paying the transfer.
+    pub source_index: u64,
+	// Receiver of SOL, or owner of the associated token account receiving
+	// tokens.
+    pub recipient: Pubkey,
+}
+
+impl VaultTransfer {
+	// The [PdaSigner] of the vault paying the transfer.
+    pub fn signer(&self, smallet: &Pubkey) -> PdaSigner {
+        let (_, bump) = SubaccountType::Derived.find_address(smallet, self.source_index);
+        PdaSigner::Derived {
+            index: self.source_index,
+            bump,
+        }
+    }
+
+	// Accounts the asset moves from and to: the vault and the recipient for
+	// SOL, their associated token accounts for tokens.
+    pub fn endpoints(&self, smallet: &Pubkey) -> (Pubkey, Pubkey) {
+        let (vault, _) = SubaccountType::Derived.find_address(smallet, self.source_index);
+        match self.asset {
+            TransferAsset::Sol => (vault, self.recipient),
+            TransferAsset::Token { mint } => (
+                associated_token_address(&vault, &mint),
+                associated_token_address(&self.recipient, &mint),
+            ),
+        }
+    }
+
+	// Builds the System or SPL Token instruction performing the transfer.
+    pub fn to_instruction(&self, smallet: &Pubkey) -> TXInstruction {
+        let (vault, _) = SubaccountType::Derived.find_address(smallet, self.source_index);
+        let (source, destination) = self.endpoints(smallet);
+        match self.asset {
+            TransferAsset::Sol => {
+                solana_program::system_instruction::transfer(&source, &destination, self.amount)
+                    .into()
+            }
+            TransferAsset::Token { .. } => {
+                // SPL Token `Transfer`: tag 3 followed by the amount.
+                let mut data = vec![3];
+                data.extend_from_slice(&self.amount.to_le_bytes());
+                TXInstruction {
+                    program_id: crate::token_program::ID,
+                    keys: vec![
+                        TXAccountMeta {
+                            pubkey: source,
+                            is_signer: false,
+                            is_writable: true,
+                        },
+                        TXAccountMeta {
+                            pubkey: destination,
+                            is_signer: false,
+                            is_writable: true,
+                        },
+                        TXAccountMeta {
+                            pubkey: vault,
+                            is_signer: true,
+                            is_writable: false,
+                        },
+                    ],
+                    data,
+                }
+            }
+        }
+    }
+}
+
+// Address of the associated token account of `owner` for `mint`.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            owner.as_ref(),
+            crate::token_program::ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &crate::associated_token_program::ID,
+    )
+    .0
+}
+
 // Type of Subaccount.
 #[derive(
-    AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord,
+    AnchorSerialize,
+    AnchorDeserialize,
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
 )]
 #[repr(u8)]
 pub enum SubaccountType {
 	// Requires the normal multisig approval process.
+    #[default]
     Derived = 0,
     // Any owner may sign an instruction  as this address.
     OwnerInvoker = 1,
 }
 
-impl Default for SubaccountType {
-    fn default() -> Self {
-        SubaccountType::Derived
+impl SubaccountType {
+	// Finds the address and bump of the sub-account of this type at `index`.
+    pub fn find_address(&self, smallet: &Pubkey, index: u64) -> (Pubkey, u8) {
+        let prefix: &[u8] = match self {
+            SubaccountType::Derived => b"CosmicSmalletDerived",
+            SubaccountType::OwnerInvoker => b"CosmicSmalletOwnerInvoker",
+        };
+        Pubkey::find_program_address(
+            &[prefix, &smallet.to_bytes(), &index.to_le_bytes()],
+            &crate::ID,
+        )
     }
 }
 
 // Mapping of a Subaccount to its [Smallet].
 #[account]
-#[derive(Copy, Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, PartialEq, Eq)]
 pub struct SubaccountInfo {
 	// Smallet of the sub-account.
     pub smallet: Pubkey,
@@ -185,9 +854,417 @@ pub struct SubaccountInfo {
     pub subaccount_type: SubaccountType,
     // Index of the sub-account.
     pub index: u64,
+    // Layout version of the account. Legacy accounts end before this field.
+    pub version: u8,
+    // Index of the sub-account in the registry of its [Smallet].
+    // See [SubaccountRegistryEntry].
+    pub registry_index: u64,
+    // When the [SubaccountInfo] was created. 0 if migrated from the legacy layout.
+    pub created_at: i64,
+    // Human-readable label of the sub-account.
+    pub label: Option<String>,
 }
 
 impl SubaccountInfo {
 	// Number of bytes that a [SubaccountInfo] uses.
+    pub const LEN: usize = 32 + 1 + 8 + 1 + 8 + 8 + 1 + 4 + MAX_LABEL_LEN;
+
+	// Deserializes a [SubaccountInfo] from raw account data in either the legacy
+	// or the current layout. Legacy accounts keep [LEGACY_VERSION] as their version.
+    pub fn try_deserialize_versioned(buf: &[u8]) -> Result<SubaccountInfo> {
+        check_discriminator::<SubaccountInfo>(buf)?;
+        let mut rest = &buf[8..];
+        let legacy = SubaccountInfoV0::deserialize(&mut rest)?;
+        if rest.first().copied().unwrap_or(LEGACY_VERSION) == LEGACY_VERSION {
+            return Ok(legacy.into());
+        }
+        SubaccountInfo::try_deserialize(&mut &buf[..])
+    }
+}
+
+// Layout of a [SubaccountInfo] before [SubaccountInfo::version] was introduced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubaccountInfoV0 {
+    pub smallet: Pubkey,
+    pub subaccount_type: SubaccountType,
+    pub index: u64,
+}
+
+impl From<SubaccountInfoV0> for SubaccountInfo {
+    fn from(legacy: SubaccountInfoV0) -> SubaccountInfo {
+        SubaccountInfo {
+            smallet: legacy.smallet,
+            subaccount_type: legacy.subaccount_type,
+            index: legacy.index,
+            version: LEGACY_VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+// Entry of the sub-account registry of a [Smallet], allowing its
+// sub-accounts to be enumerated from 0 to [Smallet::num_subaccounts].
+#[account]
+#[derive(Copy, Default, Debug, PartialEq, Eq)]
+pub struct SubaccountRegistryEntry {
+	// The [Smallet].
+    pub smallet: Pubkey,
+    // Index of the entry in the registry.
+    pub registry_index: u64,
+    // The sub-account.
+    pub subaccount: Pubkey,
+}
+
+impl SubaccountRegistryEntry {
+	// Number of bytes that a [SubaccountRegistryEntry] uses.
+    pub const LEN: usize = 32 + 8 + 32;
+}
+
+// Policy restricting who may invoke instructions as an owner invoker,
+// and which instructions they may invoke.
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct InvokerPolicy {
+	// The [Smallet] of the owner invoker.
+    pub smallet: Pubkey,
+    // Index of the owner invoker.
+    pub index: u64,
+    // Bump seed.
+    pub bump: u8,
+
+	// Owners allowed to invoke instructions as the owner invoker.
+    pub owners: Vec<Pubkey>,
+    // Instructions that may be invoked as the owner invoker.
+    pub allowed_instructions: Vec<AllowedInstruction>,
+    // Maximum number of calls per period. If 0, calls are not limited.
+    pub max_calls_per_period: u64,
+    // Length of a rate limiting period, in seconds.
+    pub period: i64,
+    // Start of the current period.
+    pub period_start: i64,
+    // Number of calls made in the current period.
+    pub calls_in_period: u64,
+}
+
+impl InvokerPolicy {
+	// Computes the space an [InvokerPolicy] uses.
+    pub fn space(owners: &[Pubkey], allowed_instructions: &[AllowedInstruction]) -> usize {
+        8 // Anchor discriminator
+            + std::mem::size_of::<InvokerPolicy>()
+            + std::mem::size_of_val(owners)
+            + allowed_instructions
+                .iter()
+                .map(|allowed| allowed.space())
+                .sum::<usize>()
+    }
+
+	// Checks the rate limit of an [InvokerPolicy]. Limited calls need a
+	// period to be counted in.
+    pub fn validate_rate_limit(max_calls_per_period: u64, period: i64) -> Result<()> {
+        invariant!(period >= 0, "period must not be negative");
+        invariant!(
+            max_calls_per_period == 0 || period > 0,
+            "period must be positive if calls are limited"
+        );
+        Ok(())
+    }
+
+	// Checks that `owner` may invoke the instruction, and records the call.
+    pub fn record_call(
+        &mut self,
+        owner: Pubkey,
+        program_id: Pubkey,
+        data: &[u8],
+        now: i64,
+    ) -> Result<()> {
+        invariant!(self.owners.contains(&owner), InvokerNotAllowed);
+        invariant!(
+            self.allowed_instructions
+                .iter()
+                .any(|allowed| allowed.matches(program_id, data)),
+            InstructionNotAllowed
+        );
+
+        if self.max_calls_per_period == 0 {
+            return Ok(());
+        }
+        if now >= unwrap_int!(self.period_start.checked_add(self.period)) {
+            self.period_start = now;
+            self.calls_in_period = 0;
+        }
+        self.calls_in_period = unwrap_int!(self.calls_in_period.checked_add(1));
+        invariant!(
+            self.calls_in_period <= self.max_calls_per_period,
+            InvokerCallLimitExceeded
+        );
+        Ok(())
+    }
+}
+
+// An instruction allowed by an [InvokerPolicy], a [TimelockTier] or an
+// [OptimisticConfig].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct AllowedInstruction {
+	// The program that may be invoked.
+    pub program_id: Pubkey,
+    // Prefix the instruction data must start with, e.g. an Anchor sighash.
+    // If empty, any instruction of the program is allowed.
+    pub discriminator: Vec<u8>,
+}
+
+impl AllowedInstruction {
+	// Space that an [AllowedInstruction] takes up.
+    pub fn space(&self) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + self.discriminator.len()
+    }
+
+	// Returns true if the instruction is allowed.
+    pub fn matches(&self, program_id: Pubkey, data: &[u8]) -> bool {
+        self.program_id == program_id && data.starts_with(&self.discriminator)
+    }
+}
+
+// Byte length of an SPL Token account.
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+// Amount held by an SPL Token account.
+pub fn token_account_amount(account: &AccountInfo) -> Result<u64> {
+    invariant!(*account.owner == crate::token_program::ID, NotATokenAccount);
+    let data = account.try_borrow_data()?;
+    invariant!(data.len() == TOKEN_ACCOUNT_LEN, NotATokenAccount);
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&data[64..72]);
+    Ok(u64::from_le_bytes(amount))
+}
+
+// Owner of an SPL Token account.
+pub fn token_account_owner(account: &AccountInfo) -> Result<Pubkey> {
+    invariant!(*account.owner == crate::token_program::ID, NotATokenAccount);
+    let data = account.try_borrow_data()?;
+    invariant!(data.len() == TOKEN_ACCOUNT_LEN, NotATokenAccount);
+    Ok(Pubkey::new(&data[32..64]))
+}
+
+// Labelled recipients trusted by a [Smallet], such as known vendors and
+// exchanges. A [Transaction] that only pays trusted entries may be proposed
+// with [TransactionOptions::address_book], so that the lower threshold and
+// shorter delay of the book apply.
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct AddressBook {
+	// The [Smallet].
+    pub smallet: Pubkey,
+    // Bump seed.
+    pub bump: u8,
+
+	// Number of owner approvals needed to execute a payment of entries.
+    pub threshold: u64,
+    // Minimum delay between proposal and execution of a payment of entries,
+    // in seconds.
+    pub minimum_delay: i64,
+    // Time between adding an entry and trusting it, in seconds, during which
+    // owners may remove an entry added by mistake.
+    pub entry_delay: i64,
+    // The recipients.
+    pub entries: Vec<AddressBookEntry>,
+}
+
+impl AddressBook {
+	// Computes the space an [AddressBook] uses.
+    pub fn space(entries: &[AddressBookEntry]) -> usize {
+        8 // Anchor discriminator
+            + std::mem::size_of::<AddressBook>()
+            + entries.iter().map(|entry| entry.space()).sum::<usize>()
+    }
+
+	// Checks the threshold and delays of an [AddressBook] of `smallet`.
+    pub fn validate_policy(
+        smallet: &Smallet,
+        threshold: u64,
+        minimum_delay: i64,
+        entry_delay: i64,
+    ) -> Result<()> {
+        invariant!(threshold > 0, InvalidThreshold);
+        invariant!(
+            threshold <= smallet.owners.len() as u64,
+            InvalidThreshold
+        );
+        for delay in [minimum_delay, entry_delay] {
+            invariant!(delay >= 0, "delay must be positive");
+            invariant!(delay < crate::MAX_DELAY_SECONDS, DelayTooHigh);
+        }
+        Ok(())
+    }
+
+	// Whether `recipient` is an entry trusted at `now`.
+    pub fn is_trusted(&self, recipient: &Pubkey, now: i64) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.recipient == *recipient && entry.trusted_at <= now)
+    }
+
+	// Checks that `ix` is a SOL or SPL Token transfer to an entry trusted at
+	// `now`. The recipient of a token transfer is the owner of the destination
+	// token account, which must be among `accounts`.
+    pub fn check_payment(&self, ix: &TXInstruction, accounts: &[AccountInfo], now: i64) -> Result<()> {
+        let key = |index: usize| ix.keys.get(index).map(|meta| meta.pubkey);
+        let recipient = if ix.program_id == solana_program::system_program::ID
+            && ix.data.len() == 12
+            && ix.data.starts_with(&2u32.to_le_bytes())
+        {
+            // System `Transfer`.
+            key(1)
+        } else if ix.program_id == crate::token_program::ID {
+            // SPL Token `Transfer` and `TransferChecked`.
+            let destination = match ix.data.first() {
+                Some(3) => key(1),
+                Some(12) => key(2),
+                _ => None,
+            };
+            let destination = unwrap_opt!(destination, RecipientNotTrusted);
+            let account = unwrap_opt!(
+                accounts.iter().find(|account| account.key() == destination),
+                RecipientNotTrusted
+            );
+            Some(token_account_owner(account)?)
+        } else {
+            None
+        };
+        let recipient = unwrap_opt!(recipient, RecipientNotTrusted);
+        invariant!(self.is_trusted(&recipient, now), RecipientNotTrusted);
+        Ok(())
+    }
+}
+
+// A recipient of an [AddressBook].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct AddressBookEntry {
+	// Receiver of SOL, or owner of the token accounts receiving tokens.
+    pub recipient: Pubkey,
+    // Name of the recipient.
+    pub label: String,
+    // When the entry becomes trusted.
+    pub trusted_at: i64,
+}
+
+impl AddressBookEntry {
+	// Space that an [AddressBookEntry] takes up.
+    pub fn space(&self) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + self.label.len() + 8
+    }
+}
+
+// An assertion on the state of an account, checked before the instructions of
+// a [Transaction] are invoked. Owners approve a [Transaction] against the
+// state they reviewed; execution fails if it has changed since.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PreCondition {
+	// The account. It must be passed to the execution.
+    pub account: Pubkey,
+	// What must hold for the account.
+    pub check: AccountCheck,
+}
+
+// A check of a [PreCondition].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum AccountCheck {
+	// The SHA-256 hash of the account data equals `hash`.
+    DataHash { hash: [u8; 32] },
+	// The account holds at least `lamports`.
+    MinLamports { lamports: u64 },
+	// The account is an SPL Token account holding between `min` and `max`
+	// tokens, inclusive.
+    TokenAmount { min: u64, max: u64 },
+	// The account is owned by `program_id`.
+    Owner { program_id: Pubkey },
+}
+
+impl PreCondition {
+	// Number of bytes that a [PreCondition] uses.
+    pub const LEN: usize = 32 + 1 + 32;
+
+	// Checks that the condition can ever hold.
+    pub fn validate(&self) -> Result<()> {
+        if let AccountCheck::TokenAmount { min, max } = self.check {
+            invariant!(min <= max, InvalidPreCondition);
+        }
+        Ok(())
+    }
+
+	// Checks the condition against `account`.
+    pub fn check(&self, account: &AccountInfo) -> Result<()> {
+        let holds = match self.check {
+            AccountCheck::DataHash { hash } => {
+                solana_program::hash::hash(&account.try_borrow_data()?).to_bytes() == hash
+            }
+            AccountCheck::MinLamports { lamports } => account.lamports() >= lamports,
+            AccountCheck::TokenAmount { min, max } => {
+                (min..=max).contains(&token_account_amount(account)?)
+            }
+            AccountCheck::Owner { program_id } => *account.owner == program_id,
+        };
+        invariant!(holds, PreConditionFailed);
+        Ok(())
+    }
+}
+
+// A limit on the outflow of an account, or a balance it must keep, checked
+// after the instructions of a [Transaction] are invoked. Approvers get a hard
+// cap on what the execution may move, whatever the instructions do.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostCondition {
+	// The account. It must be passed to the execution.
+    pub account: Pubkey,
+	// What may leave the account, or what must stay in it.
+    pub limit: OutflowLimit,
+}
+
+// A limit of a [PostCondition].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum OutflowLimit {
+	// At most `max_outflow` lamports may leave the account.
+    Lamports { max_outflow: u64 },
+	// At most `max_outflow` tokens may leave the account, which must be an SPL
+	// Token account.
+    Tokens { max_outflow: u64 },
+	// The account holds at least `min_balance` lamports after the execution,
+	// e.g. to stay rent exempt or keep an operating reserve.
+    MinLamports { min_balance: u64 },
+	// The SPL Token account holds at least `min_balance` tokens after the
+	// execution.
+    MinTokens { min_balance: u64 },
+}
+
+impl PostCondition {
+	// Number of bytes that a [PostCondition] uses.
     pub const LEN: usize = 32 + 1 + 8;
+
+	// Balance of `account` that the limit applies to.
+    pub fn balance(&self, account: &AccountInfo) -> Result<u64> {
+        match self.limit {
+            OutflowLimit::Lamports { .. } | OutflowLimit::MinLamports { .. } => {
+                Ok(account.lamports())
+            }
+            OutflowLimit::Tokens { .. } | OutflowLimit::MinTokens { .. } => {
+                token_account_amount(account)
+            }
+        }
+    }
+
+	// Checks that no more than the limit left `account` since its balance was
+	// `before`, or that it kept the minimum balance.
+    pub fn check(&self, account: &AccountInfo, before: u64) -> Result<()> {
+        let balance = self.balance(account)?;
+        match self.limit {
+            OutflowLimit::Lamports { max_outflow } | OutflowLimit::Tokens { max_outflow } => {
+                let outflow = before.saturating_sub(balance);
+                invariant!(outflow <= max_outflow, PostConditionFailed);
+            }
+            OutflowLimit::MinLamports { min_balance }
+            | OutflowLimit::MinTokens { min_balance } => {
+                invariant!(balance >= min_balance, PostConditionFailed);
+            }
+        }
+        Ok(())
+    }
 }
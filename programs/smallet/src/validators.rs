@@ -9,6 +9,7 @@ impl<'info> Validate<'info> for CreateSmallet<'info> {
 
 impl<'info> Validate<'info> for Auth<'info> {
     fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
         invariant!(
             self.smallet.to_account_info().is_signer,
             "smallet.is_signer"
@@ -19,6 +20,7 @@ impl<'info> Validate<'info> for Auth<'info> {
 
 impl<'info> Validate<'info> for CreateTransaction<'info> {
     fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
     	// owner_index check happens later
         Ok(())
     }
@@ -31,12 +33,19 @@ impl<'info> Validate<'info> for ExecuteTransaction<'info> {
             self.transaction.smallet,
             "smallet"
         );
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        invariant!(
+            self.transaction.version == TRANSACTION_VERSION,
+            AccountNotMigrated
+        );
         invariant!(
             self.smallet.owner_set_seqno == self.transaction.owner_set_seqno,
             OwnerSetChanged
         );
 		// Checking to see if this has been executed already
         invariant!(self.transaction.executed_at == -1, AlreadyExecuted);
+		// Blocked by a minority of owners during the timelock
+        invariant!(self.transaction.vetoed_at.is_none(), TransactionVetoed);
 
         let eta = self.transaction.eta;
         let clock = Clock::get()?;
@@ -52,14 +61,27 @@ impl<'info> Validate<'info> for ExecuteTransaction<'info> {
             );
         }
 		// Do we have enough signers to execute the TX?
-        let sig_count = self.transaction.num_signers();
+		// Expired approvals do not count, and optimistic transactions are
+		// approved unless objected to. Payments of address book entries are
+		// checked against the [AddressBook] threshold when executed.
+        let sig_count = self
+            .transaction
+            .num_fresh_signers(self.smallet.approval_ttl, current_ts);
+        let threshold = self.smallet.threshold_for(self.transaction.tier)?;
         invariant!(
-            (sig_count as u64) >= self.smallet.threshold,
+            self.smallet.is_optimistic(&self.transaction)
+                || self.transaction.address_book
+                || (sig_count as u64) >= threshold,
             NotEnoughSigners
         );
 		// ensure that the owner is a signer
         // this prevents common frontrunning/flash loan attacks
-        self.smallet.try_owner_index(self.owner.key())?;
+        // The proposer of an optimistic transaction may execute it too.
+        if !(self.smallet.is_optimistic(&self.transaction)
+            && self.owner.key() == self.transaction.proposer)
+        {
+            self.smallet.try_owner_index(self.owner.key())?;
+        }
 
         Ok(())
     }
@@ -67,6 +89,8 @@ impl<'info> Validate<'info> for ExecuteTransaction<'info> {
 
 impl<'info> Validate<'info> for OwnerInvokeInstruction<'info> {
     fn validate(&self) -> Result<()> {
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        assert_keys_eq!(self.smallet, self.policy.smallet, "policy.smallet");
         self.smallet.try_owner_index(self.owner.key())?;
         Ok(())
     }
@@ -74,7 +98,8 @@ impl<'info> Validate<'info> for OwnerInvokeInstruction<'info> {
 
 impl<'info> Validate<'info> for CreateSubaccountInfo<'info> {
     fn validate(&self) -> Result<()> {
-    	// no validation necessary
+        invariant!(self.smallet.version == SMALLET_VERSION, AccountNotMigrated);
+        self.smallet.try_owner_index(self.owner.key())?;
         Ok(())
     }
 }
@@ -101,6 +101,54 @@ export type SmalletIDL = {
         }
       ];
     },
+    {
+      name: "changeVetoThreshold";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "vetoThreshold";
+          type: "u64";
+        }
+      ];
+    },
+    {
+      name: "changeApprovalTtl";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "approvalTtl";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "setFifoMode";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "enabled";
+          type: "bool";
+        }
+      ];
+    },
     {
       name: "createTransaction";
       accounts: [
@@ -236,7 +284,7 @@ export type SmalletIDL = {
       ];
     },
     {
-      name: "approve";
+      name: "createTransactionV2";
       accounts: [
         {
           name: "smallet";
@@ -247,61 +295,75 @@ export type SmalletIDL = {
           name: "transaction";
           isMut: true;
           isSigner: false;
-          relations: ["smallet"];
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicTransactionV2";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "proposer";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "nonce";
+              }
+            ];
+          };
         },
         {
-          name: "owner";
+          name: "proposer";
           isMut: false;
           isSigner: true;
-        }
-      ];
-      args: [];
-    },
-    {
-      name: "unapprove";
-      accounts: [
-        {
-          name: "smallet";
-          isMut: false;
-          isSigner: false;
         },
         {
-          name: "transaction";
+          name: "payer";
           isMut: true;
-          isSigner: false;
-          relations: ["smallet"];
+          isSigner: true;
         },
         {
-          name: "owner";
+          name: "systemProgram";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         }
       ];
-      args: [];
-    },
-    {
-      name: "executeTransaction";
-      accounts: [
+      args: [
         {
-          name: "smallet";
-          isMut: false;
-          isSigner: false;
+          name: "nonce";
+          type: "u64";
         },
         {
-          name: "transaction";
-          isMut: true;
-          isSigner: false;
+          name: "instructions";
+          type: {
+            vec: {
+              defined: "TXInstruction";
+            };
+          };
         },
         {
-          name: "owner";
-          isMut: false;
-          isSigner: true;
+          name: "eta";
+          type: "i64";
+        },
+        {
+          name: "options";
+          type: {
+            defined: "TransactionOptions";
+          };
         }
       ];
-      args: [];
     },
     {
-      name: "executeTransactionDerived";
+      name: "createCommittedTransaction";
       accounts: [
         {
           name: "smallet";
@@ -312,57 +374,73 @@ export type SmalletIDL = {
           name: "transaction";
           isMut: true;
           isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicTransactionV2";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "proposer";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "nonce";
+              }
+            ];
+          };
         },
         {
-          name: "owner";
+          name: "proposer";
           isMut: false;
           isSigner: true;
-        }
-      ];
-      args: [
-        {
-          name: "index";
-          type: "u64";
         },
         {
-          name: "bump";
-          type: "u8";
-        }
-      ];
-    },
-    {
-      name: "ownerInvokeInstruction";
-      accounts: [
-        {
-          name: "smallet";
-          isMut: false;
-          isSigner: false;
+          name: "payer";
+          isMut: true;
+          isSigner: true;
         },
         {
-          name: "owner";
+          name: "systemProgram";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         }
       ];
       args: [
         {
-          name: "index";
+          name: "nonce";
           type: "u64";
         },
         {
-          name: "bump";
-          type: "u8";
+          name: "instructionsHash";
+          type: {
+            array: ["u8", 32];
+          };
         },
         {
-          name: "ix";
+          name: "eta";
+          type: "i64";
+        },
+        {
+          name: "options";
           type: {
-            defined: "TXInstruction";
+            defined: "TransactionOptions";
           };
         }
       ];
     },
     {
-      name: "ownerInvokeInstructionV2";
+      name: "proposeProgramUpgrade";
       accounts: [
         {
           name: "smallet";
@@ -370,35 +448,7 @@ export type SmalletIDL = {
           isSigner: false;
         },
         {
-          name: "owner";
-          isMut: false;
-          isSigner: true;
-        }
-      ];
-      args: [
-        {
-          name: "index";
-          type: "u64";
-        },
-        {
-          name: "bump";
-          type: "u8";
-        },
-        {
-          name: "invoker";
-          type: "publicKey";
-        },
-        {
-          name: "data";
-          type: "bytes";
-        }
-      ];
-    },
-    {
-      name: "createSubaccountInfo";
-      accounts: [
-        {
-          name: "subaccountInfo";
+          name: "transaction";
           isMut: true;
           isSigner: false;
           pda: {
@@ -406,16 +456,42 @@ export type SmalletIDL = {
               {
                 kind: "const";
                 type: "string";
-                value: "CosmicSubaccountInfo";
+                value: "CosmicTransactionV2";
               },
               {
-                kind: "arg";
+                kind: "account";
                 type: "publicKey";
-                path: "subaccount";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "proposer";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "nonce";
               }
             ];
           };
         },
+        {
+          name: "program";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "buffer";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "proposer";
+          isMut: false;
+          isSigner: true;
+        },
         {
           name: "payer";
           isMut: true;
@@ -429,704 +505,5644 @@ export type SmalletIDL = {
       ];
       args: [
         {
-          name: "bump";
-          type: "u8";
-        },
-        {
-          name: "subaccount";
-          type: "publicKey";
+          name: "nonce";
+          type: "u64";
         },
         {
-          name: "smallet";
+          name: "spill";
           type: "publicKey";
         },
         {
-          name: "index";
-          type: "u64";
-        },
-        {
-          name: "subaccountType";
+          name: "bufferHash";
           type: {
-            defined: "SubaccountType";
+            array: ["u8", 32];
           };
+        },
+        {
+          name: "eta";
+          type: "i64";
         }
       ];
-    }
-  ];
-  accounts: [
-    {
-      name: "Smallet";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "base";
-            type: "publicKey";
-          },
-          {
-            name: "bump";
-            type: "u8";
-          },
-          {
-            name: "threshold";
-            type: "u64";
-          },
-          {
-            name: "minimumDelay";
-            type: "i64";
-          },
-          {
-            name: "gracePeriod";
-            type: "i64";
-          },
-          {
-            name: "ownerSetSeqno";
-            type: "u32";
-          },
-          {
-            name: "numTransactions";
-            type: "u64";
-          },
-          {
-            name: "owners";
-            type: {
-              vec: "publicKey";
-            };
-          },
-          {
-            name: "reserved";
-            type: {
-              array: ["u64", 16];
-            };
-          }
-        ];
-      };
-    },
-    {
-      name: "Transaction";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "smallet";
-            type: "publicKey";
-          },
-          {
-            name: "index";
-            type: "u64";
-          },
-          {
-            name: "bump";
-            type: "u8";
-          },
-          {
-            name: "proposer";
-            type: "publicKey";
-          },
-          {
-            name: "instructions";
-            type: {
-              vec: {
-                defined: "TXInstruction";
-              };
-            };
-          },
-          {
-            name: "signers";
-            type: {
-              vec: "bool";
-            };
-          },
-          {
-            name: "ownerSetSeqno";
-            type: "u32";
-          },
-          {
-            name: "eta";
-            type: "i64";
-          },
-          {
-            name: "executor";
-            type: "publicKey";
-          },
-          {
-            name: "executedAt";
-            type: "i64";
-          }
-        ];
-      };
-    },
-    {
-      name: "SubaccountInfo";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "smallet";
-            type: "publicKey";
-          },
-          {
-            name: "subaccountType";
-            type: {
-              defined: "SubaccountType";
-            };
-          },
-          {
-            name: "index";
-            type: "u64";
-          }
-        ];
-      };
-    }
-  ];
-  types: [
-    {
-      name: "TXInstruction";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "programId";
-            type: "publicKey";
-          },
-          {
-            name: "keys";
-            type: {
-              vec: {
-                defined: "TXAccountMeta";
-              };
-            };
-          },
-          {
-            name: "data";
-            type: "bytes";
-          }
-        ];
-      };
-    },
-    {
-      name: "TXAccountMeta";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "pubkey";
-            type: "publicKey";
-          },
-          {
-            name: "isSigner";
-            type: "bool";
-          },
-          {
-            name: "isWritable";
-            type: "bool";
-          }
-        ];
-      };
     },
     {
-      name: "SubaccountType";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "Derived";
-          },
-          {
-            name: "OwnerInvoker";
-          }
-        ];
-      };
-    }
-  ];
-  events: [
-    {
-      name: "WalletCreateEvent";
-      fields: [
+      name: "proposeTransfer";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: "owners";
-          type: {
-            vec: "publicKey";
+          name: "transaction";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicTransactionV2";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "proposer";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "nonce";
+              }
+            ];
           };
-          index: false;
         },
         {
-          name: "threshold";
-          type: "u64";
-          index: false;
+          name: "proposer";
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "minimumDelay";
-          type: "i64";
-          index: false;
+          name: "payer";
+          isMut: true;
+          isSigner: true;
         },
         {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
         }
       ];
-    },
-    {
-      name: "WalletSetOwnersEvent";
-      fields: [
+      args: [
         {
-          name: "smallet";
-          type: "publicKey";
-          index: true;
+          name: "nonce";
+          type: "u64";
         },
         {
-          name: "owners";
+          name: "transfer";
           type: {
-            vec: "publicKey";
+            defined: "VaultTransfer";
           };
-          index: false;
         },
         {
-          name: "timestamp";
+          name: "eta";
           type: "i64";
-          index: false;
         }
       ];
     },
     {
-      name: "WalletChangeThresholdEvent";
-      fields: [
+      name: "assignTransactionIndex";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "threshold";
-          type: "u64";
-          index: false;
-        },
+          name: "transaction";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "advanceFifoCursor";
+      accounts: [
         {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          name: "smallet";
+          isMut: true;
+          isSigner: false;
         }
       ];
+      args: [];
     },
     {
-      name: "TransactionCreateEvent";
-      fields: [
+      name: "approve";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "transaction";
-          type: "publicKey";
-          index: true;
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
         },
         {
-          name: "proposer";
-          type: "publicKey";
-          index: false;
-        },
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "unapprove";
+      accounts: [
         {
-          name: "instructions";
-          type: {
-            vec: {
-              defined: "TXInstruction";
-            };
-          };
-          index: false;
+          name: "smallet";
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: "eta";
-          type: "i64";
-          index: false;
+          name: "transaction";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
         },
         {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          name: "owner";
+          isMut: false;
+          isSigner: true;
         }
       ];
+      args: [];
     },
     {
-      name: "TransactionApproveEvent";
-      fields: [
+      name: "veto";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "transaction";
-          type: "publicKey";
-          index: true;
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
         },
         {
           name: "owner";
-          type: "publicKey";
-          index: false;
+          isMut: false;
+          isSigner: true;
         },
         {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
         }
       ];
+      args: [];
     },
     {
-      name: "TransactionUnapproveEvent";
-      fields: [
+      name: "executeTransaction";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "transaction";
-          type: "publicKey";
-          index: true;
+          isMut: true;
+          isSigner: false;
         },
         {
           name: "owner";
-          type: "publicKey";
-          index: false;
-        },
-        {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          isMut: false;
+          isSigner: true;
         }
       ];
+      args: [];
     },
     {
-      name: "TransactionExecuteEvent";
-      fields: [
+      name: "executeTransactionDerived";
+      accounts: [
         {
           name: "smallet";
-          type: "publicKey";
-          index: true;
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "transaction";
-          type: "publicKey";
-          index: true;
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "executor";
-          type: "publicKey";
-          index: false;
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "index";
+          type: "u64";
         },
         {
-          name: "timestamp";
-          type: "i64";
-          index: false;
+          name: "bump";
+          type: "u8";
         }
       ];
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "InvalidOwner";
-      msg: "The given owner is not part of this smallet.";
-    },
-    {
-      code: 6001;
-      name: "InvalidETA";
-      msg: "Estimated execution block must satisfy delay.";
     },
     {
-      code: 6002;
-      name: "DelayTooHigh";
-      msg: "Delay greater than the maximum.";
+      name: "executeCommittedTransaction";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "transaction";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "salt";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "instructions";
+          type: {
+            vec: {
+              defined: "TXInstruction";
+            };
+          };
+        }
+      ];
     },
     {
-      code: 6003;
-      name: "NotEnoughSigners";
-      msg: "Not enough owners signed this transaction.";
+      name: "ownerInvokeInstruction";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "policy";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicInvokerPolicy";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "index";
+              }
+            ];
+          };
+        }
+      ];
+      args: [
+        {
+          name: "index";
+          type: "u64";
+        },
+        {
+          name: "bump";
+          type: "u8";
+        },
+        {
+          name: "ix";
+          type: {
+            defined: "TXInstruction";
+          };
+        }
+      ];
     },
     {
-      code: 6004;
+      name: "ownerInvokeInstructionV2";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "policy";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicInvokerPolicy";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "index";
+              }
+            ];
+          };
+        }
+      ];
+      args: [
+        {
+          name: "index";
+          type: "u64";
+        },
+        {
+          name: "bump";
+          type: "u8";
+        },
+        {
+          name: "invoker";
+          type: "publicKey";
+        },
+        {
+          name: "data";
+          type: "bytes";
+        }
+      ];
+    },
+    {
+      name: "migrateSmallet";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateTransaction";
+      accounts: [
+        {
+          name: "transaction";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "createInvokerPolicy";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "policy";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicInvokerPolicy";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "arg";
+                type: "u64";
+                path: "index";
+              }
+            ];
+          };
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "index";
+          type: "u64";
+        },
+        {
+          name: "owners";
+          type: {
+            vec: "publicKey";
+          };
+        },
+        {
+          name: "allowedInstructions";
+          type: {
+            vec: {
+              defined: "AllowedInstruction";
+            };
+          };
+        },
+        {
+          name: "maxCallsPerPeriod";
+          type: "u64";
+        },
+        {
+          name: "period";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "setInvokerPolicy";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "policy";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "owners";
+          type: {
+            vec: "publicKey";
+          };
+        },
+        {
+          name: "allowedInstructions";
+          type: {
+            vec: {
+              defined: "AllowedInstruction";
+            };
+          };
+        },
+        {
+          name: "maxCallsPerPeriod";
+          type: "u64";
+        },
+        {
+          name: "period";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "setTimelockTiers";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "tiers";
+          type: {
+            vec: {
+              defined: "TimelockTier";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "setOptimisticMode";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "config";
+          type: {
+            option: {
+              defined: "OptimisticConfig";
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "createAddressBook";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "addressBook";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicAddressBook";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              }
+            ];
+          };
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "threshold";
+          type: "u64";
+        },
+        {
+          name: "minimumDelay";
+          type: "i64";
+        },
+        {
+          name: "entryDelay";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "setAddressBookPolicy";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "addressBook";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        }
+      ];
+      args: [
+        {
+          name: "threshold";
+          type: "u64";
+        },
+        {
+          name: "minimumDelay";
+          type: "i64";
+        },
+        {
+          name: "entryDelay";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "addAddressBookEntry";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "addressBook";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "recipient";
+          type: "publicKey";
+        },
+        {
+          name: "label";
+          type: "string";
+        }
+      ];
+    },
+    {
+      name: "removeAddressBookEntry";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "addressBook";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        }
+      ];
+      args: [
+        {
+          name: "recipient";
+          type: "publicKey";
+        }
+      ];
+    },
+    {
+      name: "createSubaccountInfo";
+      accounts: [
+        {
+          name: "subaccountInfo";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicSubaccountInfo";
+              },
+              {
+                kind: "arg";
+                type: "publicKey";
+                path: "subaccount";
+              }
+            ];
+          };
+        },
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "registryEntry";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicSubaccountRegistry";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "u64";
+                account: "Smallet";
+                path: "smallet.num_subaccounts";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "bump";
+          type: "u8";
+        },
+        {
+          name: "subaccount";
+          type: "publicKey";
+        },
+        {
+          name: "smallet";
+          type: "publicKey";
+        },
+        {
+          name: "index";
+          type: "u64";
+        },
+        {
+          name: "subaccountType";
+          type: {
+            defined: "SubaccountType";
+          };
+        },
+        {
+          name: "label";
+          type: {
+            option: "string";
+          };
+        }
+      ];
+    },
+    {
+      name: "updateSubaccountInfo";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "subaccountInfo";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        }
+      ];
+      args: [
+        {
+          name: "label";
+          type: {
+            option: "string";
+          };
+        }
+      ];
+    },
+    {
+      name: "closeSubaccountInfo";
+      accounts: [
+        {
+          name: "smallet";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "subaccountInfo";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        },
+        {
+          name: "registryEntry";
+          isMut: true;
+          isSigner: false;
+          relations: ["smallet"];
+        },
+        {
+          name: "receiver";
+          isMut: true;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateSubaccountInfo";
+      accounts: [
+        {
+          name: "subaccountInfo";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "smallet";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "registryEntry";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "CosmicSubaccountRegistry";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Smallet";
+                path: "smallet";
+              },
+              {
+                kind: "account";
+                type: "u64";
+                account: "Smallet";
+                path: "smallet.num_subaccounts";
+              }
+            ];
+          };
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    }
+  ];
+  accounts: [
+    {
+      name: "Smallet";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "base";
+            type: "publicKey";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "threshold";
+            type: "u64";
+          },
+          {
+            name: "minimumDelay";
+            type: "i64";
+          },
+          {
+            name: "gracePeriod";
+            type: "i64";
+          },
+          {
+            name: "ownerSetSeqno";
+            type: "u32";
+          },
+          {
+            name: "numTransactions";
+            type: "u64";
+          },
+          {
+            name: "owners";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "version";
+            type: "u8";
+          },
+          {
+            name: "numSubaccounts";
+            type: "u64";
+          },
+          {
+            name: "tiers";
+            type: {
+              vec: {
+                defined: "TimelockTier";
+              };
+            };
+          },
+          {
+            name: "vetoThreshold";
+            type: "u64";
+          },
+          {
+            name: "optimistic";
+            type: {
+              option: {
+                defined: "OptimisticConfig";
+              };
+            };
+          },
+          {
+            name: "approvalTtl";
+            type: "i64";
+          },
+          {
+            name: "fifoCursor";
+            type: {
+              option: "u64";
+            };
+          },
+          {
+            name: "reserved";
+            type: {
+              array: ["u64", 16];
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "Transaction";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "index";
+            type: "u64";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "instructions";
+            type: {
+              vec: {
+                defined: "TXInstruction";
+              };
+            };
+          },
+          {
+            name: "signers";
+            type: {
+              vec: "bool";
+            };
+          },
+          {
+            name: "ownerSetSeqno";
+            type: "u32";
+          },
+          {
+            name: "eta";
+            type: "i64";
+          },
+          {
+            name: "executor";
+            type: "publicKey";
+          },
+          {
+            name: "executedAt";
+            type: "i64";
+          },
+          {
+            name: "version";
+            type: "u8";
+          },
+          {
+            name: "programUpgrade";
+            type: {
+              option: {
+                defined: "ProgramUpgrade";
+              };
+            };
+          },
+          {
+            name: "pdaSigners";
+            type: {
+              vec: {
+                defined: "PdaSigner";
+              };
+            };
+          },
+          {
+            name: "instructionsHash";
+            type: {
+              option: {
+                array: ["u8", 32];
+              };
+            };
+          },
+          {
+            name: "tier";
+            type: {
+              option: "u8";
+            };
+          },
+          {
+            name: "vetoes";
+            type: {
+              vec: "bool";
+            };
+          },
+          {
+            name: "vetoedAt";
+            type: {
+              option: "i64";
+            };
+          },
+          {
+            name: "optimistic";
+            type: "bool";
+          },
+          {
+            name: "approvedAt";
+            type: {
+              vec: "i64";
+            };
+          },
+          {
+            name: "prerequisites";
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "preConditions";
+            type: {
+              vec: {
+                defined: "PreCondition";
+              };
+            };
+          },
+          {
+            name: "postConditions";
+            type: {
+              vec: {
+                defined: "PostCondition";
+              };
+            };
+          },
+          {
+            name: "transfer";
+            type: {
+              option: {
+                defined: "VaultTransfer";
+              };
+            };
+          },
+          {
+            name: "addressBook";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "SubaccountInfo";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "subaccountType";
+            type: {
+              defined: "SubaccountType";
+            };
+          },
+          {
+            name: "index";
+            type: "u64";
+          },
+          {
+            name: "version";
+            type: "u8";
+          },
+          {
+            name: "registryIndex";
+            type: "u64";
+          },
+          {
+            name: "createdAt";
+            type: "i64";
+          },
+          {
+            name: "label";
+            type: {
+              option: "string";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "SubaccountRegistryEntry";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "registryIndex";
+            type: "u64";
+          },
+          {
+            name: "subaccount";
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "InvokerPolicy";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "index";
+            type: "u64";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "owners";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "allowedInstructions";
+            type: {
+              vec: {
+                defined: "AllowedInstruction";
+              };
+            };
+          },
+          {
+            name: "maxCallsPerPeriod";
+            type: "u64";
+          },
+          {
+            name: "period";
+            type: "i64";
+          },
+          {
+            name: "periodStart";
+            type: "i64";
+          },
+          {
+            name: "callsInPeriod";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "AddressBook";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "threshold";
+            type: "u64";
+          },
+          {
+            name: "minimumDelay";
+            type: "i64";
+          },
+          {
+            name: "entryDelay";
+            type: "i64";
+          },
+          {
+            name: "entries";
+            type: {
+              vec: {
+                defined: "AddressBookEntry";
+              };
+            };
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "SmalletV0";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "base";
+            type: "publicKey";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "threshold";
+            type: "u64";
+          },
+          {
+            name: "minimumDelay";
+            type: "i64";
+          },
+          {
+            name: "gracePeriod";
+            type: "i64";
+          },
+          {
+            name: "ownerSetSeqno";
+            type: "u32";
+          },
+          {
+            name: "numTransactions";
+            type: "u64";
+          },
+          {
+            name: "owners";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "reserved";
+            type: {
+              array: ["u64", 16];
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "TransactionV0";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "index";
+            type: "u64";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "proposer";
+            type: "publicKey";
+          },
+          {
+            name: "instructions";
+            type: {
+              vec: {
+                defined: "TXInstruction";
+              };
+            };
+          },
+          {
+            name: "signers";
+            type: {
+              vec: "bool";
+            };
+          },
+          {
+            name: "ownerSetSeqno";
+            type: "u32";
+          },
+          {
+            name: "eta";
+            type: "i64";
+          },
+          {
+            name: "executor";
+            type: "publicKey";
+          },
+          {
+            name: "executedAt";
+            type: "i64";
+          }
+        ];
+      };
+    },
+    {
+      name: "TXInstruction";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "programId";
+            type: "publicKey";
+          },
+          {
+            name: "keys";
+            type: {
+              vec: {
+                defined: "TXAccountMeta";
+              };
+            };
+          },
+          {
+            name: "data";
+            type: "bytes";
+          }
+        ];
+      };
+    },
+    {
+      name: "TXAccountMeta";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pubkey";
+            type: "publicKey";
+          },
+          {
+            name: "isSigner";
+            type: "bool";
+          },
+          {
+            name: "isWritable";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "TransactionOptions";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pdaSigners";
+            type: {
+              vec: {
+                defined: "PdaSigner";
+              };
+            };
+          },
+          {
+            name: "tier";
+            type: {
+              option: "u8";
+            };
+          },
+          {
+            name: "optimistic";
+            type: "bool";
+          },
+          {
+            name: "prerequisites";
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "preConditions";
+            type: {
+              vec: {
+                defined: "PreCondition";
+              };
+            };
+          },
+          {
+            name: "postConditions";
+            type: {
+              vec: {
+                defined: "PostCondition";
+              };
+            };
+          },
+          {
+            name: "addressBook";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "OptimisticConfig";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "proposers";
+            type: {
+              vec: "publicKey";
+            };
+          },
+          {
+            name: "objectionThreshold";
+            type: "u64";
+          },
+          {
+            name: "challengePeriod";
+            type: "i64";
+          },
+          {
+            name: "allowedInstructions";
+            type: {
+              vec: {
+                defined: "AllowedInstruction";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "TimelockTier";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "name";
+            type: "string";
+          },
+          {
+            name: "minimumDelay";
+            type: "i64";
+          },
+          {
+            name: "threshold";
+            type: "u64";
+          },
+          {
+            name: "allowedInstructions";
+            type: {
+              vec: {
+                defined: "AllowedInstruction";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "ProgramUpgrade";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "program";
+            type: "publicKey";
+          },
+          {
+            name: "buffer";
+            type: "publicKey";
+          },
+          {
+            name: "bufferHash";
+            type: {
+              array: ["u8", 32];
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "VaultTransfer";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "asset";
+            type: {
+              defined: "TransferAsset";
+            };
+          },
+          {
+            name: "amount";
+            type: "u64";
+          },
+          {
+            name: "sourceIndex";
+            type: "u64";
+          },
+          {
+            name: "recipient";
+            type: "publicKey";
+          }
+        ];
+      };
+    },
+    {
+      name: "SubaccountInfoV0";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "smallet";
+            type: "publicKey";
+          },
+          {
+            name: "subaccountType";
+            type: {
+              defined: "SubaccountType";
+            };
+          },
+          {
+            name: "index";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "AllowedInstruction";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "programId";
+            type: "publicKey";
+          },
+          {
+            name: "discriminator";
+            type: "bytes";
+          }
+        ];
+      };
+    },
+    {
+      name: "AddressBookEntry";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "recipient";
+            type: "publicKey";
+          },
+          {
+            name: "label";
+            type: "string";
+          },
+          {
+            name: "trustedAt";
+            type: "i64";
+          }
+        ];
+      };
+    },
+    {
+      name: "PreCondition";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "account";
+            type: "publicKey";
+          },
+          {
+            name: "check";
+            type: {
+              defined: "AccountCheck";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "PostCondition";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "account";
+            type: "publicKey";
+          },
+          {
+            name: "limit";
+            type: {
+              defined: "OutflowLimit";
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "PdaSigner";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Smallet";
+          },
+          {
+            name: "Derived";
+            fields: [
+              {
+                name: "index";
+                type: "u64";
+              },
+              {
+                name: "bump";
+                type: "u8";
+              }
+            ];
+          },
+          {
+            name: "OwnerInvoker";
+            fields: [
+              {
+                name: "index";
+                type: "u64";
+              },
+              {
+                name: "bump";
+                type: "u8";
+              }
+            ];
+          }
+        ];
+      };
+    },
+    {
+      name: "TransferAsset";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Sol";
+          },
+          {
+            name: "Token";
+            fields: [
+              {
+                name: "mint";
+                type: "publicKey";
+              }
+            ];
+          }
+        ];
+      };
+    },
+    {
+      name: "SubaccountType";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Derived";
+          },
+          {
+            name: "OwnerInvoker";
+          }
+        ];
+      };
+    },
+    {
+      name: "AccountCheck";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "DataHash";
+            fields: [
+              {
+                name: "hash";
+                type: {
+                  array: ["u8", 32];
+                };
+              }
+            ];
+          },
+          {
+            name: "MinLamports";
+            fields: [
+              {
+                name: "lamports";
+                type: "u64";
+              }
+            ];
+          },
+          {
+            name: "TokenAmount";
+            fields: [
+              {
+                name: "min";
+                type: "u64";
+              },
+              {
+                name: "max";
+                type: "u64";
+              }
+            ];
+          },
+          {
+            name: "Owner";
+            fields: [
+              {
+                name: "program_id";
+                type: "publicKey";
+              }
+            ];
+          }
+        ];
+      };
+    },
+    {
+      name: "OutflowLimit";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Lamports";
+            fields: [
+              {
+                name: "max_outflow";
+                type: "u64";
+              }
+            ];
+          },
+          {
+            name: "Tokens";
+            fields: [
+              {
+                name: "max_outflow";
+                type: "u64";
+              }
+            ];
+          },
+          {
+            name: "MinLamports";
+            fields: [
+              {
+                name: "min_balance";
+                type: "u64";
+              }
+            ];
+          },
+          {
+            name: "MinTokens";
+            fields: [
+              {
+                name: "min_balance";
+                type: "u64";
+              }
+            ];
+          }
+        ];
+      };
+    }
+  ];
+  events: [
+    {
+      name: "WalletCreateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "owners";
+          type: {
+            vec: "publicKey";
+          };
+          index: false;
+        },
+        {
+          name: "threshold";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "minimumDelay";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WalletSetOwnersEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "owners";
+          type: {
+            vec: "publicKey";
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WalletChangeThresholdEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "threshold";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WalletChangeVetoThresholdEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "vetoThreshold";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WalletChangeApprovalTtlEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "approvalTtl";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FifoCursorSetEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "cursor";
+          type: {
+            option: "u64";
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionCreateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "instructions";
+          type: {
+            vec: {
+              defined: "TXInstruction";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "eta";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionApproveEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionUnapproveEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionVetoEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "blocked";
+          type: "bool";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionExecuteEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "executor";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionIndexAssignEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "index";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SmalletMigrateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "version";
+          type: "u8";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionMigrateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "version";
+          type: "u8";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ProgramUpgradeProposeEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "program";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "buffer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "bufferHash";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransferProposeEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "proposer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "transfer";
+          type: {
+            defined: "VaultTransfer";
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransferExecuteEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "asset";
+          type: {
+            defined: "TransferAsset";
+          };
+          index: false;
+        },
+        {
+          name: "amount";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "source";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "destination";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionCommitEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "instructionsHash";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TransactionRevealEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "transaction";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "salt";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        },
+        {
+          name: "instructions";
+          type: {
+            vec: {
+              defined: "TXInstruction";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "TimelockTiersSetEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "tiers";
+          type: {
+            vec: {
+              defined: "TimelockTier";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "OptimisticModeSetEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "config";
+          type: {
+            option: {
+              defined: "OptimisticConfig";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "InvokerPolicySetEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "policy";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "index";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "owners";
+          type: {
+            vec: "publicKey";
+          };
+          index: false;
+        },
+        {
+          name: "allowedInstructions";
+          type: {
+            vec: {
+              defined: "AllowedInstruction";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "maxCallsPerPeriod";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "period";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "OwnerInvokeEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "invoker";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "index";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "owner";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "programId";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SubaccountCreateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "subaccount";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "subaccountType";
+          type: {
+            defined: "SubaccountType";
+          };
+          index: false;
+        },
+        {
+          name: "index";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "registryIndex";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "label";
+          type: {
+            option: "string";
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SubaccountUpdateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "subaccountInfo";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "label";
+          type: {
+            option: "string";
+          };
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SubaccountCloseEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "subaccount";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "registryIndex";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SubaccountMigrateEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "subaccount";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "registryIndex";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "AddressBookPolicySetEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "addressBook";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "threshold";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "minimumDelay";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "entryDelay";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "AddressBookEntryAddEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "addressBook";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "recipient";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "label";
+          type: "string";
+          index: false;
+        },
+        {
+          name: "trustedAt";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "AddressBookEntryRemoveEvent";
+      fields: [
+        {
+          name: "smallet";
+          type: "publicKey";
+          index: true;
+        },
+        {
+          name: "addressBook";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "recipient";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "timestamp";
+          type: "i64";
+          index: false;
+        }
+      ];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "InvalidOwner";
+      msg: "The given owner is not part of this smallet.";
+    },
+    {
+      code: 6001;
+      name: "InvalidETA";
+      msg: "Estimated execution block must satisfy delay.";
+    },
+    {
+      code: 6002;
+      name: "DelayTooHigh";
+      msg: "Delay greater than the maximum.";
+    },
+    {
+      code: 6003;
+      name: "NotEnoughSigners";
+      msg: "Not enough owners signed this transaction.";
+    },
+    {
+      code: 6004;
       name: "TransactionIsStale";
       msg: "Transaction is past the grace period.";
     },
     {
-      code: 6005;
-      name: "TransactionNotReady";
-      msg: "Transaction hasn't surpassed time lock.";
+      code: 6005;
+      name: "TransactionNotReady";
+      msg: "Transaction hasn't surpassed time lock.";
+    },
+    {
+      code: 6006;
+      name: "AlreadyExecuted";
+      msg: "The given transaction has already been executed.";
+    },
+    {
+      code: 6007;
+      name: "InvalidThreshold";
+      msg: "Threshold must be less than or equal to the number of owners.";
+    },
+    {
+      code: 6008;
+      name: "OwnerSetChanged";
+      msg: "Owner set has changed since the creation of the transaction.";
+    },
+    {
+      code: 6009;
+      name: "SubaccountOwnerMismatch";
+      msg: "Subaccount does not belong to smallet.";
+    },
+    {
+      code: 6010;
+      name: "BufferFinalized";
+      msg: "Buffer already finalized.";
+    },
+    {
+      code: 6011;
+      name: "BufferBundleNotFound";
+      msg: "Buffer bundle not found.";
+    },
+    {
+      code: 6012;
+      name: "BufferBundleOutOfRange";
+      msg: "Buffer index specified is out of range.";
+    },
+    {
+      code: 6013;
+      name: "BufferBundleNotFinalized";
+      msg: "Buffer has not been finalized.";
+    },
+    {
+      code: 6014;
+      name: "BufferBundleExecuted";
+      msg: "Buffer bundle has already been executed.";
+    },
+    {
+      code: 6015;
+      name: "TransactionIndexAlreadyAssigned";
+      msg: "Transaction has already been assigned an index.";
+    },
+    {
+      code: 6016;
+      name: "AccountNotMigrated";
+      msg: "Account must be migrated to the current layout.";
+    },
+    {
+      code: 6017;
+      name: "AccountAlreadyMigrated";
+      msg: "Account has already been migrated.";
+    },
+    {
+      code: 6018;
+      name: "InvalidProgramBuffer";
+      msg: "Program buffer is not a valid upgradeable loader buffer.";
+    },
+    {
+      code: 6019;
+      name: "MissingProgramBuffer";
+      msg: "Program buffer account was not provided.";
+    },
+    {
+      code: 6020;
+      name: "BufferHashMismatch";
+      msg: "Program buffer has changed since the upgrade was proposed.";
+    },
+    {
+      code: 6021;
+      name: "InvalidSignerBump";
+      msg: "Bump seed of a PDA signer is not canonical.";
+    },
+    {
+      code: 6022;
+      name: "DerivedSignerNotDeclared";
+      msg: "Derived address was not declared as a signer of the transaction.";
+    },
+    {
+      code: 6023;
+      name: "InvokerNotAllowed";
+      msg: "Owner is not allowed to use this owner invoker.";
+    },
+    {
+      code: 6024;
+      name: "InstructionNotAllowed";
+      msg: "Instruction is not allowed by the invoker policy.";
+    },
+    {
+      code: 6025;
+      name: "InvokerCallLimitExceeded";
+      msg: "Owner invoker has reached its call limit for the period.";
+    },
+    {
+      code: 6026;
+      name: "InvalidInvoker";
+      msg: "Invoker is not the owner invoker derived from the given index and bump.";
+    },
+    {
+      code: 6027;
+      name: "LabelTooLong";
+      msg: "Label is too long.";
+    },
+    {
+      code: 6028;
+      name: "InstructionsNotRevealed";
+      msg: "Instructions of a committed transaction must be revealed to execute it.";
+    },
+    {
+      code: 6029;
+      name: "TransactionNotCommitted";
+      msg: "Transaction did not commit to the hash of its instructions.";
+    },
+    {
+      code: 6030;
+      name: "InstructionsHashMismatch";
+      msg: "Revealed instructions do not match the committed hash.";
+    },
+    {
+      code: 6031;
+      name: "InvalidTier";
+      msg: "Timelock tier does not exist on this smallet.";
+    },
+    {
+      code: 6032;
+      name: "InstructionNotInTier";
+      msg: "Instruction is not allowed in the timelock tier of the transaction.";
+    },
+    {
+      code: 6033;
+      name: "VetoDisabled";
+      msg: "Vetoes are disabled on this smallet.";
+    },
+    {
+      code: 6034;
+      name: "VetoWindowClosed";
+      msg: "Transaction can only be vetoed before its ETA.";
+    },
+    {
+      code: 6035;
+      name: "TransactionVetoed";
+      msg: "Transaction was vetoed.";
+    },
+    {
+      code: 6036;
+      name: "OptimisticModeDisabled";
+      msg: "Optimistic mode is disabled on this smallet.";
+    },
+    {
+      code: 6037;
+      name: "UnauthorizedProposer";
+      msg: "Proposer is not authorized to propose optimistically.";
+    },
+    {
+      code: 6038;
+      name: "InvalidPrerequisite";
+      msg: "Prerequisite must be the index of an earlier transaction.";
+    },
+    {
+      code: 6039;
+      name: "PrerequisiteNotExecuted";
+      msg: "Prerequisite transaction is missing or not executed.";
+    },
+    {
+      code: 6040;
+      name: "TransactionNotIndexed";
+      msg: "Transaction must be assigned an index to be executed in order.";
+    },
+    {
+      code: 6041;
+      name: "EarlierTransactionPending";
+      msg: "An earlier transaction is missing or still pending.";
+    },
+    {
+      code: 6042;
+      name: "InvalidPreCondition";
+      msg: "Pre-condition can never hold.";
+    },
+    {
+      code: 6043;
+      name: "MissingPreConditionAccount";
+      msg: "Account of a pre-condition was not passed.";
+    },
+    {
+      code: 6044;
+      name: "PreConditionFailed";
+      msg: "Pre-condition does not hold.";
+    },
+    {
+      code: 6045;
+      name: "NotATokenAccount";
+      msg: "Account is not an SPL Token account.";
+    },
+    {
+      code: 6046;
+      name: "MissingPostConditionAccount";
+      msg: "Account of a post-condition was not passed.";
+    },
+    {
+      code: 6047;
+      name: "PostConditionFailed";
+      msg: "Post-condition does not hold after execution.";
+    },
+    {
+      code: 6048;
+      name: "InvalidTransferAmount";
+      msg: "Transfer amount must be positive.";
+    },
+    {
+      code: 6049;
+      name: "MissingAddressBook";
+      msg: "Address book of the smallet was not passed.";
+    },
+    {
+      code: 6050;
+      name: "RecipientNotTrusted";
+      msg: "Instruction does not pay a trusted address book entry.";
+    },
+    {
+      code: 6051;
+      name: "AddressBookEntryExists";
+      msg: "Recipient is already in the address book.";
+    },
+    {
+      code: 6052;
+      name: "AddressBookEntryNotFound";
+      msg: "Recipient is not in the address book.";
+    },
+    {
+      code: 6053;
+      name: "MissingInvokedProgram";
+      msg: "Program to invoke was not passed.";
+    },
+    {
+      code: 6054;
+      name: "TierRequired";
+      msg: "Smallets with timelock tiers only accept proposals via create_transaction_v2.";
+    },
+    {
+      code: 6055;
+      name: "InstructionNotOptimistic";
+      msg: "Instruction may not be proposed optimistically.";
+    }
+  ];
+};
+export const SmalletJSON: SmalletIDL = {
+  version: "0.11.1",
+  name: "smallet",
+  instructions: [
+    {
+      name: "createSmallet",
+      accounts: [
+        {
+          name: "base",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicSmallet",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "base",
+              },
+            ],
+          },
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "maxOwners",
+          type: "u8",
+        },
+        {
+          name: "owners",
+          type: {
+            vec: "publicKey",
+          },
+        },
+        {
+          name: "threshold",
+          type: "u64",
+        },
+        {
+          name: "minimumDelay",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "setOwners",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "owners",
+          type: {
+            vec: "publicKey",
+          },
+        },
+      ],
+    },
+    {
+      name: "changeThreshold",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "threshold",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "changeVetoThreshold",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "vetoThreshold",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "changeApprovalTtl",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "approvalTtl",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "setFifoMode",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "enabled",
+          type: "bool",
+        },
+      ],
+    },
+    {
+      name: "createTransaction",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransaction",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "u64",
+                account: "Smallet",
+                path: "smallet.num_transactions",
+              },
+            ],
+          },
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "TXInstruction",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "createTransactionWithTimelock",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransaction",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "u64",
+                account: "Smallet",
+                path: "smallet.num_transactions",
+              },
+            ],
+          },
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "TXInstruction",
+            },
+          },
+        },
+        {
+          name: "eta",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "createTransactionV2",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransactionV2",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "proposer",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "nonce",
+              },
+            ],
+          },
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "TXInstruction",
+            },
+          },
+        },
+        {
+          name: "eta",
+          type: "i64",
+        },
+        {
+          name: "options",
+          type: {
+            defined: "TransactionOptions",
+          },
+        },
+      ],
+    },
+    {
+      name: "createCommittedTransaction",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransactionV2",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "proposer",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "nonce",
+              },
+            ],
+          },
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "instructionsHash",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+        {
+          name: "eta",
+          type: "i64",
+        },
+        {
+          name: "options",
+          type: {
+            defined: "TransactionOptions",
+          },
+        },
+      ],
+    },
+    {
+      name: "proposeProgramUpgrade",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransactionV2",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "proposer",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "nonce",
+              },
+            ],
+          },
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "buffer",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "spill",
+          type: "publicKey",
+        },
+        {
+          name: "bufferHash",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+        {
+          name: "eta",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "proposeTransfer",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicTransactionV2",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "proposer",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "nonce",
+              },
+            ],
+          },
+        },
+        {
+          name: "proposer",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "transfer",
+          type: {
+            defined: "VaultTransfer",
+          },
+        },
+        {
+          name: "eta",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "assignTransactionIndex",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "advanceFifoCursor",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "approve",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "unapprove",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "veto",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "executeTransaction",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "executeTransactionDerived",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u64",
+        },
+        {
+          name: "bump",
+          type: "u8",
+        },
+      ],
+    },
+    {
+      name: "executeCommittedTransaction",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "salt",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "TXInstruction",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "ownerInvokeInstruction",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "policy",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicInvokerPolicy",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "index",
+              },
+            ],
+          },
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u64",
+        },
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "ix",
+          type: {
+            defined: "TXInstruction",
+          },
+        },
+      ],
+    },
+    {
+      name: "ownerInvokeInstructionV2",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "policy",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicInvokerPolicy",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "index",
+              },
+            ],
+          },
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u64",
+        },
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "invoker",
+          type: "publicKey",
+        },
+        {
+          name: "data",
+          type: "bytes",
+        },
+      ],
+    },
+    {
+      name: "migrateSmallet",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateTransaction",
+      accounts: [
+        {
+          name: "transaction",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "createInvokerPolicy",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "policy",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicInvokerPolicy",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "arg",
+                type: "u64",
+                path: "index",
+              },
+            ],
+          },
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "index",
+          type: "u64",
+        },
+        {
+          name: "owners",
+          type: {
+            vec: "publicKey",
+          },
+        },
+        {
+          name: "allowedInstructions",
+          type: {
+            vec: {
+              defined: "AllowedInstruction",
+            },
+          },
+        },
+        {
+          name: "maxCallsPerPeriod",
+          type: "u64",
+        },
+        {
+          name: "period",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "setInvokerPolicy",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "policy",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "owners",
+          type: {
+            vec: "publicKey",
+          },
+        },
+        {
+          name: "allowedInstructions",
+          type: {
+            vec: {
+              defined: "AllowedInstruction",
+            },
+          },
+        },
+        {
+          name: "maxCallsPerPeriod",
+          type: "u64",
+        },
+        {
+          name: "period",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "setTimelockTiers",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "tiers",
+          type: {
+            vec: {
+              defined: "TimelockTier",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "setOptimisticMode",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "config",
+          type: {
+            option: {
+              defined: "OptimisticConfig",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "createAddressBook",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "addressBook",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicAddressBook",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+            ],
+          },
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "threshold",
+          type: "u64",
+        },
+        {
+          name: "minimumDelay",
+          type: "i64",
+        },
+        {
+          name: "entryDelay",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "setAddressBookPolicy",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "addressBook",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+      ],
+      args: [
+        {
+          name: "threshold",
+          type: "u64",
+        },
+        {
+          name: "minimumDelay",
+          type: "i64",
+        },
+        {
+          name: "entryDelay",
+          type: "i64",
+        },
+      ],
+    },
+    {
+      name: "addAddressBookEntry",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "addressBook",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "recipient",
+          type: "publicKey",
+        },
+        {
+          name: "label",
+          type: "string",
+        },
+      ],
+    },
+    {
+      name: "removeAddressBookEntry",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "addressBook",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+      ],
+      args: [
+        {
+          name: "recipient",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "createSubaccountInfo",
+      accounts: [
+        {
+          name: "subaccountInfo",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicSubaccountInfo",
+              },
+              {
+                kind: "arg",
+                type: "publicKey",
+                path: "subaccount",
+              },
+            ],
+          },
+        },
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "registryEntry",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicSubaccountRegistry",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "u64",
+                account: "Smallet",
+                path: "smallet.num_subaccounts",
+              },
+            ],
+          },
+        },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "bump",
+          type: "u8",
+        },
+        {
+          name: "subaccount",
+          type: "publicKey",
+        },
+        {
+          name: "smallet",
+          type: "publicKey",
+        },
+        {
+          name: "index",
+          type: "u64",
+        },
+        {
+          name: "subaccountType",
+          type: {
+            defined: "SubaccountType",
+          },
+        },
+        {
+          name: "label",
+          type: {
+            option: "string",
+          },
+        },
+      ],
+    },
+    {
+      name: "updateSubaccountInfo",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "subaccountInfo",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+      ],
+      args: [
+        {
+          name: "label",
+          type: {
+            option: "string",
+          },
+        },
+      ],
+    },
+    {
+      name: "closeSubaccountInfo",
+      accounts: [
+        {
+          name: "smallet",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "subaccountInfo",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "registryEntry",
+          isMut: true,
+          isSigner: false,
+          relations: ["smallet"],
+        },
+        {
+          name: "receiver",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateSubaccountInfo",
+      accounts: [
+        {
+          name: "subaccountInfo",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "smallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "registryEntry",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "CosmicSubaccountRegistry",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Smallet",
+                path: "smallet",
+              },
+              {
+                kind: "account",
+                type: "u64",
+                account: "Smallet",
+                path: "smallet.num_subaccounts",
+              },
+            ],
+          },
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+  ],
+  accounts: [
+    {
+      name: "Smallet",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "base",
+            type: "publicKey",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "threshold",
+            type: "u64",
+          },
+          {
+            name: "minimumDelay",
+            type: "i64",
+          },
+          {
+            name: "gracePeriod",
+            type: "i64",
+          },
+          {
+            name: "ownerSetSeqno",
+            type: "u32",
+          },
+          {
+            name: "numTransactions",
+            type: "u64",
+          },
+          {
+            name: "owners",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "version",
+            type: "u8",
+          },
+          {
+            name: "numSubaccounts",
+            type: "u64",
+          },
+          {
+            name: "tiers",
+            type: {
+              vec: {
+                defined: "TimelockTier",
+              },
+            },
+          },
+          {
+            name: "vetoThreshold",
+            type: "u64",
+          },
+          {
+            name: "optimistic",
+            type: {
+              option: {
+                defined: "OptimisticConfig",
+              },
+            },
+          },
+          {
+            name: "approvalTtl",
+            type: "i64",
+          },
+          {
+            name: "fifoCursor",
+            type: {
+              option: "u64",
+            },
+          },
+          {
+            name: "reserved",
+            type: {
+              array: ["u64", 16],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "Transaction",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "instructions",
+            type: {
+              vec: {
+                defined: "TXInstruction",
+              },
+            },
+          },
+          {
+            name: "signers",
+            type: {
+              vec: "bool",
+            },
+          },
+          {
+            name: "ownerSetSeqno",
+            type: "u32",
+          },
+          {
+            name: "eta",
+            type: "i64",
+          },
+          {
+            name: "executor",
+            type: "publicKey",
+          },
+          {
+            name: "executedAt",
+            type: "i64",
+          },
+          {
+            name: "version",
+            type: "u8",
+          },
+          {
+            name: "programUpgrade",
+            type: {
+              option: {
+                defined: "ProgramUpgrade",
+              },
+            },
+          },
+          {
+            name: "pdaSigners",
+            type: {
+              vec: {
+                defined: "PdaSigner",
+              },
+            },
+          },
+          {
+            name: "instructionsHash",
+            type: {
+              option: {
+                array: ["u8", 32],
+              },
+            },
+          },
+          {
+            name: "tier",
+            type: {
+              option: "u8",
+            },
+          },
+          {
+            name: "vetoes",
+            type: {
+              vec: "bool",
+            },
+          },
+          {
+            name: "vetoedAt",
+            type: {
+              option: "i64",
+            },
+          },
+          {
+            name: "optimistic",
+            type: "bool",
+          },
+          {
+            name: "approvedAt",
+            type: {
+              vec: "i64",
+            },
+          },
+          {
+            name: "prerequisites",
+            type: {
+              vec: "u64",
+            },
+          },
+          {
+            name: "preConditions",
+            type: {
+              vec: {
+                defined: "PreCondition",
+              },
+            },
+          },
+          {
+            name: "postConditions",
+            type: {
+              vec: {
+                defined: "PostCondition",
+              },
+            },
+          },
+          {
+            name: "transfer",
+            type: {
+              option: {
+                defined: "VaultTransfer",
+              },
+            },
+          },
+          {
+            name: "addressBook",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "SubaccountInfo",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "subaccountType",
+            type: {
+              defined: "SubaccountType",
+            },
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+          {
+            name: "version",
+            type: "u8",
+          },
+          {
+            name: "registryIndex",
+            type: "u64",
+          },
+          {
+            name: "createdAt",
+            type: "i64",
+          },
+          {
+            name: "label",
+            type: {
+              option: "string",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "SubaccountRegistryEntry",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "registryIndex",
+            type: "u64",
+          },
+          {
+            name: "subaccount",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "InvokerPolicy",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "owners",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "allowedInstructions",
+            type: {
+              vec: {
+                defined: "AllowedInstruction",
+              },
+            },
+          },
+          {
+            name: "maxCallsPerPeriod",
+            type: "u64",
+          },
+          {
+            name: "period",
+            type: "i64",
+          },
+          {
+            name: "periodStart",
+            type: "i64",
+          },
+          {
+            name: "callsInPeriod",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "AddressBook",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "threshold",
+            type: "u64",
+          },
+          {
+            name: "minimumDelay",
+            type: "i64",
+          },
+          {
+            name: "entryDelay",
+            type: "i64",
+          },
+          {
+            name: "entries",
+            type: {
+              vec: {
+                defined: "AddressBookEntry",
+              },
+            },
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "SmalletV0",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "base",
+            type: "publicKey",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "threshold",
+            type: "u64",
+          },
+          {
+            name: "minimumDelay",
+            type: "i64",
+          },
+          {
+            name: "gracePeriod",
+            type: "i64",
+          },
+          {
+            name: "ownerSetSeqno",
+            type: "u32",
+          },
+          {
+            name: "numTransactions",
+            type: "u64",
+          },
+          {
+            name: "owners",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "reserved",
+            type: {
+              array: ["u64", 16],
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "TransactionV0",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+          {
+            name: "bump",
+            type: "u8",
+          },
+          {
+            name: "proposer",
+            type: "publicKey",
+          },
+          {
+            name: "instructions",
+            type: {
+              vec: {
+                defined: "TXInstruction",
+              },
+            },
+          },
+          {
+            name: "signers",
+            type: {
+              vec: "bool",
+            },
+          },
+          {
+            name: "ownerSetSeqno",
+            type: "u32",
+          },
+          {
+            name: "eta",
+            type: "i64",
+          },
+          {
+            name: "executor",
+            type: "publicKey",
+          },
+          {
+            name: "executedAt",
+            type: "i64",
+          },
+        ],
+      },
+    },
+    {
+      name: "TXInstruction",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "programId",
+            type: "publicKey",
+          },
+          {
+            name: "keys",
+            type: {
+              vec: {
+                defined: "TXAccountMeta",
+              },
+            },
+          },
+          {
+            name: "data",
+            type: "bytes",
+          },
+        ],
+      },
+    },
+    {
+      name: "TXAccountMeta",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "pubkey",
+            type: "publicKey",
+          },
+          {
+            name: "isSigner",
+            type: "bool",
+          },
+          {
+            name: "isWritable",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "TransactionOptions",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "pdaSigners",
+            type: {
+              vec: {
+                defined: "PdaSigner",
+              },
+            },
+          },
+          {
+            name: "tier",
+            type: {
+              option: "u8",
+            },
+          },
+          {
+            name: "optimistic",
+            type: "bool",
+          },
+          {
+            name: "prerequisites",
+            type: {
+              vec: "u64",
+            },
+          },
+          {
+            name: "preConditions",
+            type: {
+              vec: {
+                defined: "PreCondition",
+              },
+            },
+          },
+          {
+            name: "postConditions",
+            type: {
+              vec: {
+                defined: "PostCondition",
+              },
+            },
+          },
+          {
+            name: "addressBook",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "OptimisticConfig",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "proposers",
+            type: {
+              vec: "publicKey",
+            },
+          },
+          {
+            name: "objectionThreshold",
+            type: "u64",
+          },
+          {
+            name: "challengePeriod",
+            type: "i64",
+          },
+          {
+            name: "allowedInstructions",
+            type: {
+              vec: {
+                defined: "AllowedInstruction",
+              },
+            },
+          },
+        ],
+      },
     },
     {
-      code: 6006;
-      name: "AlreadyExecuted";
-      msg: "The given transaction has already been executed.";
+      name: "TimelockTier",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "name",
+            type: "string",
+          },
+          {
+            name: "minimumDelay",
+            type: "i64",
+          },
+          {
+            name: "threshold",
+            type: "u64",
+          },
+          {
+            name: "allowedInstructions",
+            type: {
+              vec: {
+                defined: "AllowedInstruction",
+              },
+            },
+          },
+        ],
+      },
     },
     {
-      code: 6007;
-      name: "InvalidThreshold";
-      msg: "Threshold must be less than or equal to the number of owners.";
+      name: "ProgramUpgrade",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "program",
+            type: "publicKey",
+          },
+          {
+            name: "buffer",
+            type: "publicKey",
+          },
+          {
+            name: "bufferHash",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+        ],
+      },
     },
     {
-      code: 6008;
-      name: "OwnerSetChanged";
-      msg: "Owner set has changed since the creation of the transaction.";
+      name: "VaultTransfer",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "asset",
+            type: {
+              defined: "TransferAsset",
+            },
+          },
+          {
+            name: "amount",
+            type: "u64",
+          },
+          {
+            name: "sourceIndex",
+            type: "u64",
+          },
+          {
+            name: "recipient",
+            type: "publicKey",
+          },
+        ],
+      },
     },
     {
-      code: 6009;
-      name: "SubaccountOwnerMismatch";
-      msg: "Subaccount does not belong to smallet.";
+      name: "SubaccountInfoV0",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "smallet",
+            type: "publicKey",
+          },
+          {
+            name: "subaccountType",
+            type: {
+              defined: "SubaccountType",
+            },
+          },
+          {
+            name: "index",
+            type: "u64",
+          },
+        ],
+      },
     },
     {
-      code: 6010;
-      name: "BufferFinalized";
-      msg: "Buffer already finalized.";
+      name: "AllowedInstruction",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "programId",
+            type: "publicKey",
+          },
+          {
+            name: "discriminator",
+            type: "bytes",
+          },
+        ],
+      },
     },
     {
-      code: 6011;
-      name: "BufferBundleNotFound";
-      msg: "Buffer bundle not found.";
+      name: "AddressBookEntry",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "recipient",
+            type: "publicKey",
+          },
+          {
+            name: "label",
+            type: "string",
+          },
+          {
+            name: "trustedAt",
+            type: "i64",
+          },
+        ],
+      },
     },
     {
-      code: 6012;
-      name: "BufferBundleOutOfRange";
-      msg: "Buffer index specified is out of range.";
+      name: "PreCondition",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "account",
+            type: "publicKey",
+          },
+          {
+            name: "check",
+            type: {
+              defined: "AccountCheck",
+            },
+          },
+        ],
+      },
     },
     {
-      code: 6013;
-      name: "BufferBundleNotFinalized";
-      msg: "Buffer has not been finalized.";
+      name: "PostCondition",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "account",
+            type: "publicKey",
+          },
+          {
+            name: "limit",
+            type: {
+              defined: "OutflowLimit",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "PdaSigner",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Smallet",
+          },
+          {
+            name: "Derived",
+            fields: [
+              {
+                name: "index",
+                type: "u64",
+              },
+              {
+                name: "bump",
+                type: "u8",
+              },
+            ],
+          },
+          {
+            name: "OwnerInvoker",
+            fields: [
+              {
+                name: "index",
+                type: "u64",
+              },
+              {
+                name: "bump",
+                type: "u8",
+              },
+            ],
+          },
+        ],
+      },
+    },
+    {
+      name: "TransferAsset",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Sol",
+          },
+          {
+            name: "Token",
+            fields: [
+              {
+                name: "mint",
+                type: "publicKey",
+              },
+            ],
+          },
+        ],
+      },
+    },
+    {
+      name: "SubaccountType",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Derived",
+          },
+          {
+            name: "OwnerInvoker",
+          },
+        ],
+      },
+    },
+    {
+      name: "AccountCheck",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "DataHash",
+            fields: [
+              {
+                name: "hash",
+                type: {
+                  array: ["u8", 32],
+                },
+              },
+            ],
+          },
+          {
+            name: "MinLamports",
+            fields: [
+              {
+                name: "lamports",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "TokenAmount",
+            fields: [
+              {
+                name: "min",
+                type: "u64",
+              },
+              {
+                name: "max",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "Owner",
+            fields: [
+              {
+                name: "program_id",
+                type: "publicKey",
+              },
+            ],
+          },
+        ],
+      },
     },
     {
-      code: 6014;
-      name: "BufferBundleExecuted";
-      msg: "Buffer bundle has already been executed.";
-    }
-  ];
-};
-export const SmalletJSON: SmalletIDL = {
-  version: "0.11.1",
-  name: "smallet",
-  instructions: [
-    {
-      name: "createSmallet",
-      accounts: [
-        {
-          name: "base",
-          isMut: false,
-          isSigner: true,
-        },
-        {
-          name: "smallet",
-          isMut: true,
-          isSigner: false,
-          pda: {
-            seeds: [
+      name: "OutflowLimit",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Lamports",
+            fields: [
               {
-                kind: "const",
-                type: "string",
-                value: "CosmicSmallet",
+                name: "max_outflow",
+                type: "u64",
               },
+            ],
+          },
+          {
+            name: "Tokens",
+            fields: [
               {
-                kind: "account",
-                type: "publicKey",
-                path: "base",
+                name: "max_outflow",
+                type: "u64",
               },
             ],
           },
-        },
-        {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
-      ],
-      args: [
-        {
-          name: "bump",
-          type: "u8",
-        },
+          {
+            name: "MinLamports",
+            fields: [
+              {
+                name: "min_balance",
+                type: "u64",
+              },
+            ],
+          },
+          {
+            name: "MinTokens",
+            fields: [
+              {
+                name: "min_balance",
+                type: "u64",
+              },
+            ],
+          },
+        ],
+      },
+    },
+  ],
+  events: [
+    {
+      name: "WalletCreateEvent",
+      fields: [
         {
-          name: "maxOwners",
-          type: "u8",
+          name: "smallet",
+          type: "publicKey",
+          index: true,
         },
         {
           name: "owners",
           type: {
             vec: "publicKey",
           },
+          index: false,
         },
         {
           name: "threshold",
           type: "u64",
+          index: false,
         },
         {
           name: "minimumDelay",
           type: "i64",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "setOwners",
-      accounts: [
+      name: "WalletSetOwnersEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: true,
-          isSigner: true,
+          type: "publicKey",
+          index: true,
         },
-      ],
-      args: [
         {
           name: "owners",
           type: {
             vec: "publicKey",
           },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "changeThreshold",
-      accounts: [
+      name: "WalletChangeThresholdEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: true,
-          isSigner: true,
+          type: "publicKey",
+          index: true,
         },
-      ],
-      args: [
         {
           name: "threshold",
           type: "u64",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "createTransaction",
-      accounts: [
+      name: "WalletChangeVetoThresholdEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "transaction",
-          isMut: true,
-          isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "CosmicTransaction",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Smallet",
-                path: "smallet",
-              },
-              {
-                kind: "account",
-                type: "u64",
-                account: "Smallet",
-                path: "smallet.num_transactions",
-              },
-            ],
-          },
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "proposer",
-          isMut: false,
-          isSigner: true,
+          name: "vetoThreshold",
+          type: "u64",
+          index: false,
         },
         {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
+      ],
+    },
+    {
+      name: "WalletChangeApprovalTtlEvent",
+      fields: [
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "smallet",
+          type: "publicKey",
+          index: true,
         },
-      ],
-      args: [
         {
-          name: "bump",
-          type: "u8",
+          name: "approvalTtl",
+          type: "i64",
+          index: false,
         },
         {
-          name: "instructions",
-          type: {
-            vec: {
-              defined: "TXInstruction",
-            },
-          },
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "createTransactionWithTimelock",
-      accounts: [
+      name: "FifoCursorSetEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: true,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "transaction",
-          isMut: true,
-          isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "CosmicTransaction",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Smallet",
-                path: "smallet",
-              },
-              {
-                kind: "account",
-                type: "u64",
-                account: "Smallet",
-                path: "smallet.num_transactions",
-              },
-            ],
+          name: "cursor",
+          type: {
+            option: "u64",
           },
+          index: false,
         },
         {
-          name: "proposer",
-          isMut: false,
-          isSigner: true,
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
+      ],
+    },
+    {
+      name: "TransactionCreateEvent",
+      fields: [
         {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
+          name: "smallet",
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "transaction",
+          type: "publicKey",
+          index: true,
         },
-      ],
-      args: [
         {
-          name: "bump",
-          type: "u8",
+          name: "proposer",
+          type: "publicKey",
+          index: false,
         },
         {
           name: "instructions",
@@ -1135,426 +6151,435 @@ export const SmalletJSON: SmalletIDL = {
               defined: "TXInstruction",
             },
           },
+          index: false,
         },
         {
           name: "eta",
           type: "i64",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "approve",
-      accounts: [
+      name: "TransactionApproveEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
           name: "transaction",
-          isMut: true,
-          isSigner: false,
-          relations: ["smallet"],
+          type: "publicKey",
+          index: true,
         },
         {
           name: "owner",
-          isMut: false,
-          isSigner: true,
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [],
     },
     {
-      name: "unapprove",
-      accounts: [
+      name: "TransactionUnapproveEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
           name: "transaction",
-          isMut: true,
-          isSigner: false,
-          relations: ["smallet"],
+          type: "publicKey",
+          index: true,
         },
         {
           name: "owner",
-          isMut: false,
-          isSigner: true,
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [],
     },
     {
-      name: "executeTransaction",
-      accounts: [
+      name: "TransactionVetoEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
           name: "transaction",
-          isMut: true,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
           name: "owner",
-          isMut: false,
-          isSigner: true,
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "blocked",
+          type: "bool",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [],
     },
     {
-      name: "executeTransactionDerived",
-      accounts: [
+      name: "TransactionExecuteEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
           name: "transaction",
-          isMut: true,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "owner",
-          isMut: false,
-          isSigner: true,
+          name: "executor",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [
+    },
+    {
+      name: "TransactionIndexAssignEvent",
+      fields: [
+        {
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "transaction",
+          type: "publicKey",
+          index: true,
+        },
         {
           name: "index",
           type: "u64",
+          index: false,
         },
         {
-          name: "bump",
-          type: "u8",
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "ownerInvokeInstruction",
-      accounts: [
+      name: "SmalletMigrateEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "owner",
-          isMut: false,
-          isSigner: true,
+          name: "version",
+          type: "u8",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [
+    },
+    {
+      name: "TransactionMigrateEvent",
+      fields: [
         {
-          name: "index",
-          type: "u64",
+          name: "smallet",
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "bump",
+          name: "transaction",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "version",
           type: "u8",
+          index: false,
         },
         {
-          name: "ix",
-          type: {
-            defined: "TXInstruction",
-          },
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "ownerInvokeInstructionV2",
-      accounts: [
+      name: "ProgramUpgradeProposeEvent",
+      fields: [
         {
           name: "smallet",
-          isMut: false,
-          isSigner: false,
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "owner",
-          isMut: false,
-          isSigner: true,
+          name: "transaction",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "program",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "buffer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "bufferHash",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
-      args: [
+    },
+    {
+      name: "TransferProposeEvent",
+      fields: [
         {
-          name: "index",
-          type: "u64",
+          name: "smallet",
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "bump",
-          type: "u8",
+          name: "transaction",
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "invoker",
+          name: "proposer",
           type: "publicKey",
+          index: false,
         },
         {
-          name: "data",
-          type: "bytes",
+          name: "transfer",
+          type: {
+            defined: "VaultTransfer",
+          },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
     {
-      name: "createSubaccountInfo",
-      accounts: [
+      name: "TransferExecuteEvent",
+      fields: [
         {
-          name: "subaccountInfo",
-          isMut: true,
-          isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "CosmicSubaccountInfo",
-              },
-              {
-                kind: "arg",
-                type: "publicKey",
-                path: "subaccount",
-              },
-            ],
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "transaction",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "asset",
+          type: {
+            defined: "TransferAsset",
           },
+          index: false,
         },
         {
-          name: "payer",
-          isMut: true,
-          isSigner: true,
+          name: "amount",
+          type: "u64",
+          index: false,
         },
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "source",
+          type: "publicKey",
+          index: false,
         },
-      ],
-      args: [
         {
-          name: "bump",
-          type: "u8",
+          name: "destination",
+          type: "publicKey",
+          index: false,
         },
         {
-          name: "subaccount",
-          type: "publicKey",
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
+      ],
+    },
+    {
+      name: "TransactionCommitEvent",
+      fields: [
         {
           name: "smallet",
           type: "publicKey",
+          index: true,
         },
         {
-          name: "index",
-          type: "u64",
+          name: "transaction",
+          type: "publicKey",
+          index: true,
         },
         {
-          name: "subaccountType",
+          name: "instructionsHash",
           type: {
-            defined: "SubaccountType",
+            array: ["u8", 32],
           },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
         },
       ],
     },
-  ],
-  accounts: [
-    {
-      name: "Smallet",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "base",
-            type: "publicKey",
-          },
-          {
-            name: "bump",
-            type: "u8",
-          },
-          {
-            name: "threshold",
-            type: "u64",
-          },
-          {
-            name: "minimumDelay",
-            type: "i64",
-          },
-          {
-            name: "gracePeriod",
-            type: "i64",
-          },
-          {
-            name: "ownerSetSeqno",
-            type: "u32",
-          },
-          {
-            name: "numTransactions",
-            type: "u64",
-          },
-          {
-            name: "owners",
-            type: {
-              vec: "publicKey",
-            },
-          },
-          {
-            name: "reserved",
-            type: {
-              array: ["u64", 16],
-            },
-          },
-        ],
-      },
-    },
-    {
-      name: "Transaction",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "smallet",
-            type: "publicKey",
-          },
-          {
-            name: "index",
-            type: "u64",
-          },
-          {
-            name: "bump",
-            type: "u8",
-          },
-          {
-            name: "proposer",
-            type: "publicKey",
-          },
-          {
-            name: "instructions",
-            type: {
-              vec: {
-                defined: "TXInstruction",
-              },
-            },
-          },
-          {
-            name: "signers",
-            type: {
-              vec: "bool",
-            },
-          },
-          {
-            name: "ownerSetSeqno",
-            type: "u32",
-          },
-          {
-            name: "eta",
-            type: "i64",
-          },
-          {
-            name: "executor",
-            type: "publicKey",
-          },
-          {
-            name: "executedAt",
-            type: "i64",
-          },
-        ],
-      },
-    },
     {
-      name: "SubaccountInfo",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "smallet",
-            type: "publicKey",
+      name: "TransactionRevealEvent",
+      fields: [
+        {
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "transaction",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "salt",
+          type: {
+            array: ["u8", 32],
           },
-          {
-            name: "subaccountType",
-            type: {
-              defined: "SubaccountType",
+          index: false,
+        },
+        {
+          name: "instructions",
+          type: {
+            vec: {
+              defined: "TXInstruction",
             },
           },
-          {
-            name: "index",
-            type: "u64",
-          },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
+        },
+      ],
     },
-  ],
-  types: [
     {
-      name: "TXInstruction",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "programId",
-            type: "publicKey",
-          },
-          {
-            name: "keys",
-            type: {
-              vec: {
-                defined: "TXAccountMeta",
-              },
+      name: "TimelockTiersSetEvent",
+      fields: [
+        {
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "tiers",
+          type: {
+            vec: {
+              defined: "TimelockTier",
             },
           },
-          {
-            name: "data",
-            type: "bytes",
-          },
-        ],
-      },
-    },
-    {
-      name: "TXAccountMeta",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "pubkey",
-            type: "publicKey",
-          },
-          {
-            name: "isSigner",
-            type: "bool",
-          },
-          {
-            name: "isWritable",
-            type: "bool",
-          },
-        ],
-      },
-    },
-    {
-      name: "SubaccountType",
-      type: {
-        kind: "enum",
-        variants: [
-          {
-            name: "Derived",
-          },
-          {
-            name: "OwnerInvoker",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "OptimisticModeSetEvent",
+      fields: [
+        {
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "config",
+          type: {
+            option: {
+              defined: "OptimisticConfig",
+            },
           },
-        ],
-      },
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
+        },
+      ],
     },
-  ],
-  events: [
     {
-      name: "WalletCreateEvent",
+      name: "InvokerPolicySetEvent",
       fields: [
         {
           name: "smallet",
           type: "publicKey",
           index: true,
         },
+        {
+          name: "policy",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "index",
+          type: "u64",
+          index: false,
+        },
         {
           name: "owners",
           type: {
@@ -1563,12 +6588,21 @@ export const SmalletJSON: SmalletIDL = {
           index: false,
         },
         {
-          name: "threshold",
+          name: "allowedInstructions",
+          type: {
+            vec: {
+              defined: "AllowedInstruction",
+            },
+          },
+          index: false,
+        },
+        {
+          name: "maxCallsPerPeriod",
           type: "u64",
           index: false,
         },
         {
-          name: "minimumDelay",
+          name: "period",
           type: "i64",
           index: false,
         },
@@ -1580,7 +6614,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "WalletSetOwnersEvent",
+      name: "OwnerInvokeEvent",
       fields: [
         {
           name: "smallet",
@@ -1588,10 +6622,23 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "owners",
-          type: {
-            vec: "publicKey",
-          },
+          name: "invoker",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "index",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "owner",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "programId",
+          type: "publicKey",
           index: false,
         },
         {
@@ -1602,7 +6649,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "WalletChangeThresholdEvent",
+      name: "SubaccountCreateEvent",
       fields: [
         {
           name: "smallet",
@@ -1610,10 +6657,34 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "threshold",
+          name: "subaccount",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "subaccountType",
+          type: {
+            defined: "SubaccountType",
+          },
+          index: false,
+        },
+        {
+          name: "index",
           type: "u64",
           index: false,
         },
+        {
+          name: "registryIndex",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "label",
+          type: {
+            option: "string",
+          },
+          index: false,
+        },
         {
           name: "timestamp",
           type: "i64",
@@ -1622,7 +6693,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "TransactionCreateEvent",
+      name: "SubaccountUpdateEvent",
       fields: [
         {
           name: "smallet",
@@ -1630,29 +6701,42 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "transaction",
+          name: "subaccountInfo",
           type: "publicKey",
           index: true,
         },
         {
-          name: "proposer",
-          type: "publicKey",
-          index: false,
-        },
-        {
-          name: "instructions",
+          name: "label",
           type: {
-            vec: {
-              defined: "TXInstruction",
-            },
+            option: "string",
           },
           index: false,
         },
         {
-          name: "eta",
+          name: "timestamp",
           type: "i64",
           index: false,
         },
+      ],
+    },
+    {
+      name: "SubaccountCloseEvent",
+      fields: [
+        {
+          name: "smallet",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "subaccount",
+          type: "publicKey",
+          index: true,
+        },
+        {
+          name: "registryIndex",
+          type: "u64",
+          index: false,
+        },
         {
           name: "timestamp",
           type: "i64",
@@ -1661,7 +6745,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "TransactionApproveEvent",
+      name: "SubaccountMigrateEvent",
       fields: [
         {
           name: "smallet",
@@ -1669,13 +6753,13 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "transaction",
+          name: "subaccount",
           type: "publicKey",
           index: true,
         },
         {
-          name: "owner",
-          type: "publicKey",
+          name: "registryIndex",
+          type: "u64",
           index: false,
         },
         {
@@ -1686,7 +6770,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "TransactionUnapproveEvent",
+      name: "AddressBookPolicySetEvent",
       fields: [
         {
           name: "smallet",
@@ -1694,15 +6778,60 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "transaction",
+          name: "addressBook",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "threshold",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "minimumDelay",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "entryDelay",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "timestamp",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "AddressBookEntryAddEvent",
+      fields: [
+        {
+          name: "smallet",
           type: "publicKey",
           index: true,
         },
         {
-          name: "owner",
+          name: "addressBook",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "recipient",
           type: "publicKey",
           index: false,
         },
+        {
+          name: "label",
+          type: "string",
+          index: false,
+        },
+        {
+          name: "trustedAt",
+          type: "i64",
+          index: false,
+        },
         {
           name: "timestamp",
           type: "i64",
@@ -1711,7 +6840,7 @@ export const SmalletJSON: SmalletIDL = {
       ],
     },
     {
-      name: "TransactionExecuteEvent",
+      name: "AddressBookEntryRemoveEvent",
       fields: [
         {
           name: "smallet",
@@ -1719,12 +6848,12 @@ export const SmalletJSON: SmalletIDL = {
           index: true,
         },
         {
-          name: "transaction",
+          name: "addressBook",
           type: "publicKey",
-          index: true,
+          index: false,
         },
         {
-          name: "executor",
+          name: "recipient",
           type: "publicKey",
           index: false,
         },
@@ -1812,6 +6941,211 @@ export const SmalletJSON: SmalletIDL = {
       name: "BufferBundleExecuted",
       msg: "Buffer bundle has already been executed.",
     },
+    {
+      code: 6015,
+      name: "TransactionIndexAlreadyAssigned",
+      msg: "Transaction has already been assigned an index.",
+    },
+    {
+      code: 6016,
+      name: "AccountNotMigrated",
+      msg: "Account must be migrated to the current layout.",
+    },
+    {
+      code: 6017,
+      name: "AccountAlreadyMigrated",
+      msg: "Account has already been migrated.",
+    },
+    {
+      code: 6018,
+      name: "InvalidProgramBuffer",
+      msg: "Program buffer is not a valid upgradeable loader buffer.",
+    },
+    {
+      code: 6019,
+      name: "MissingProgramBuffer",
+      msg: "Program buffer account was not provided.",
+    },
+    {
+      code: 6020,
+      name: "BufferHashMismatch",
+      msg: "Program buffer has changed since the upgrade was proposed.",
+    },
+    {
+      code: 6021,
+      name: "InvalidSignerBump",
+      msg: "Bump seed of a PDA signer is not canonical.",
+    },
+    {
+      code: 6022,
+      name: "DerivedSignerNotDeclared",
+      msg: "Derived address was not declared as a signer of the transaction.",
+    },
+    {
+      code: 6023,
+      name: "InvokerNotAllowed",
+      msg: "Owner is not allowed to use this owner invoker.",
+    },
+    {
+      code: 6024,
+      name: "InstructionNotAllowed",
+      msg: "Instruction is not allowed by the invoker policy.",
+    },
+    {
+      code: 6025,
+      name: "InvokerCallLimitExceeded",
+      msg: "Owner invoker has reached its call limit for the period.",
+    },
+    {
+      code: 6026,
+      name: "InvalidInvoker",
+      msg: "Invoker is not the owner invoker derived from the given index and bump.",
+    },
+    {
+      code: 6027,
+      name: "LabelTooLong",
+      msg: "Label is too long.",
+    },
+    {
+      code: 6028,
+      name: "InstructionsNotRevealed",
+      msg: "Instructions of a committed transaction must be revealed to execute it.",
+    },
+    {
+      code: 6029,
+      name: "TransactionNotCommitted",
+      msg: "Transaction did not commit to the hash of its instructions.",
+    },
+    {
+      code: 6030,
+      name: "InstructionsHashMismatch",
+      msg: "Revealed instructions do not match the committed hash.",
+    },
+    {
+      code: 6031,
+      name: "InvalidTier",
+      msg: "Timelock tier does not exist on this smallet.",
+    },
+    {
+      code: 6032,
+      name: "InstructionNotInTier",
+      msg: "Instruction is not allowed in the timelock tier of the transaction.",
+    },
+    {
+      code: 6033,
+      name: "VetoDisabled",
+      msg: "Vetoes are disabled on this smallet.",
+    },
+    {
+      code: 6034,
+      name: "VetoWindowClosed",
+      msg: "Transaction can only be vetoed before its ETA.",
+    },
+    {
+      code: 6035,
+      name: "TransactionVetoed",
+      msg: "Transaction was vetoed.",
+    },
+    {
+      code: 6036,
+      name: "OptimisticModeDisabled",
+      msg: "Optimistic mode is disabled on this smallet.",
+    },
+    {
+      code: 6037,
+      name: "UnauthorizedProposer",
+      msg: "Proposer is not authorized to propose optimistically.",
+    },
+    {
+      code: 6038,
+      name: "InvalidPrerequisite",
+      msg: "Prerequisite must be the index of an earlier transaction.",
+    },
+    {
+      code: 6039,
+      name: "PrerequisiteNotExecuted",
+      msg: "Prerequisite transaction is missing or not executed.",
+    },
+    {
+      code: 6040,
+      name: "TransactionNotIndexed",
+      msg: "Transaction must be assigned an index to be executed in order.",
+    },
+    {
+      code: 6041,
+      name: "EarlierTransactionPending",
+      msg: "An earlier transaction is missing or still pending.",
+    },
+    {
+      code: 6042,
+      name: "InvalidPreCondition",
+      msg: "Pre-condition can never hold.",
+    },
+    {
+      code: 6043,
+      name: "MissingPreConditionAccount",
+      msg: "Account of a pre-condition was not passed.",
+    },
+    {
+      code: 6044,
+      name: "PreConditionFailed",
+      msg: "Pre-condition does not hold.",
+    },
+    {
+      code: 6045,
+      name: "NotATokenAccount",
+      msg: "Account is not an SPL Token account.",
+    },
+    {
+      code: 6046,
+      name: "MissingPostConditionAccount",
+      msg: "Account of a post-condition was not passed.",
+    },
+    {
+      code: 6047,
+      name: "PostConditionFailed",
+      msg: "Post-condition does not hold after execution.",
+    },
+    {
+      code: 6048,
+      name: "InvalidTransferAmount",
+      msg: "Transfer amount must be positive.",
+    },
+    {
+      code: 6049,
+      name: "MissingAddressBook",
+      msg: "Address book of the smallet was not passed.",
+    },
+    {
+      code: 6050,
+      name: "RecipientNotTrusted",
+      msg: "Instruction does not pay a trusted address book entry.",
+    },
+    {
+      code: 6051,
+      name: "AddressBookEntryExists",
+      msg: "Recipient is already in the address book.",
+    },
+    {
+      code: 6052,
+      name: "AddressBookEntryNotFound",
+      msg: "Recipient is not in the address book.",
+    },
+    {
+      code: 6053,
+      name: "MissingInvokedProgram",
+      msg: "Program to invoke was not passed.",
+    },
+    {
+      code: 6054,
+      name: "TierRequired",
+      msg: "Smallets with timelock tiers only accept proposals via create_transaction_v2.",
+    },
+    {
+      code: 6055,
+      name: "InstructionNotOptimistic",
+      msg: "Instruction may not be proposed optimistically.",
+    },
   ],
 };
 export const SmalletErrors = generateErrorMap(SmalletJSON);
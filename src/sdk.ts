@@ -17,6 +17,7 @@ import {
   findOwnerInvokerAddress,
   findSmallet,
   findSubaccountInfoAddress,
+  findSubaccountRegistryAddress,
   findWalletDerivedAddress,
   SmalletWrapper,
 } from "./wrappers/smallet";
@@ -42,17 +43,24 @@ export class COSMICSDK {
   }
 
   /**
-   * Creates a subaccount info.
+   * Creates a subaccount info, registering the subaccount on its smallet.
    */
   async createSubaccountInfo({
     smallet,
     index,
     type,
+    label = null,
+    owner = this.provider.wallet.publicKey,
     payer = this.provider.wallet.publicKey,
   }: {
     smallet: PublicKey;
     index: number;
     type: "derived" | "ownerInvoker";
+    label?: string | null;
+    /**
+     * An owner of the smallet.
+     */
+    owner?: PublicKey;
     payer?: PublicKey;
   }) {
     const [subaccount] =
@@ -60,6 +68,12 @@ export class COSMICSDK {
         ? await findWalletDerivedAddress(smallet, index)
         : await findOwnerInvokerAddress(smallet, index);
     const [subaccountInfo, bump] = await findSubaccountInfoAddress(subaccount);
+    const { numSubaccounts } =
+      await this.programs.Smallet.account.Smallet.fetch(smallet);
+    const [registryEntry] = await findSubaccountRegistryAddress(
+      smallet,
+      numSubaccounts.toNumber()
+    );
     return this.provider.newTX([
       this.programs.Smallet.instruction.createSubaccountInfo(
         bump,
@@ -69,9 +83,13 @@ export class COSMICSDK {
         {
           [type]: {},
         },
+        label,
         {
           accounts: {
             subaccountInfo,
+            smallet,
+            registryEntry,
+            owner,
             payer,
             systemProgram: SystemProgram.programId,
           },
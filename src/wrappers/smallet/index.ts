@@ -13,12 +13,17 @@ import type { COSMICSDK } from "../../sdk";
 import {
   findOwnerInvokerAddress,
   findTransactionAddress,
+  findTransactionV2Address,
   findWalletDerivedAddress,
+  getAddressBookAddress,
+  getInvokerPolicyAddress,
 } from "./pda";
 import type {
   InitSmalletWrapperArgs,
   NewTransactionArgs,
+  NewTransactionV2Args,
   PendingSmalletTransaction,
+  PendingSmalletTransactionV2,
 } from "./types";
 
 export * from "./pda";
@@ -95,6 +100,47 @@ export class SmalletWrapper {
       index,
     };
   }
+  /**Proposes a new transaction signed by the given PDAs, without reserving an index */
+
+  async createTransactionV2({
+    proposer = this.provider.wallet.publicKey,
+    payer = this.provider.wallet.publicKey,
+    instructions: ixs,
+    eta = new BN(-1),
+    nonce = new BN(Date.now()),
+    pdaSigners = [],
+  }: NewTransactionV2Args): Promise<PendingSmalletTransactionV2> {
+    const [txKey] = await findTransactionV2Address(this.key, proposer, nonce);
+    const ix = this.program.instruction.createTransactionV2(
+      nonce,
+      ixs,
+      eta,
+      {
+        pdaSigners,
+        tier: null,
+        optimistic: false,
+        prerequisites: [],
+        preConditions: [],
+        postConditions: [],
+        addressBook: false,
+      },
+      {
+        accounts: {
+          smallet: this.key,
+          transaction: txKey,
+          proposer,
+          payer,
+          systemProgram: SystemProgram.programId,
+        },
+      }
+    );
+    return {
+      transactionKey: txKey,
+      tx: new TransactionEnvelope(this.provider, [ix]),
+      nonce,
+    };
+  }
+
   /**Creates a new transaction from an envelope */
 
   async newTransactionFromEnvelope({
@@ -244,6 +290,7 @@ export class SmalletWrapper {
         accounts: {
           smallet: this.key,
           owner,
+          policy: getInvokerPolicyAddress(this.key, index),
         },
         remainingAccounts: [
           {
@@ -288,6 +335,7 @@ export class SmalletWrapper {
         accounts: {
           smallet: this.key,
           owner,
+          policy: getInvokerPolicyAddress(this.key, index),
         },
         remainingAccounts: [
           {
@@ -315,6 +363,14 @@ export class SmalletWrapper {
       accounts: {
         smallet: this.key,
       },
+      // The address book threshold is lowered with the owners.
+      remainingAccounts: [
+        {
+          pubkey: getAddressBookAddress(this.key),
+          isSigner: false,
+          isWritable: true,
+        },
+      ],
     });
     return new TransactionEnvelope(this.provider, [ix]);
   }
@@ -327,6 +383,49 @@ export class SmalletWrapper {
     });
     return new TransactionEnvelope(this.provider, [ix]);
   }
+  /**
+   * Creates the policy of the Owner Invoker at `index`. Must be proposed and
+   * executed as a transaction of the smallet.
+   */
+  createInvokerPolicy({
+    index,
+    owners,
+    allowedInstructions,
+    maxCallsPerPeriod = 0,
+    period = 0,
+    payer = this.provider.wallet.publicKey,
+  }: {
+    index: number;
+    owners: PublicKey[];
+    allowedInstructions: { programId: PublicKey; discriminator: Buffer }[];
+    /**
+     * Maximum number of calls per period. If 0, calls are not limited.
+     */
+    maxCallsPerPeriod?: number;
+    /**
+     * Length of a rate limiting period, in seconds.
+     */
+    period?: number;
+    payer?: PublicKey;
+  }): TransactionEnvelope {
+    const ix = this.program.instruction.createInvokerPolicy(
+      new BN(index),
+      owners,
+      allowedInstructions,
+      new BN(maxCallsPerPeriod),
+      new BN(period),
+      {
+        accounts: {
+          smallet: this.key,
+          policy: getInvokerPolicyAddress(this.key, index),
+          payer,
+          systemProgram: SystemProgram.programId,
+        },
+      }
+    );
+    return new TransactionEnvelope(this.provider, [ix]);
+  }
+
   /**Loads a smallet */
 
   static async load(sdk: COSMICSDK, key: PublicKey): Promise<SmalletWrapper> {
@@ -2,6 +2,7 @@ import { utils } from "@project-serum/anchor";
 import { getProgramAddress } from "@saberhq/solana-contrib";
 import { u64 } from "@saberhq/token-utils";
 import { PublicKey } from "@solana/web3.js";
+import type BN from "bn.js";
 
 import { COSMIC_ADDRESSES } from "../../constants";
 
@@ -28,6 +29,26 @@ export const findTransactionAddress = async (
   );
 };
 
+/**
+ * Finds the address of a transaction proposed via `createTransactionV2`,
+ * seeded by its proposer and their chosen nonce.
+ */
+export const findTransactionV2Address = async (
+  smallet: PublicKey,
+  proposer: PublicKey,
+  nonce: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("CosmicTransactionV2"),
+      smallet.toBuffer(),
+      proposer.toBuffer(),
+      new u64(nonce).toBuffer(),
+    ],
+    COSMIC_ADDRESSES.Smallet
+  );
+};
+
 /**
  * Finds a derived address of a Smart Wallet.
  */
@@ -74,6 +95,40 @@ export const findSubaccountInfoAddress = async (
   );
 };
 
+/**
+ * Finds the invoker policy of an Owner Invoker of a Smart Wallet.
+ */
+export const findInvokerPolicyAddress = async (
+  smallet: PublicKey,
+  index: number
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("CosmicInvokerPolicy"),
+      smallet.toBuffer(),
+      new u64(index).toBuffer(),
+    ],
+    COSMIC_ADDRESSES.Smallet
+  );
+};
+
+/**
+ * Finds the subaccount registry entry of a smart wallet at a registry index.
+ */
+export const findSubaccountRegistryAddress = async (
+  smallet: PublicKey,
+  registryIndex: number
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("CosmicSubaccountRegistry"),
+      smallet.toBuffer(),
+      new u64(registryIndex).toBuffer(),
+    ],
+    COSMIC_ADDRESSES.Smallet
+  );
+};
+
 export const getSmalletAddress = (base: PublicKey): PublicKey => {
   return getProgramAddress(
     [utils.bytes.utf8.encode("CosmicSmallet"), base.toBuffer()],
@@ -138,3 +193,30 @@ export const getSubaccountInfoAddress = (subaccount: PublicKey): PublicKey => {
     COSMIC_ADDRESSES.Smallet
   );
 };
+
+/**
+ * Finds the address book of a smart wallet.
+ */
+export const getAddressBookAddress = (smallet: PublicKey): PublicKey => {
+  return getProgramAddress(
+    [utils.bytes.utf8.encode("CosmicAddressBook"), smallet.toBuffer()],
+    COSMIC_ADDRESSES.Smallet
+  );
+};
+
+/**
+ * Finds the invoker policy of an Owner Invoker of a Smart Wallet.
+ */
+export const getInvokerPolicyAddress = (
+  smallet: PublicKey,
+  index: number
+): PublicKey => {
+  return getProgramAddress(
+    [
+      utils.bytes.utf8.encode("CosmicInvokerPolicy"),
+      smallet.toBuffer(),
+      new u64(index).toBuffer(),
+    ],
+    COSMIC_ADDRESSES.Smallet
+  );
+};
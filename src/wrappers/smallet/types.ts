@@ -47,3 +47,37 @@ export interface NewTransactionArgs {
    */
   readonly eta?: BN;
 }
+
+/**
+ * A PDA signing the instructions of a [Transaction].
+ */
+export type PdaSigner =
+  | { smallet: Record<string, never> }
+  | { derived: { index: BN; bump: number } }
+  | { ownerInvoker: { index: BN; bump: number } };
+
+export interface NewTransactionV2Args extends NewTransactionArgs {
+  /**
+   * Nonce of the proposer seeding the [Transaction] address
+   */
+  readonly nonce?: BN;
+  /**
+   * PDAs signing the instructions. If empty, only the smallet signs.
+   */
+  readonly pdaSigners?: PdaSigner[];
+}
+
+export type PendingSmalletTransactionV2 = {
+  /**
+   * Pubkey of the created [Transaction]
+   */
+  readonly transactionKey: PublicKey;
+  /**
+   * Transaction to create the [Transaction]
+   */
+  readonly tx: TransactionEnvelope;
+  /**
+   * Nonce seeding the [Transaction] address
+   */
+  readonly nonce: BN;
+};
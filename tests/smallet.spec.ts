@@ -22,6 +22,8 @@ import {
   findSubaccountInfoAddress,
   findTransactionAddress,
   findWalletDerivedAddress,
+  getAddressBookAddress,
+  getInvokerPolicyAddress,
 } from "../src/wrappers/smallet";
 import { makeSDK } from "./workspace";
 
@@ -87,6 +89,12 @@ describe("smallet", () => {
             isWritable: true,
             isSigner: true,
           },
+          // The address book threshold is lowered with the owners
+          {
+            pubkey: getAddressBookAddress(smalletKey),
+            isWritable: true,
+            isSigner: false,
+          },
         ],
         data,
       });
@@ -290,6 +298,12 @@ describe("smallet", () => {
             isWritable: true,
             isSigner: true,
           },
+          // The address book threshold is lowered with the owners
+          {
+            pubkey: getAddressBookAddress(smalletKey),
+            isWritable: true,
+            isSigner: false,
+          },
         ],
         data,
       });
@@ -329,6 +343,12 @@ describe("smallet", () => {
             isWritable: true,
             isSigner: true,
           },
+          // The address book threshold is lowered with the owners
+          {
+            pubkey: getAddressBookAddress(smalletKey),
+            isWritable: true,
+            isSigner: false,
+          },
         ],
         data,
       });
@@ -408,7 +428,8 @@ describe("smallet", () => {
       // Specify the index for deriving the wallet address
       const index = 0;
       // Find the derived wallet address using the specified index
-      const [derivedWalletKey] = await findWalletDerivedAddress(key, index);
+      const [derivedWalletKey, derivedWalletBump] =
+        await findWalletDerivedAddress(key, index);
       // Transfer lamports from the provider's wallet to the derived wallet
       const tx1 = new TransactionEnvelope(provider, [
         SystemProgram.transfer({
@@ -426,11 +447,15 @@ describe("smallet", () => {
         toPubkey: receiver,
         lamports: LAMPORTS_PER_SOL,
       });
-      // Create a new transaction and transaction key for the transfer instruction
-      const { transactionKey, tx: tx2 } = await smalletWrapper.newTransaction({
-        proposer: provider.wallet.publicKey,
-        instructions: [ix],
-      });
+      // Propose the transfer, declaring the derived wallet as its signer
+      const { transactionKey, tx: tx2 } =
+        await smalletWrapper.createTransactionV2({
+          proposer: provider.wallet.publicKey,
+          instructions: [ix],
+          pdaSigners: [
+            { derived: { index: new BN(index), bump: derivedWalletBump } },
+          ],
+        });
       await expectTX(
         tx2,
         "queue transaction to transfer lamports out of smallet"
@@ -464,6 +489,29 @@ describe("smallet", () => {
     ];
     let smalletWrapper: SmalletWrapper;
 
+    // Creates the policy of the owner invoker at `index` through a transaction of the smallet
+    const createPolicy = async (
+      index: number,
+      allowedInstructions: {
+        programId: web3.PublicKey;
+        discriminator: Buffer;
+      }[]
+    ) => {
+      const { transactionKey, tx } =
+        await smalletWrapper.newTransactionFromEnvelope({
+          tx: smalletWrapper.createInvokerPolicy({
+            index,
+            owners,
+            allowedInstructions,
+          }),
+        });
+      await expectTX(tx, "propose invoker policy").to.be.fulfilled;
+      await expectTX(
+        await smalletWrapper.executeTransaction({ transactionKey }),
+        "create invoker policy"
+      ).to.be.fulfilled;
+    };
+
     beforeEach(async () => {
       // Create a new smallet with specified parameters
       const { smalletWrapper: wrapperInner, tx } = await sdk.newSmallet({
@@ -473,6 +521,13 @@ describe("smallet", () => {
       });
       await expectTX(tx, "create new smallet").to.be.fulfilled;
       smalletWrapper = wrapperInner;
+      // Allow the owner invoker at index 5 to transfer lamports
+      await createPolicy(5, [
+        {
+          programId: SystemProgram.programId,
+          discriminator: Buffer.from([2, 0, 0, 0]),
+        },
+      ]);
     });
     // Test function to invoke 1 of N
     it("should invoke 1 of N", async () => {
@@ -494,15 +549,15 @@ describe("smallet", () => {
         }),
       });
       await expectTX(invokeTX, "transfer lamports to smallet").to.be.fulfilled;
-      // Create a subaccount info for the invoker key associated with the wrong smallet
-      await expectTX(
-        await sdk.createSubaccountInfo({
+      // Creating a subaccount info for the invoker key as a smallet is rejected
+      await expect(
+        sdk.createSubaccountInfo({
           smallet: invokerKey,
           index,
           type: "ownerInvoker",
         }),
         "create wrong subaccount info"
-      ).to.be.fulfilled;
+      ).to.be.rejected;
       // Find the subaccount info address for the invoker key
       const [infoKey] = await findSubaccountInfoAddress(invokerKey);
       // Fetch the subaccount info and expect it to be null
@@ -517,6 +572,7 @@ describe("smallet", () => {
           smallet: smalletWrapper.key,
           index,
           type: "ownerInvoker",
+          label: "invoker",
         }),
         "create subaccount info"
       ).to.be.fulfilled;
@@ -527,6 +583,8 @@ describe("smallet", () => {
       expect(info2.index).to.bignumber.eq(index.toString());
       expect(info2.smallet).to.eqAddress(smalletWrapper.key);
       expect(info2.subaccountType).to.deep.eq({ ownerInvoker: {} });
+      expect(info2.label).to.eq("invoker");
+      expect(info2.registryIndex).to.bignumber.eq("0");
     });
     // Test function to invoke 1 of N (v2)
     it("should invoke 1 of N (v2)", async () => {
@@ -548,15 +606,15 @@ describe("smallet", () => {
         }),
       });
       await expectTX(invokeTX, "transfer lamports to smallet").to.be.fulfilled;
-      // Create a subaccount info for the invoker key associated with the wrong smallet
-      await expectTX(
-        await sdk.createSubaccountInfo({
+      // Creating a subaccount info for the invoker key as a smallet is rejected
+      await expect(
+        sdk.createSubaccountInfo({
           smallet: invokerKey,
           index,
           type: "ownerInvoker",
         }),
         "create wrong subaccount info"
-      ).to.be.fulfilled;
+      ).to.be.rejected;
       // Find the subaccount info address for the invoker key
       const [infoKey] = await findSubaccountInfoAddress(invokerKey);
       // Fetch the subaccount info and expect it to be null
@@ -571,6 +629,7 @@ describe("smallet", () => {
           smallet: smalletWrapper.key,
           index,
           type: "ownerInvoker",
+          label: "invoker",
         }),
         "create subaccount info"
       ).to.be.fulfilled;
@@ -581,6 +640,8 @@ describe("smallet", () => {
       expect(info2.index).to.bignumber.eq(index.toString());
       expect(info2.smallet).to.eqAddress(smalletWrapper.key);
       expect(info2.subaccountType).to.deep.eq({ ownerInvoker: {} });
+      expect(info2.label).to.eq("invoker");
+      expect(info2.registryIndex).to.bignumber.eq("0");
     });
     // Test function to invoke large TX (v2)
     it("invoke large TX (v2)", async () => {
@@ -609,6 +670,7 @@ describe("smallet", () => {
           accounts: {
             smallet: smalletWrapper.key,
             owner: ownerA.publicKey,
+            policy: getInvokerPolicyAddress(smalletWrapper.key, index),
           },
           remainingAccounts: [
             {
@@ -642,15 +704,15 @@ describe("smallet", () => {
         [ownerA]
       );
       await expectTX(tx, "transfer lamports to smallet").to.be.fulfilled;
-      // Create a subaccount info for the invoker key associated with the wrong smallet
-      await expectTX(
-        await sdk.createSubaccountInfo({
+      // Creating a subaccount info for the invoker key as a smallet is rejected
+      await expect(
+        sdk.createSubaccountInfo({
           smallet: invokerKey,
           index,
           type: "ownerInvoker",
         }),
         "create wrong subaccount info"
-      ).to.be.fulfilled;
+      ).to.be.rejected;
       // Find the subaccount info address for the invoker key
       const [infoKey] = await findSubaccountInfoAddress(invokerKey);
       // Fetch the subaccount info and expect it to be null
@@ -665,6 +727,7 @@ describe("smallet", () => {
           smallet: smalletWrapper.key,
           index,
           type: "ownerInvoker",
+          label: "invoker",
         }),
         "create subaccount info"
       ).to.be.fulfilled;
@@ -675,6 +738,8 @@ describe("smallet", () => {
       expect(info2.index).to.bignumber.eq(index.toString());
       expect(info2.smallet).to.eqAddress(smalletWrapper.key);
       expect(info2.subaccountType).to.deep.eq({ ownerInvoker: {} });
+      expect(info2.label).to.eq("invoker");
+      expect(info2.registryIndex).to.bignumber.eq("0");
     });
 
     it("invalid invoker should fail (v2)", async () => {
@@ -684,6 +749,13 @@ describe("smallet", () => {
       const [invokerKey] = await smalletWrapper.findOwnerInvokerAddress(index);
       // Create an instruction to execute a memo with the invoker key as a signer
       const instructionToExecute = createMemoInstruction("hello", [invokerKey]);
+      // Allow the owner invoker to invoke the memo program
+      await createPolicy(index, [
+        {
+          programId: instructionToExecute.programId,
+          discriminator: Buffer.alloc(0),
+        },
+      ]);
       // Generate a fake invoker key and its associated invoker bump value
       const [fakeInvoker, invokerBump] = [Keypair.generate(), 254];
       const fakeInvokerKey = fakeInvoker.publicKey;
@@ -697,6 +769,7 @@ describe("smallet", () => {
           accounts: {
             smallet: smalletWrapper.key,
             owner: ownerA.publicKey,
+            policy: getInvokerPolicyAddress(smalletWrapper.key, index),
           },
           remainingAccounts: [
             {